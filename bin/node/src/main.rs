@@ -3,20 +3,38 @@
 #![allow(clippy::similar_names)]
 #![allow(clippy::items_after_statements)]
 
-use std::str::FromStr;
+use std::{net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{
+    utils::{format_units, parse_units},
+    Address, U256,
+};
 use alloy_signer_local::PrivateKeySigner;
-use anyhow::{anyhow, Result};
-use clap::Parser;
+use anyhow::{anyhow, Context, Result};
+use clap::{CommandFactory, Parser, ValueEnum};
+use clap_complete::Shell;
 use rustls::crypto::ring;
+use serde::{Deserialize, Serialize};
 use tabled::{settings::Style, Table, Tabled};
-use tracing::info;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
-use sp1_sdk::{include_elf, Elf, SP1Stdin};
-use spn_calibrator::{Calibrator, SinglePassCalibrator};
-use spn_network_types::prover_network_client::ProverNetworkClient;
-use spn_node_core::{Node, NodeContext, SerialBidder, SerialContext, SerialMonitor, SerialProver};
+use sp1_sdk::{include_elf, Elf, SP1ProofMode, SP1Stdin};
+use spn_artifacts::ArtifactCache;
+use spn_calibrator::{
+    Calibrator, CalibratorMetrics, DeviceCalibration, DownloadCalibrator, MultiGpuCalibrator,
+    MultiPassCalibrator, SinglePassCalibrator,
+};
+use spn_metrics::health::HealthServer;
+use spn_network_types::{
+    FulfillmentStatus, GetDelegationRequest, GetFilteredProofRequestsRequest, ProofMode,
+};
+use spn_node_core::{
+    check_readiness, Node, NodeContext, ProvingOrder, SerialBidder, SerialContext, SerialMonitor,
+    SerialProver, VramPreflight, SP1_NETWORK_VERSION,
+};
+use spn_vapp_core::fee::request_cost;
 
 /// The CLI application that defines all available commands.
 #[derive(Parser)]
@@ -26,6 +44,13 @@ enum Args {
     Calibrate(CalibrateArgs),
     /// Run the prover with previously benchmarked parameters.  
     Prove(ProveArgs),
+    /// Show the balance, delegation and recent requests of a prover.
+    Status(StatusArgs),
+    /// Estimate the cost of a proof request.
+    Estimate(EstimateArgs),
+    /// Print the completion script of the CLI for a shell.
+    #[command(hide = true)]
+    Completions(CompletionsArgs),
 }
 
 /// The arguments for the `calibrate` command.
@@ -43,26 +68,479 @@ struct CalibrateArgs {
     /// The price of $PROVE in USD.
     #[arg(long, help = "Price of $PROVE in USD, e.g. 1.00")]
     prove_price: f64,
+    /// The number of proving runs to average over.
+    #[arg(long, default_value_t = 1, help = "Number of proving runs to average over, e.g. 5")]
+    passes: usize,
+    /// The number of untimed proving runs before timing begins.
+    #[arg(long, default_value_t = 1, help = "Number of untimed warmup runs, e.g. 1")]
+    warmup: usize,
+    /// The proof mode to calibrate for.
+    #[arg(long, value_enum, default_value_t = ProofModeArg::Compressed)]
+    proof_mode: ProofModeArg,
+    /// Whether to calibrate every GPU of the host concurrently and report their total throughput.
+    #[arg(long, help = "Calibrate all GPUs concurrently, e.g. on a multi-GPU host")]
+    multi_gpu: bool,
+    /// The URI of a representative stdin artifact to time the download of.
+    #[arg(
+        long,
+        requires = "download_pgus",
+        help = "URI of a representative stdin to download, e.g. s3://bucket/stdins/artifact_01..."
+    )]
+    download_uri: Option<String>,
+    /// The prover gas of a request whose artifacts are the size of the downloaded one.
+    #[arg(
+        long,
+        requires = "download_uri",
+        help = "PGUs of a request with that stdin, e.g. 1000000000"
+    )]
+    download_pgus: Option<u64>,
+    /// The path to save the calibration results to.
+    #[arg(long, help = "Path to save the calibration results to, e.g. calibration.json")]
+    calibration_file: Option<PathBuf>,
+    /// Whether to reuse the calibration results saved at the calibration file.
+    #[arg(long, requires = "calibration_file", help = "Reuse the saved calibration if present")]
+    reuse_calibration: bool,
+    /// Whether to print the calibration results as JSON instead of tables.
+    #[arg(long, help = "Print the results as JSON, e.g. to pass them to the prove command")]
+    json: bool,
+}
+
+/// The calibration results printed by the `calibrate` command with `--json`.
+#[derive(Debug, Serialize)]
+struct CalibrationOutput {
+    /// The throughput to pass to the `prove` command, in PGUs per second.
+    throughput: f64,
+    /// The bid to pass to the `prove` command, in $PROVE per billion PGUs.
+    bid: f64,
+    /// The raw calibration metrics.
+    #[serde(flatten)]
+    metrics: CalibratorMetrics,
+    /// The calibration of each GPU, if they were calibrated concurrently.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    devices: Vec<DeviceCalibration>,
+}
+
+/// The proof modes that the prover can be calibrated for and bid on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ProofModeArg {
+    /// A core proof.
+    Core,
+    /// A compressed proof.
+    Compressed,
+    /// A PLONK proof verifiable on EVM chains.
+    Plonk,
+    /// A Groth16 proof verifiable on EVM chains.
+    Groth16,
+}
+
+impl From<ProofModeArg> for SP1ProofMode {
+    fn from(mode: ProofModeArg) -> Self {
+        match mode {
+            ProofModeArg::Core => SP1ProofMode::Core,
+            ProofModeArg::Compressed => SP1ProofMode::Compressed,
+            ProofModeArg::Plonk => SP1ProofMode::Plonk,
+            ProofModeArg::Groth16 => SP1ProofMode::Groth16,
+        }
+    }
+}
+
+impl From<ProofModeArg> for ProofMode {
+    fn from(mode: ProofModeArg) -> Self {
+        match mode {
+            ProofModeArg::Core => ProofMode::Core,
+            ProofModeArg::Compressed => ProofMode::Compressed,
+            ProofModeArg::Plonk => ProofMode::Plonk,
+            ProofModeArg::Groth16 => ProofMode::Groth16,
+        }
+    }
+}
+
+/// The orders in which the prover can prove its assigned requests.
+#[derive(Debug, Clone, Copy, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ProvingOrderArg {
+    /// The soonest deadline first.
+    #[default]
+    Deadline,
+    /// The highest payment first.
+    Profit,
+}
+
+impl From<ProvingOrderArg> for ProvingOrder {
+    fn from(order: ProvingOrderArg) -> Self {
+        match order {
+            ProvingOrderArg::Deadline => ProvingOrder::Deadline,
+            ProvingOrderArg::Profit => ProvingOrder::Profit,
+        }
+    }
 }
 
 /// The arguments for the `prove` command.
-#[derive(Debug, Clone, Parser)]
+///
+/// Every argument except `--config` can also be set in the config file, keyed by its name in
+/// snake case (e.g. `rpc_url`). Arguments given on the command line override the file, so secrets
+/// like the private key can be kept out of the shell history and the process list.
+#[derive(Debug, Clone, Parser, Serialize)]
 struct ProveArgs {
+    /// The path to a TOML or JSON file to load the arguments from.
+    #[arg(long)]
+    #[serde(skip)]
+    config: Option<PathBuf>,
     /// The RPC URL for the network.
     #[arg(long)]
-    rpc_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rpc_url: Option<String>,
     /// The amount of proving gas units (PGUs) per second your prover can process.
     #[arg(long)]
-    throughput: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    throughput: Option<f64>,
     /// The $PROVE price per billion proving gas units (PGUs) your prover is willing to bid.
     #[arg(long)]
-    bid: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bid: Option<f64>,
     /// The private key for the prover.
+    ///
+    /// Prefer `--keystore` or `--private-key-env`, since this exposes the key in the process
+    /// list and shell history.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    private_key: Option<String>,
+    /// The path to an encrypted JSON keystore holding the private key for the prover.
+    ///
+    /// The password is read from the `SPN_KEYSTORE_PASSWORD` environment variable if it is set,
+    /// and prompted for otherwise.
     #[arg(long)]
-    private_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keystore: Option<PathBuf>,
+    /// The name of the environment variable holding the private key for the prover.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    private_key_env: Option<String>,
     /// The address of the prover.
     #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prover: Option<Address>,
+    /// The maximum number of requests to prove at once [default: 1].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    concurrency: Option<usize>,
+    /// The interval in seconds at which the node polls the network for requests [default: 3].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    poll_interval: Option<u64>,
+    /// The interval in seconds at which the node records metrics [default: 30].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    monitor_interval: Option<u64>,
+    /// Whether to record the latency and errors of each request to the network as metrics.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    rpc_metrics: bool,
+    /// Whether to accept responses from the network compressed with gzip. Responses are only
+    /// compressed if the network supports it.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    rpc_compression: bool,
+    /// The maximum size in MiB of a message sent to or received from the network [default: 256].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_message_size: Option<usize>,
+    /// Whether to verify each proof locally before submitting it to the network.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    verify_proofs: bool,
+    /// The directory in which to cache downloaded programs and stdins, if any.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    artifact_cache_dir: Option<PathBuf>,
+    /// The maximum size in MiB of the artifact cache [default: 10240].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    artifact_cache_size: Option<u64>,
+    /// Whether to stream new requests from the network and bid on them as they appear, falling
+    /// back to polling while streaming is unavailable.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream_requests: bool,
+    /// The comma-separated proof modes to bid on, e.g. to skip the modes the hardware can't
+    /// produce in time [default: all].
+    #[arg(long, value_enum, value_delimiter = ',')]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_modes: Option<Vec<ProofModeArg>>,
+    /// The estimated GPU memory in bytes needed per cycle of a request, which enables skipping the
+    /// requests that don't fit in the GPU memory.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vram_bytes_per_cycle: Option<f64>,
+    /// The estimated GPU memory in MiB needed by any request, on top of the memory per cycle
+    /// [default: 8192].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vram_base_mib: Option<u64>,
+    /// The GPU memory in MiB kept free for the driver and other processes, which requests can't
+    /// use [default: 1024].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vram_headroom_mib: Option<u64>,
+    /// Whether to log the bids and proofs the node would submit instead of sending them to the
+    /// network, e.g. to check that a new prover works. Only requests already assigned to the
+    /// prover are proven.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
+    /// The address to serve the `/healthz` and `/readyz` endpoints on, e.g. `0.0.0.0:8080`. The
+    /// node is ready while the network is reachable and the balance is above the minimum.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    health_addr: Option<SocketAddr>,
+    /// The order in which to prove the assigned requests when there are more than the concurrency
+    /// [default: deadline].
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proving_order: Option<ProvingOrderArg>,
+    /// The minimum balance in $PROVE required to bid, below which the node waits to be funded.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_balance: Option<String>,
+    /// The interval in seconds at which the balance is re-checked while it is above the minimum
+    /// [default: 60].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance_check_interval: Option<u64>,
+    /// The bearer token used to authenticate with the network, if it requires one.
+    ///
+    /// Prefer setting it in the `SPN_AUTH_TOKEN` environment variable or the config file, since
+    /// the flag exposes it in the process list and shell history.
+    #[arg(long, env = AUTH_TOKEN_ENV, hide_env_values = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_token: Option<String>,
+    /// Whether to send the node's SP1 version in the `x-client-version` header of each request,
+    /// for networks that reject incompatible clients.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    send_client_version: bool,
+    /// The timeout in seconds for connecting to the network [default: 15].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    connect_timeout: Option<u64>,
+    /// The interval in seconds of TCP keep-alive probes, or 0 to disable them [default: 30].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tcp_keepalive: Option<u64>,
+    /// The interval in seconds at which HTTP/2 keep-alive pings are sent to the network
+    /// [default: 15].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keep_alive_interval: Option<u64>,
+    /// The timeout in seconds for replies to HTTP/2 keep-alive pings [default: 15].
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keep_alive_timeout: Option<u64>,
+}
+
+impl ProveArgs {
+    /// Load the settings of the `prove` command from the config file, if any, overridden by the
+    /// arguments given on the command line.
+    fn settings(&self) -> Result<ProveSettings> {
+        let mut builder = config::Config::builder();
+        if let Some(path) = &self.config {
+            builder = builder.add_source(config::File::from(path.as_path()));
+        }
+        builder
+            .add_source(config::Config::try_from(self)?)
+            .build()
+            .context("failed to load the config")?
+            .try_deserialize()
+            .context("invalid settings, set them with flags or in the config file")
+    }
+}
+
+/// The resolved settings of the `prove` command.
+///
+/// See [`ProveArgs`] for the meaning of each setting.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProveSettings {
+    rpc_url: String,
+    throughput: f64,
+    bid: f64,
+    #[serde(default)]
+    private_key: Option<String>,
+    #[serde(default)]
+    keystore: Option<PathBuf>,
+    #[serde(default)]
+    private_key_env: Option<String>,
     prover: Address,
+    #[serde(default = "default_concurrency")]
+    concurrency: usize,
+    #[serde(default = "default_poll_interval")]
+    poll_interval: u64,
+    #[serde(default = "default_monitor_interval")]
+    monitor_interval: u64,
+    #[serde(default)]
+    rpc_metrics: bool,
+    #[serde(default)]
+    rpc_compression: bool,
+    #[serde(default = "default_max_message_size")]
+    max_message_size: usize,
+    #[serde(default)]
+    verify_proofs: bool,
+    #[serde(default)]
+    artifact_cache_dir: Option<PathBuf>,
+    #[serde(default = "default_artifact_cache_size")]
+    artifact_cache_size: u64,
+    #[serde(default)]
+    stream_requests: bool,
+    #[serde(default)]
+    proof_modes: Option<Vec<ProofModeArg>>,
+    #[serde(default)]
+    vram_bytes_per_cycle: Option<f64>,
+    #[serde(default = "default_vram_base_mib")]
+    vram_base_mib: u64,
+    #[serde(default = "default_vram_headroom_mib")]
+    vram_headroom_mib: u64,
+    #[serde(default)]
+    dry_run: bool,
+    #[serde(default)]
+    health_addr: Option<SocketAddr>,
+    #[serde(default)]
+    proving_order: ProvingOrderArg,
+    #[serde(default)]
+    minimum_balance: Option<String>,
+    #[serde(default = "default_balance_check_interval")]
+    balance_check_interval: u64,
+    #[serde(default)]
+    auth_token: Option<String>,
+    #[serde(default)]
+    send_client_version: bool,
+    #[serde(default = "default_timeout")]
+    connect_timeout: u64,
+    #[serde(default = "default_tcp_keepalive")]
+    tcp_keepalive: u64,
+    #[serde(default = "default_timeout")]
+    keep_alive_interval: u64,
+    #[serde(default = "default_timeout")]
+    keep_alive_timeout: u64,
+}
+
+impl ProveSettings {
+    /// Load the signer of the prover from the keystore, the environment variable or the raw
+    /// private key, in that order of preference.
+    fn signer(&self) -> Result<PrivateKeySigner> {
+        let safer_source = self.keystore.is_some() || self.private_key_env.is_some();
+        if safer_source && self.private_key.is_some() {
+            warn!("Ignoring the raw private key in favor of the keystore or environment variable.");
+        }
+
+        if let Some(path) = &self.keystore {
+            let password = match std::env::var(KEYSTORE_PASSWORD_ENV) {
+                Ok(password) => password,
+                Err(_) => rpassword::prompt_password("Enter the keystore password: ")
+                    .context("failed to read the keystore password")?,
+            };
+            return PrivateKeySigner::decrypt_keystore(path, password)
+                .with_context(|| format!("failed to decrypt the keystore {}", path.display()));
+        }
+        if let Some(var) = &self.private_key_env {
+            let key = std::env::var(var)
+                .with_context(|| format!("the environment variable {var} is not set"))?;
+            return PrivateKeySigner::from_str(key.trim())
+                .with_context(|| format!("the environment variable {var} is not a private key"));
+        }
+        let key = self
+            .private_key
+            .as_deref()
+            .context("no private key, set --keystore, --private-key-env or --private-key")?;
+        Ok(PrivateKeySigner::from_str(key)?)
+    }
+}
+
+/// The environment variable that holds the password of the keystore, if it shouldn't be prompted
+/// for.
+const KEYSTORE_PASSWORD_ENV: &str = "SPN_KEYSTORE_PASSWORD";
+
+/// The environment variable that holds the bearer token used to authenticate with the network.
+const AUTH_TOKEN_ENV: &str = "SPN_AUTH_TOKEN";
+
+fn default_concurrency() -> usize {
+    1
+}
+
+fn default_poll_interval() -> u64 {
+    3
+}
+
+fn default_monitor_interval() -> u64 {
+    30
+}
+
+fn default_max_message_size() -> usize {
+    spn_rpc::DEFAULT_MAX_DECODING_MESSAGE_SIZE / (1024 * 1024)
+}
+
+fn default_artifact_cache_size() -> u64 {
+    10 * 1024
+}
+
+fn default_vram_base_mib() -> u64 {
+    spn_node_core::DEFAULT_VRAM_BASE_MIB
+}
+
+fn default_vram_headroom_mib() -> u64 {
+    spn_node_core::DEFAULT_VRAM_HEADROOM_MIB
+}
+
+fn default_balance_check_interval() -> u64 {
+    60
+}
+
+fn default_timeout() -> u64 {
+    15
+}
+
+fn default_tcp_keepalive() -> u64 {
+    30
+}
+
+/// The arguments for the `status` command.
+#[derive(Debug, Clone, Parser)]
+struct StatusArgs {
+    /// The RPC URL for the network.
+    #[arg(long)]
+    rpc_url: String,
+    /// The address of the prover, or of one of its signers.
+    #[arg(long)]
+    address: Address,
+    /// Whether to send the node's SP1 version in the `x-client-version` header of each request,
+    /// for networks that reject incompatible clients.
+    #[arg(long)]
+    send_client_version: bool,
+}
+
+/// The arguments for the `estimate` command.
+#[derive(Debug, Clone, Parser)]
+struct EstimateArgs {
+    /// The amount of proving gas units (PGUs) the request is expected to use.
+    #[arg(long)]
+    gas: u64,
+    /// The $PROVE price per billion proving gas units (PGUs) of the request.
+    #[arg(long, help = "Price in $PROVE per 1B PGUs, e.g. 0.5")]
+    price: String,
+    /// The base fee of the request in $PROVE.
+    #[arg(long, default_value = "0", help = "Base fee in $PROVE, e.g. 0.1")]
+    base_fee: String,
+    /// The price of $PROVE in USD, to also show the cost in USD.
+    #[arg(long, help = "Price of $PROVE in USD, e.g. 1.00")]
+    prove_price: Option<f64>,
+}
+
+/// The arguments for the `completions` command.
+#[derive(Debug, Clone, Parser)]
+struct CompletionsArgs {
+    /// The shell to print the completion script for.
+    #[arg(value_enum)]
+    shell: Shell,
 }
 
 /// The main entry point for the CLI.
@@ -74,9 +552,11 @@ async fn main() -> Result<()> {
     // Parse the arguments.
     let cli = Args::parse();
 
-    // Print the header.
-    let header = include_str!("./header.txt");
-    println!("{header}");
+    // Print the header, unless the output is meant to be parsed.
+    if !matches!(&cli, Args::Calibrate(CalibrateArgs { json: true, .. }) | Args::Completions(_)) {
+        let header = include_str!("./header.txt");
+        println!("{header}");
+    }
 
     // Run the command.
     match cli {
@@ -111,6 +591,11 @@ async fn main() -> Result<()> {
                     name: "USD Price of $PROVE".to_string(),
                     value: format!("${:.2}", args.prove_price),
                 },
+                ParametersTable {
+                    name: "Proof Mode".to_string(),
+                    value: format!("{:?}", args.proof_mode),
+                },
+                ParametersTable { name: "Warmup Runs".to_string(), value: args.warmup.to_string() },
             ];
 
             // Create and style the parameters table.
@@ -118,25 +603,146 @@ async fn main() -> Result<()> {
             params_table.with(Style::modern());
 
             // Print parameters with a title.
-            println!("\nParameters:");
-            println!("{params_table}\n");
+            if !args.json {
+                println!("\nParameters:");
+                println!("{params_table}\n");
+            }
 
             // Create the input stream.
             let n: u64 = 20;
             let mut stdin = SP1Stdin::new();
             stdin.write(&n);
 
+            // Reuse a saved calibration if requested.
+            let saved = match &args.calibration_file {
+                Some(path) if args.reuse_calibration && path.exists() => {
+                    let metrics = CalibratorMetrics::load(path)?;
+                    if !args.json {
+                        println!("Reusing calibration from {}...", path.display());
+                    }
+                    if metrics.is_stale() {
+                        let warning = format!(
+                            "Warning: the saved calibration was run on different hardware ({}), consider recalibrating.",
+                            metrics.hardware
+                        );
+                        if args.json {
+                            eprintln!("{warning}");
+                        } else {
+                            println!("{warning}");
+                        }
+                    }
+                    Some(metrics)
+                }
+                _ => None,
+            };
+
             // Run the calibrator to get the metrics.
-            println!("Starting calibration...");
-            let calibrator = SinglePassCalibrator::new(
-                SPN_FIBONACCI_ELF.to_vec(),
-                stdin,
-                args.usd_cost_per_hour,
-                args.utilization_rate,
-                args.profit_margin,
-            );
-            let metrics =
-                calibrator.calibrate().await.map_err(|e| anyhow!("failed to calibrate: {}", e))?;
+            let reused = saved.is_some();
+            let mut devices = Vec::new();
+            let metrics = if let Some(metrics) = saved {
+                metrics
+            } else if args.multi_gpu {
+                // Calibrate each GPU with a copy of this command pinned to it, since CUDA only
+                // picks the devices of a process once.
+                let gpus = spn_utils::detect_gpus();
+                if !args.json {
+                    println!("Starting calibration on {} GPUs...", gpus.len());
+                }
+                let proof_mode =
+                    args.proof_mode.to_possible_value().expect("every proof mode has a name");
+                let device_args = [
+                    "calibrate".to_string(),
+                    format!("--usd-cost-per-hour={}", args.usd_cost_per_hour),
+                    format!("--utilization-rate={}", args.utilization_rate),
+                    format!("--profit-margin={}", args.profit_margin),
+                    format!("--prove-price={}", args.prove_price),
+                    format!("--passes={}", args.passes),
+                    format!("--warmup={}", args.warmup),
+                    format!("--proof-mode={}", proof_mode.get_name()),
+                    "--json".to_string(),
+                ];
+                let program =
+                    std::env::current_exe().context("failed to locate the node executable")?;
+                let calibrator = MultiGpuCalibrator::new(
+                    program,
+                    device_args,
+                    (0..gpus.len()).collect(),
+                    args.usd_cost_per_hour,
+                    args.utilization_rate,
+                    args.profit_margin,
+                );
+                devices = calibrator
+                    .calibrate_devices()
+                    .await
+                    .map_err(|e| anyhow!("failed to calibrate: {}", e))?;
+                calibrator.aggregate(&devices)
+            } else if args.passes > 1 {
+                if !args.json {
+                    println!("Starting calibration...");
+                }
+                let calibrator = MultiPassCalibrator::new(
+                    SPN_FIBONACCI_ELF.to_vec(),
+                    stdin,
+                    args.usd_cost_per_hour,
+                    args.utilization_rate,
+                    args.profit_margin,
+                    args.passes,
+                )
+                .with_proof_mode(args.proof_mode.into())
+                .with_warmup(args.warmup);
+                calibrator.calibrate().await.map_err(|e| anyhow!("failed to calibrate: {}", e))?
+            } else {
+                if !args.json {
+                    println!("Starting calibration...");
+                }
+                let calibrator = SinglePassCalibrator::new(
+                    SPN_FIBONACCI_ELF.to_vec(),
+                    stdin,
+                    args.usd_cost_per_hour,
+                    args.utilization_rate,
+                    args.profit_margin,
+                )
+                .with_proof_mode(args.proof_mode.into())
+                .with_warmup(args.warmup);
+                calibrator.calibrate().await.map_err(|e| anyhow!("failed to calibrate: {}", e))?
+            };
+
+            // Account for the time to download the artifacts of requests if requested.
+            let metrics = match (&args.download_uri, args.download_pgus) {
+                (Some(uri), Some(pgus)) if !reused => {
+                    if !args.json {
+                        println!("Starting download calibration...");
+                    }
+                    let download = DownloadCalibrator::new(uri)
+                        .with_passes(args.passes)
+                        .calibrate()
+                        .await
+                        .map_err(|e| anyhow!("failed to calibrate downloads: {}", e))?;
+                    metrics.with_download(download, pgus)
+                }
+                _ => metrics,
+            };
+
+            // Save the calibration if requested.
+            if let Some(path) = args.calibration_file.as_ref().filter(|_| !reused) {
+                metrics.save(path)?;
+                if !args.json {
+                    println!("Saved calibration to {}.", path.display());
+                }
+            }
+
+            // Print the results as JSON if requested.
+            let bid = metrics.pgu_price * args.prove_price * 1_000_000_000.0;
+            if args.json {
+                let output = CalibrationOutput {
+                    throughput: metrics.pgus_per_second.round(),
+                    bid: (bid * 100.0).round() / 100.0,
+                    metrics,
+                    devices,
+                };
+                println!("{}", serde_json::to_string_pretty(&output)?);
+                return Ok(());
+            }
 
             // Create a table for the calibration results.
             #[derive(Tabled)]
@@ -149,19 +755,42 @@ async fn main() -> Result<()> {
 
             // Create results table data.
             let pgus_per_second = metrics.pgus_per_second.round();
-            let results_data = vec![
+            let mut results_data = vec![
                 CalibrationResultsTable {
                     name: "Estimated Throughput".to_string(),
                     value: format!("{pgus_per_second} PGUs/second"),
                 },
                 CalibrationResultsTable {
-                    name: "Estimated Bid Price".to_string(),
-                    value: format!(
-                        "{:.2} $PROVE per 1B PGUs",
-                        metrics.pgu_price * args.prove_price * 1_000_000_000.0
+                    name: "Throughput Std. Deviation".to_string(),
+                    value: metrics.std_dev.map_or_else(
+                        || "n/a".to_string(),
+                        |std_dev| format!("{} PGUs/second", std_dev.round()),
+                    ),
+                },
+                CalibrationResultsTable {
+                    name: "Throughput 95% Confidence Interval".to_string(),
+                    value: metrics.confidence_interval.map_or_else(
+                        || "n/a".to_string(),
+                        |(low, high)| format!("{} - {} PGUs/second", low.round(), high.round()),
                     ),
                 },
+                CalibrationResultsTable {
+                    name: "Estimated Bid Price".to_string(),
+                    value: format!("{bid:.2} $PROVE per 1B PGUs"),
+                },
             ];
+            if let (Some(download_bytes_per_second), Some(end_to_end_pgus_per_second)) =
+                (metrics.download_bytes_per_second, metrics.end_to_end_pgus_per_second)
+            {
+                results_data.push(CalibrationResultsTable {
+                    name: "Download Throughput".to_string(),
+                    value: format!("{:.2} MB/second", download_bytes_per_second / 1_000_000.0),
+                });
+                results_data.push(CalibrationResultsTable {
+                    name: "End-to-End Throughput".to_string(),
+                    value: format!("{} PGUs/second", end_to_end_pgus_per_second.round()),
+                });
+            }
 
             // Create and style the results table.
             let mut results_table = Table::new(results_data);
@@ -170,25 +799,143 @@ async fn main() -> Result<()> {
             // Print results with a title.
             println!("\nCalibration Results:");
             println!("{results_table}\n");
+
+            // Print the throughput of each GPU if they were calibrated concurrently.
+            if !devices.is_empty() {
+                #[derive(Tabled)]
+                struct DeviceResultsTable {
+                    #[tabled(rename = "GPU")]
+                    device: usize,
+                    #[tabled(rename = "Throughput")]
+                    throughput: String,
+                    #[tabled(rename = "Std. Deviation")]
+                    std_dev: String,
+                }
+
+                let mut devices_table =
+                    Table::new(devices.iter().map(|device| DeviceResultsTable {
+                        device: device.device,
+                        throughput: format!(
+                            "{} PGUs/second",
+                            device.metrics.pgus_per_second.round()
+                        ),
+                        std_dev: device.metrics.std_dev.map_or_else(
+                            || "n/a".to_string(),
+                            |std_dev| format!("{} PGUs/second", std_dev.round()),
+                        ),
+                    }));
+                devices_table.with(Style::modern());
+
+                println!("GPU Results:");
+                println!("{devices_table}\n");
+            }
         }
         Args::Prove(args) => {
-            spn_utils::init_logger(spn_utils::LogFormat::Pretty);
+            let _logger =
+                spn_utils::LoggerBuilder::new(spn_utils::LogFormat::Pretty).with_redaction().init();
+
+            // Load the settings from the config file and the arguments.
+            let args = args.settings()?;
 
             // Setup the connection to the network.
-            let endpoint = spn_rpc::configure_endpoint(&args.rpc_url)?;
-            let network = ProverNetworkClient::connect(endpoint).await?;
+            let endpoint_config = spn_rpc::EndpointConfig::default()
+                .with_connect_timeout(Duration::from_secs(args.connect_timeout))
+                .with_tcp_keepalive(
+                    (args.tcp_keepalive > 0).then_some(Duration::from_secs(args.tcp_keepalive)),
+                )
+                .with_keep_alive_interval(Duration::from_secs(args.keep_alive_interval))
+                .with_keep_alive_timeout(Duration::from_secs(args.keep_alive_timeout));
+            let endpoint = spn_rpc::configure_endpoint_with(&args.rpc_url, &endpoint_config)?;
+            let mut auth = spn_rpc::AuthInterceptor::new();
+            if args.send_client_version {
+                auth = auth.with_client_version(SP1_NETWORK_VERSION)?;
+            }
+            if let Some(token) = &args.auth_token {
+                auth = auth.with_bearer_token(token)?;
+            }
+            let max_message_size = args.max_message_size.saturating_mul(1024 * 1024);
+            let mut channel = spn_rpc::NetworkChannel::authenticated(&endpoint, auth)
+                .with_max_decoding_message_size(max_message_size)
+                .with_max_encoding_message_size(max_message_size);
+            if args.rpc_metrics {
+                channel = channel.with_metrics();
+            }
+            if args.rpc_compression {
+                channel = channel.with_compression();
+            }
+            let network = channel.client();
 
             // Setup the signer.
-            let signer = PrivateKeySigner::from_str(&args.private_key)?;
+            let signer = args.signer()?;
 
             // Setup the context.
             let ctx = SerialContext::new(network, signer);
 
+            // Check that the signer is allowed to bid on behalf of the prover, since the network
+            // would otherwise reject every bid.
+            let prover_signers =
+                spn_rpc::fetch_prover_signers(&ctx.network, args.prover.as_slice())
+                    .await
+                    .context("failed to fetch the signers of the prover")?;
+            if prover_signers.is_empty() {
+                return Err(anyhow!("the prover {} does not exist on the network", args.prover));
+            }
+            let prover_signers = prover_signers
+                .iter()
+                .map(|signer| Address::try_from(signer.as_slice()))
+                .collect::<Result<Vec<_>, _>>()
+                .context("the network returned an invalid signer")?;
+            if !prover_signers.contains(&ctx.signer.address()) {
+                return Err(anyhow!(
+                    "the private key of {} is not authorized to sign for the prover {}, expected \
+                     the key of one of {prover_signers:?}",
+                    ctx.signer.address(),
+                    args.prover,
+                ));
+            }
+
             // Setup the bidder.
-            let bidder = SerialBidder::new(U256::from(args.bid), args.throughput, args.prover);
+            let mut bidder = SerialBidder::new(U256::from(args.bid), args.throughput, args.prover)
+                .with_concurrency(args.concurrency);
+            if let Some(modes) = &args.proof_modes {
+                if modes.is_empty() {
+                    return Err(anyhow!("--proof-modes must list at least one proof mode"));
+                }
+                bidder = bidder.with_supported_modes(modes.iter().copied().map(ProofMode::from));
+            }
+            bidder = bidder.with_dry_run(args.dry_run);
+            if let Some(bytes_per_cycle) = args.vram_bytes_per_cycle {
+                let preflight = VramPreflight::new(bytes_per_cycle)
+                    .with_base_mib(args.vram_base_mib)
+                    .with_headroom_mib(args.vram_headroom_mib);
+
+                // Detect the GPUs now, so that bidding never waits on it.
+                match preflight.available_mib() {
+                    Some(available_mib) => {
+                        info!(available_mib = %available_mib, "Detected GPU memory for requests.");
+                    }
+                    None => warn!("No GPU detected, so requests won't be checked for GPU memory."),
+                }
+                bidder = bidder.with_vram_preflight(preflight);
+            }
 
             // Setup the prover
-            let prover = SerialProver::new().await;
+            let mut prover = SerialProver::new()
+                .await
+                .with_concurrency(args.concurrency)
+                .with_proof_verification(args.verify_proofs)
+                .with_dry_run(args.dry_run)
+                .with_proving_order(args.proving_order.into());
+            if args.dry_run {
+                warn!("Running in dry-run mode, bids and proofs will not be submitted to the network.");
+            }
+            if let Some(dir) = &args.artifact_cache_dir {
+                let max_size = args.artifact_cache_size.saturating_mul(1024 * 1024);
+                let cache = ArtifactCache::open(dir, max_size).with_context(|| {
+                    format!("failed to open the artifact cache {}", dir.display())
+                })?;
+                prover = prover.with_artifact_cache(cache);
+            }
 
             // Setup the monitor.
             let monitor = SerialMonitor::new();
@@ -201,11 +948,209 @@ async fn main() -> Result<()> {
                 bid = %args.bid,
                 "Starting Node on Succinct Network..."
             );
-            let node = Node::new(ctx, bidder, prover, monitor);
+            let token = CancellationToken::new();
+            let mut node = Node::new(ctx, bidder, prover, monitor)
+                .with_cancellation_token(token.clone())
+                .with_poll_interval(Duration::from_secs(args.poll_interval))
+                .with_monitor_interval(Duration::from_secs(args.monitor_interval))
+                .with_balance_check_interval(Duration::from_secs(args.balance_check_interval))
+                .with_request_streaming(args.stream_requests);
+            if let Some(minimum_balance) = &args.minimum_balance {
+                let minimum_balance = parse_units(minimum_balance, 18)
+                    .context("invalid minimum balance")?
+                    .get_absolute();
+                node = node.with_minimum_balance(minimum_balance);
+            }
+
+            // Serve the health endpoints until the node stops, if enabled.
+            let (_health_shutdown_tx, health_shutdown_rx) = broadcast::channel(1);
+            if let Some(health_addr) = args.health_addr {
+                let ctx = node.ctx.clone();
+                let minimum_balance = node.minimum_balance;
+                let server = HealthServer::new(health_addr, move || {
+                    let ctx = ctx.clone();
+                    async move {
+                        check_readiness(ctx.as_ref(), minimum_balance)
+                            .await
+                            .map_err(|e| format!("{e:#}"))
+                    }
+                });
+                tokio::spawn(async move {
+                    if let Err(e) = server.serve(health_shutdown_rx).await {
+                        warn!("Failed to serve the health endpoints: {e:?}");
+                    }
+                });
+            }
+
+            // Shut down the node gracefully on ctrl-c.
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    warn!("Received ctrl-c, shutting down node...");
+                    token.cancel();
+                }
+            });
 
             // Run the node.
             node.run().await?;
         }
+        Args::Estimate(args) => {
+            // Convert the prices to wei, rounding the price per PGU down like the network does.
+            let price_per_bpgu =
+                parse_units(&args.price, 18).context("invalid price")?.get_absolute();
+            let price_per_pgu = price_per_bpgu / U256::from(1_000_000_000u64);
+            let base_fee =
+                parse_units(&args.base_fee, 18).context("invalid base fee")?.get_absolute();
+
+            // Calculate the cost the same way the network settles a fulfilled request.
+            let proving_cost = request_cost(price_per_pgu, args.gas, U256::ZERO)
+                .context("the proving cost overflows")?;
+            let total_cost = request_cost(price_per_pgu, args.gas, base_fee)
+                .context("the total cost overflows")?;
+
+            // Create a table for the estimate.
+            #[derive(Tabled)]
+            struct EstimateTable {
+                #[tabled(rename = "Item")]
+                name: String,
+                #[tabled(rename = "Value")]
+                value: String,
+            }
+
+            // Create estimate table data.
+            let mut estimate_data = vec![
+                EstimateTable { name: "Gas".to_string(), value: format!("{} PGUs", args.gas) },
+                EstimateTable {
+                    name: "Price Per PGU".to_string(),
+                    value: format!("{price_per_pgu} wei"),
+                },
+                EstimateTable {
+                    name: "Base Fee".to_string(),
+                    value: format!("{} $PROVE", format_units(base_fee, 18)?),
+                },
+                EstimateTable {
+                    name: "Proving Cost".to_string(),
+                    value: format!("{} $PROVE", format_units(proving_cost, 18)?),
+                },
+                EstimateTable {
+                    name: "Total Cost".to_string(),
+                    value: format!("{} $PROVE", format_units(total_cost, 18)?),
+                },
+            ];
+            if let Some(prove_price) = args.prove_price {
+                let total_cost: f64 = format_units(total_cost, 18)?.parse()?;
+                estimate_data.push(EstimateTable {
+                    name: "Total Cost (USD)".to_string(),
+                    value: format!("${:.2}", total_cost * prove_price),
+                });
+            }
+
+            // Create and style the estimate table.
+            let mut estimate_table = Table::new(estimate_data);
+            estimate_table.with(Style::modern());
+
+            // Print the estimate with a title.
+            println!("\nCost Estimate:");
+            println!("{estimate_table}\n");
+        }
+        Args::Status(args) => {
+            // Setup the connection to the network.
+            let endpoint = spn_rpc::configure_endpoint(&args.rpc_url)?;
+            let mut auth = spn_rpc::AuthInterceptor::new();
+            if args.send_client_version {
+                auth = auth.with_client_version(SP1_NETWORK_VERSION)?;
+            }
+            let network = spn_rpc::NetworkChannel::authenticated(&endpoint, auth).client();
+
+            // Resolve the owner, whose balance and requests belong to the prover.
+            let owner = spn_rpc::fetch_owner(&network, args.address.as_slice()).await?;
+            let balance = spn_rpc::fetch_balance(&network, &owner).await?;
+            let owner = Address::try_from(owner.as_slice())
+                .context("the network returned an invalid owner")?;
+
+            // Fetch the delegated signers, including the additional ones.
+            let delegation = network
+                .clone()
+                .get_delegation(GetDelegationRequest { prover: args.address.to_vec() })
+                .await?
+                .into_inner()
+                .delegation;
+            let delegates = match delegation {
+                Some(delegation) => std::iter::once(&delegation.delegate)
+                    .chain(&delegation.additional_delegates)
+                    .map(|delegate| {
+                        Address::try_from(delegate.as_slice())
+                            .map(|delegate| delegate.to_string())
+                            .context("the network returned an invalid delegate")
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join("\n"),
+                None => "None".to_string(),
+            };
+
+            // Count the requests assigned to the prover, and those fulfilled in the last day.
+            const MAX_REQUESTS: u32 = 100;
+            let count_requests = |status: FulfillmentStatus, from: Option<u64>| {
+                let mut network = network.clone();
+                let owner = owner.to_vec();
+                async move {
+                    let requests = network
+                        .get_filtered_proof_requests(GetFilteredProofRequestsRequest {
+                            fulfillment_status: Some(status.into()),
+                            fulfiller: Some(owner),
+                            from,
+                            limit: Some(MAX_REQUESTS),
+                            ..Default::default()
+                        })
+                        .await?
+                        .into_inner()
+                        .requests;
+                    let count = requests.len();
+                    Ok::<_, anyhow::Error>(if count >= MAX_REQUESTS as usize {
+                        format!("{count}+")
+                    } else {
+                        count.to_string()
+                    })
+                }
+            };
+            let assigned = count_requests(FulfillmentStatus::Assigned, None).await?;
+            let day_ago = spn_utils::time_now().saturating_sub(24 * 60 * 60);
+            let fulfilled = count_requests(FulfillmentStatus::Fulfilled, Some(day_ago)).await?;
+
+            // Create a table for the status.
+            #[derive(Tabled)]
+            struct StatusTable {
+                #[tabled(rename = "Field")]
+                name: String,
+                #[tabled(rename = "Value")]
+                value: String,
+            }
+
+            // Create status table data.
+            let status_data = vec![
+                StatusTable { name: "Address".to_string(), value: args.address.to_string() },
+                StatusTable { name: "Owner".to_string(), value: owner.to_string() },
+                StatusTable {
+                    name: "Balance".to_string(),
+                    value: format!("{} $PROVE", format_units(balance, 18)?),
+                },
+                StatusTable { name: "Delegated Signers".to_string(), value: delegates },
+                StatusTable { name: "Assigned Requests".to_string(), value: assigned },
+                StatusTable { name: "Fulfilled Requests (24h)".to_string(), value: fulfilled },
+            ];
+
+            // Create and style the status table.
+            let mut status_table = Table::new(status_data);
+            status_table.with(Style::modern());
+
+            // Print the status with a title.
+            println!("\nProver Status:");
+            println!("{status_table}\n");
+        }
+        Args::Completions(args) => {
+            let mut command = Args::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(args.shell, &mut command, name, &mut std::io::stdout());
+        }
     }
 
     Ok(())
@@ -0,0 +1,308 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use spn_artifact_types::ArtifactType;
+use tracing::{debug, warn};
+
+use crate::get_s3_key;
+
+/// The extension of the files holding the checksums of the cached artifacts.
+const CHECKSUM_EXTENSION: &str = "blake3";
+
+/// The extension of the files that are still being written.
+const PARTIAL_EXTENSION: &str = "partial";
+
+/// An on-disk cache of downloaded artifacts, keyed by artifact type and ID.
+///
+/// Artifacts are stored under `<dir>/<prefix>/<id>`, where the prefix is the S3 prefix of the
+/// artifact type, next to a BLAKE3 checksum of their contents. The checksum is verified on every
+/// hit, and a corrupted artifact is evicted so that it gets downloaded again.
+///
+/// Once the total size of the cached artifacts exceeds the maximum size, the least recently used
+/// ones are evicted. The cache is rebuilt from the directory when it is opened, using the
+/// modification times of the files as a stand-in for their last use.
+#[derive(Debug)]
+pub struct ArtifactCache {
+    dir: PathBuf,
+    max_size: u64,
+    index: Mutex<CacheIndex>,
+}
+
+/// The artifacts in an [`ArtifactCache`] and the order in which they were used.
+#[derive(Debug, Default)]
+struct CacheIndex {
+    entries: HashMap<PathBuf, CacheEntry>,
+    size: u64,
+    clock: u64,
+}
+
+/// An artifact in an [`ArtifactCache`].
+#[derive(Debug, Clone, Copy)]
+struct CacheEntry {
+    size: u64,
+    last_used: u64,
+}
+
+impl ArtifactCache {
+    /// Open the cache in the given directory, creating it if needed, with a maximum total size in
+    /// bytes.
+    ///
+    /// Artifacts already in the directory are kept, and evicted if they exceed the maximum size.
+    pub fn open(dir: impl Into<PathBuf>, max_size: u64) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create artifact cache {}", dir.display()))?;
+
+        // Collect the cached artifacts, skipping the checksums and unfinished writes.
+        let mut files = Vec::new();
+        for prefix in fs::read_dir(&dir)? {
+            let prefix = prefix?;
+            if !prefix.file_type()?.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(prefix.path())? {
+                let file = file?;
+                let path = file.path();
+                let extension = path.extension().and_then(|extension| extension.to_str());
+                if matches!(extension, Some(CHECKSUM_EXTENSION | PARTIAL_EXTENSION)) {
+                    continue;
+                }
+                let metadata = file.metadata()?;
+                if metadata.is_file() {
+                    files.push((path, metadata.len(), metadata.modified()?));
+                }
+            }
+        }
+        files.sort_by_key(|(_, _, modified)| *modified);
+
+        let mut index = CacheIndex::default();
+        for (path, size, _) in files {
+            index.insert(path, size);
+        }
+        let cache = Self { dir, max_size, index: Mutex::new(index) };
+        cache.evict();
+        Ok(cache)
+    }
+
+    /// The total size in bytes of the cached artifacts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another thread panicked while holding the index of the cache.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.index.lock().unwrap().size
+    }
+
+    /// Get a cached artifact, or `None` if it isn't cached or its checksum doesn't match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another thread panicked while holding the index of the cache.
+    pub async fn get(&self, artifact_type: ArtifactType, id: &str) -> Option<Bytes> {
+        let path = self.path(artifact_type, id)?;
+        if !self.index.lock().unwrap().entries.contains_key(&path) {
+            return None;
+        }
+
+        let data = tokio::fs::read(&path).await;
+        let checksum = tokio::fs::read_to_string(checksum_path(&path)).await;
+        let (Ok(data), Ok(checksum)) = (data, checksum) else {
+            warn!("Failed to read cached artifact {}, evicting it", path.display());
+            self.remove(&path);
+            return None;
+        };
+        if blake3::hash(&data).to_hex().as_str() != checksum.trim() {
+            warn!("Cached artifact {} is corrupted, evicting it", path.display());
+            self.remove(&path);
+            return None;
+        }
+
+        debug!("Found artifact {} in the cache", path.display());
+        self.index.lock().unwrap().touch(&path);
+        Some(Bytes::from(data))
+    }
+
+    /// Add an artifact to the cache, evicting the least recently used ones if it gets too big.
+    ///
+    /// Artifacts larger than the maximum size of the cache, or whose ID isn't a plain file name,
+    /// are not cached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another thread panicked while holding the index of the cache.
+    pub async fn insert(&self, artifact_type: ArtifactType, id: &str, data: &[u8]) -> Result<()> {
+        let Some(path) = self.path(artifact_type, id) else {
+            return Ok(());
+        };
+        if data.len() as u64 > self.max_size {
+            return Ok(());
+        }
+
+        // Write the checksum before moving the artifact in place, so that a cached artifact
+        // always has a checksum to be verified against.
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let checksum = blake3::hash(data).to_hex();
+        write_atomic(&checksum_path(&path), checksum.as_bytes()).await?;
+        write_atomic(&path, data).await?;
+
+        self.index.lock().unwrap().insert(path, data.len() as u64);
+        self.evict();
+        Ok(())
+    }
+
+    /// The path of an artifact in the cache, or `None` if its ID isn't a plain file name.
+    fn path(&self, artifact_type: ArtifactType, id: &str) -> Option<PathBuf> {
+        let is_file_name = !id.is_empty() &&
+            id != "." &&
+            id != ".." &&
+            !id.contains(['/', '\\']) &&
+            !id.ends_with(&format!(".{CHECKSUM_EXTENSION}")) &&
+            !id.ends_with(&format!(".{PARTIAL_EXTENSION}"));
+        is_file_name.then(|| self.dir.join(get_s3_key(artifact_type, id)))
+    }
+
+    /// Evict the least recently used artifacts until the cache fits in its maximum size.
+    fn evict(&self) {
+        let mut index = self.index.lock().unwrap();
+        while index.size > self.max_size {
+            let Some(path) = index
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(path, _)| path.clone())
+            else {
+                break;
+            };
+            debug!("Evicting artifact {} from the cache", path.display());
+            index.remove(&path);
+            remove_files(&path);
+        }
+    }
+
+    /// Remove an artifact from the cache.
+    fn remove(&self, path: &Path) {
+        self.index.lock().unwrap().remove(path);
+        remove_files(path);
+    }
+}
+
+impl CacheIndex {
+    /// Add or replace an artifact, marking it as the most recently used.
+    fn insert(&mut self, path: PathBuf, size: u64) {
+        self.remove(&path);
+        self.clock += 1;
+        self.size += size;
+        self.entries.insert(path, CacheEntry { size, last_used: self.clock });
+    }
+
+    /// Mark an artifact as the most recently used.
+    fn touch(&mut self, path: &Path) {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(path) {
+            entry.last_used = self.clock;
+        }
+    }
+
+    /// Remove an artifact.
+    fn remove(&mut self, path: &Path) {
+        if let Some(entry) = self.entries.remove(path) {
+            self.size -= entry.size;
+        }
+    }
+}
+
+/// The path of the checksum of a cached artifact.
+fn checksum_path(path: &Path) -> PathBuf {
+    with_extension(path, CHECKSUM_EXTENSION)
+}
+
+/// Append an extension to a path, keeping any extension it already has.
+fn with_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
+/// Write a file by renaming a fully written temporary file over it, so that readers never see a
+/// partially written file.
+async fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let partial = with_extension(path, PARTIAL_EXTENSION);
+    tokio::fs::write(&partial, data)
+        .await
+        .with_context(|| format!("Failed to write {}", partial.display()))?;
+    tokio::fs::rename(&partial, path)
+        .await
+        .with_context(|| format!("Failed to move {} in place", partial.display()))?;
+    Ok(())
+}
+
+/// Remove the files of a cached artifact, ignoring the ones that are already gone.
+fn remove_files(path: &Path) {
+    let _ = fs::remove_file(path);
+    let _ = fs::remove_file(checksum_path(path));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cache_hit_and_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ArtifactCache::open(dir.path(), 1024).unwrap();
+        assert_eq!(cache.get(ArtifactType::Program, "artifact_1").await, None);
+
+        cache.insert(ArtifactType::Program, "artifact_1", b"program").await.unwrap();
+        assert_eq!(
+            cache.get(ArtifactType::Program, "artifact_1").await,
+            Some(Bytes::from_static(b"program"))
+        );
+        assert_eq!(cache.get(ArtifactType::Stdin, "artifact_1").await, None);
+
+        // A corrupted artifact is evicted instead of being returned.
+        fs::write(dir.path().join("programs/artifact_1"), b"tampered").unwrap();
+        assert_eq!(cache.get(ArtifactType::Program, "artifact_1").await, None);
+        assert_eq!(cache.size(), 0);
+        assert!(!dir.path().join("programs/artifact_1").exists());
+
+        // IDs that aren't plain file names are never cached.
+        cache.insert(ArtifactType::Program, "..", b"program").await.unwrap();
+        assert_eq!(cache.size(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ArtifactCache::open(dir.path(), 10).unwrap();
+        cache.insert(ArtifactType::Stdin, "a", b"aaaa").await.unwrap();
+        cache.insert(ArtifactType::Stdin, "b", b"bbbb").await.unwrap();
+
+        // Using `a` makes `b` the least recently used artifact, so it is evicted first.
+        assert!(cache.get(ArtifactType::Stdin, "a").await.is_some());
+        cache.insert(ArtifactType::Stdin, "c", b"cccc").await.unwrap();
+        assert!(cache.get(ArtifactType::Stdin, "b").await.is_none());
+        assert!(cache.get(ArtifactType::Stdin, "a").await.is_some());
+        assert!(cache.get(ArtifactType::Stdin, "c").await.is_some());
+        assert_eq!(cache.size(), 8);
+
+        // Artifacts larger than the cache are skipped.
+        cache.insert(ArtifactType::Stdin, "d", &[0; 11]).await.unwrap();
+        assert!(cache.get(ArtifactType::Stdin, "d").await.is_none());
+
+        // Reopening the cache keeps the artifacts.
+        drop(cache);
+        let cache = ArtifactCache::open(dir.path(), 10).unwrap();
+        assert_eq!(cache.size(), 8);
+        assert_eq!(cache.get(ArtifactType::Stdin, "c").await, Some(Bytes::from_static(b"cccc")));
+    }
+}
@@ -20,10 +20,15 @@
 #![allow(clippy::struct_excessive_bools)]
 #![warn(missing_docs)]
 
+mod cache;
+
+pub use cache::*;
+
 use std::{
     collections::HashMap,
+    path::{Path, PathBuf},
     sync::{Arc, LazyLock},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use spn_artifact_types::ArtifactType;
@@ -32,14 +37,18 @@ use anyhow::{anyhow, Context, Result};
 use aws_config::{retry::RetryConfig, BehaviorVersion, Region};
 use aws_sdk_s3::{
     config::{IdentityCache, StalledStreamProtectionConfig},
+    error::{ProvideErrorMetadata, SdkError},
+    operation::get_object::GetObjectError,
     primitives::{ByteStream, SdkBody},
+    types::BucketLocationConstraint,
     Client as S3Client,
 };
 use aws_smithy_async::rt::sleep::default_async_sleep;
 use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
-use tokio::{sync::RwLock, task::JoinSet};
-use tracing::instrument;
+use spn_utils::{retry_with_policy, RetryPolicy};
+use tokio::{io::AsyncWriteExt, sync::RwLock, task::JoinSet};
+use tracing::{field::Empty, instrument, Instrument, Span};
 use url::Url;
 
 /// Chunk size for parallel downloads in bytes (32MB).
@@ -48,11 +57,33 @@ const CHUNK_SIZE: usize = 32 * 1024 * 1024;
 /// Default concurrency for parallel downloads.
 const DEFAULT_CONCURRENCY: usize = 32;
 
+/// The maximum number of attempts to download an artifact or one of its chunks.
+pub const MAX_DOWNLOAD_ATTEMPTS: u32 = 5;
+
+/// The codes of S3 errors that are expected to go away when the request is retried.
+const RETRYABLE_S3_ERROR_CODES: &[&str] = &[
+    "SlowDown",
+    "ExpiredToken",
+    "RequestTimeout",
+    "InternalError",
+    "ServiceUnavailable",
+    "Throttling",
+    "ThrottlingException",
+    "RequestLimitExceeded",
+];
+
 /// S3 Clients that are cached across the entire application.
 #[allow(clippy::type_complexity)]
 static S3_CLIENTS: LazyLock<Arc<RwLock<HashMap<String, Arc<S3Client>>>>> =
     LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
 
+/// The regions of S3 buckets, keyed by bucket name, that are cached across the entire application.
+static BUCKET_REGIONS: LazyLock<RwLock<HashMap<String, String>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// The region used to look up the region of a bucket, which works for buckets in any region.
+const BUCKET_LOOKUP_REGION: &str = "us-east-1";
+
 /// An artifact is a file that is stored in S3.
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
 pub struct Artifact {
@@ -95,7 +126,7 @@ impl Artifact {
     /// Downloads raw bytes of an artifact from S3.
     ///
     /// Retrieves the artifact from the specified S3 bucket and region. Implements
-    /// exponential backoff retry logic with up to 5 attempts.
+    /// exponential backoff retry logic with up to [`MAX_DOWNLOAD_ATTEMPTS`] attempts.
     ///
     /// # Arguments
     /// * `s3_bucket` - The S3 bucket name
@@ -109,35 +140,88 @@ impl Artifact {
         artifact_type: ArtifactType,
     ) -> Result<Bytes> {
         let s3_client = get_s3_client(s3_region).await;
+        retry_with_policy(
+            &download_retry_policy(),
+            || download_s3_file(&s3_client, s3_bucket, &self.id, artifact_type),
+            |e| {
+                tracing::warn!("failed to download artifact {}: {:#}", self.id, e);
+                is_retryable_download_error(e)
+            },
+        )
+        .await
+    }
 
-        // Retry configuration
-        let max_retries = 5;
-        let mut retry_count = 0;
-        let mut delay = Duration::from_secs(1); // Start with 1 second delay
-
-        loop {
-            match download_s3_file(&s3_client, s3_bucket, &self.id, artifact_type).await {
-                Ok(bytes) => return Ok(bytes),
-                Err(e) => {
-                    retry_count += 1;
-                    if retry_count >= max_retries {
-                        return Err(e);
-                    }
-
-                    // Log the retry attempt
-                    tracing::warn!(
-                        "retry attempt {} for downloading artifact {}: {}",
-                        retry_count,
-                        self.id,
-                        e
-                    );
+    /// Downloads an artifact from S3 to a file, resuming from where a failed attempt stopped.
+    ///
+    /// The artifact is streamed into a `.partial` file next to `path`. After a transient failure,
+    /// the next attempt requests only the bytes that aren't in that file yet, so a large download
+    /// interrupted near the end doesn't start over. Once complete, the file is renamed to `path`.
+    /// Implements exponential backoff retry logic with up to [`MAX_DOWNLOAD_ATTEMPTS`] attempts.
+    ///
+    /// The attempts are pinned to the version of the artifact seen by the first one, so that the
+    /// download fails instead of mixing two versions if the artifact is replaced in the meantime.
+    /// The `ETag` of that version is stored next to the `.partial` file, so that a later call only
+    /// resumes the file if the artifact hasn't changed. Otherwise, or if the artifact changes
+    /// during the download, the `.partial` file is deleted.
+    ///
+    /// # Arguments
+    /// * `s3_bucket` - The S3 bucket name
+    /// * `s3_region` - The AWS region of the S3 bucket
+    /// * `artifact_type` - The type of artifact determining the S3 prefix
+    /// * `path` - The path of the file to download the artifact to
+    #[instrument(fields(label = self.label, id = self.id), skip_all)]
+    pub async fn download_to_file(
+        &self,
+        s3_bucket: &str,
+        s3_region: &str,
+        artifact_type: ArtifactType,
+        path: &Path,
+    ) -> Result<()> {
+        let s3_client = get_s3_client(s3_region).await;
+        let key = get_s3_key(artifact_type, &self.id);
+        let partial = PartialDownload::new(path);
 
-                    // Wait with exponential backoff
-                    tokio::time::sleep(delay).await;
-                    delay *= 2; // Double the delay for next retry
-                }
+        // Look up the size and version of the artifact to resume against.
+        let head_res = s3_client
+            .head_object()
+            .bucket(s3_bucket)
+            .key(&key)
+            .send()
+            .await
+            .context("Failed to get object metadata from S3")?;
+        let size = u64::try_from(head_res.content_length().unwrap_or(0)).unwrap_or(0);
+        let e_tag = head_res.e_tag().map(String::from);
+        partial.prepare(e_tag.as_deref(), size).await?;
+
+        let result = retry_with_policy(
+            &download_retry_policy(),
+            || {
+                resume_s3_download(
+                    &s3_client,
+                    s3_bucket,
+                    &key,
+                    e_tag.as_deref(),
+                    &partial.path,
+                    size,
+                    artifact_type,
+                )
+            },
+            |e| {
+                tracing::warn!("failed to download artifact {} to file: {:#}", self.id, e);
+                is_retryable_download_error(e)
+            },
+        )
+        .await;
+
+        // The artifact was replaced during the download, so the staged bytes can't be resumed.
+        if let Err(e) = result {
+            if is_precondition_failed(&e) {
+                partial.discard().await?;
             }
+            return Err(e);
         }
+
+        partial.finish(path).await
     }
 
     /// Downloads raw bytes of an artifact from a URI.
@@ -205,6 +289,36 @@ impl Artifact {
         }
     }
 
+    /// Downloads raw bytes of an artifact from a URI, reusing the copy in the given cache if it
+    /// has one.
+    ///
+    /// Like [`Artifact::download_raw_from_uri`], but the artifact is looked up in the
+    /// [`ArtifactCache`] by its type and ID first, and added to it after being downloaded. Failing
+    /// to add it is logged and otherwise ignored, since the download itself succeeded.
+    ///
+    /// # Arguments
+    /// * `uri` - The URI to download from (s3:// or https://)
+    /// * `s3_region` - The AWS region for S3 operations
+    /// * `artifact_type` - The type of artifact determining the S3 prefix
+    /// * `cache` - The cache to look the artifact up in and add it to
+    #[instrument(fields(label = self.label, id = self.id), skip_all)]
+    pub async fn download_raw_from_uri_cached(
+        &self,
+        uri: &str,
+        s3_region: &str,
+        artifact_type: ArtifactType,
+        cache: &ArtifactCache,
+    ) -> Result<Bytes> {
+        if let Some(bytes) = cache.get(artifact_type, &self.id).await {
+            return Ok(bytes);
+        }
+        let bytes = self.download_raw_from_uri(uri, s3_region, artifact_type).await?;
+        if let Err(e) = cache.insert(artifact_type, &self.id, &bytes).await {
+            tracing::warn!("failed to cache artifact {}: {:#}", self.id, e);
+        }
+        Ok(bytes)
+    }
+
     /// Downloads and deserializes a program artifact from S3.
     ///
     /// Downloads the program artifact and deserializes it using bincode into the
@@ -329,6 +443,42 @@ impl Artifact {
         upload_file(&s3_client, s3_bucket, &self.id, artifact_type, data).await
     }
 
+    /// Downloads raw bytes of an artifact from S3, detecting the region of the bucket.
+    ///
+    /// Like [`Artifact::download_raw`], but the region is resolved with [`get_bucket_region`]
+    /// instead of being passed in.
+    ///
+    /// # Arguments
+    /// * `s3_bucket` - The S3 bucket name
+    /// * `artifact_type` - The type of artifact determining the S3 prefix
+    pub async fn download_raw_auto_region(
+        &self,
+        s3_bucket: &str,
+        artifact_type: ArtifactType,
+    ) -> Result<Bytes> {
+        let s3_region = get_bucket_region(s3_bucket).await?;
+        self.download_raw(s3_bucket, &s3_region, artifact_type).await
+    }
+
+    /// Uploads raw bytes as an artifact to S3, detecting the region of the bucket.
+    ///
+    /// Like [`Artifact::upload_raw`], but the region is resolved with [`get_bucket_region`]
+    /// instead of being passed in.
+    ///
+    /// # Arguments
+    /// * `data` - The raw bytes to upload
+    /// * `s3_bucket` - The S3 bucket name
+    /// * `artifact_type` - The type of artifact determining the S3 prefix
+    pub async fn upload_raw_auto_region(
+        &self,
+        data: Bytes,
+        s3_bucket: &str,
+        artifact_type: ArtifactType,
+    ) -> Result<()> {
+        let s3_region = get_bucket_region(s3_bucket).await?;
+        self.upload_raw(data, s3_bucket, &s3_region, artifact_type).await
+    }
+
     /// Copies an artifact between S3 buckets.
     ///
     /// Copies the artifact from a source bucket to a destination bucket, potentially
@@ -348,6 +498,39 @@ impl Artifact {
         src_region: &str,
         dst_bucket: &str,
         dst_region: &str,
+    ) -> Result<()> {
+        self.copy_with_options(
+            artifact_type,
+            src_bucket,
+            src_region,
+            dst_bucket,
+            dst_region,
+            CopyOptions::default(),
+        )
+        .await
+    }
+
+    /// Copies an artifact between S3 buckets with the given [`CopyOptions`].
+    ///
+    /// Behaves like [`Artifact::copy`], except that an existing artifact in the destination is
+    /// replaced if [`CopyOptions::overwrite`] is set, and the copy is checked against the source
+    /// if [`CopyOptions::verify`] is set.
+    ///
+    /// # Arguments
+    /// * `artifact_type` - The type of artifact determining the S3 prefix
+    /// * `src_bucket` - The source S3 bucket name
+    /// * `src_region` - The AWS region of the source bucket
+    /// * `dst_bucket` - The destination S3 bucket name
+    /// * `dst_region` - The AWS region of the destination bucket
+    /// * `options` - Whether to overwrite an existing artifact and verify the copy
+    pub async fn copy_with_options(
+        &self,
+        artifact_type: ArtifactType,
+        src_bucket: &str,
+        src_region: &str,
+        dst_bucket: &str,
+        dst_region: &str,
+        options: CopyOptions,
     ) -> Result<()> {
         let key = get_s3_key(artifact_type, &self.id);
 
@@ -355,9 +538,11 @@ impl Artifact {
         let dst_client = get_s3_client(dst_region).await;
 
         // Check if destination exists
-        let dst_res = dst_client.head_object().bucket(dst_bucket).key(&key).send().await;
-        if dst_res.is_ok() {
-            return Ok(());
+        if !options.overwrite {
+            let dst_res = dst_client.head_object().bucket(dst_bucket).key(&key).send().await;
+            if dst_res.is_ok() {
+                return Ok(());
+            }
         }
 
         let src_res = src_client
@@ -368,19 +553,92 @@ impl Artifact {
             .await
             .context("Failed to get object from S3")?;
 
+        // Verified copies read the source in full, so that its checksum can be compared with the
+        // copy. Otherwise, the source is streamed straight into the destination.
+        let (body, src_checksum) = if options.verify {
+            let data =
+                src_res.body.collect().await.context("Failed to read S3 object body")?.into_bytes();
+            let checksum = blake3::hash(&data);
+            (ByteStream::from(data), Some(checksum))
+        } else {
+            (src_res.body, None)
+        };
+
         dst_client
             .put_object()
             .bucket(dst_bucket)
             .key(&key)
-            .body(src_res.body)
+            .body(body)
             .send()
             .await
             .context("Failed to upload object to S3")?;
 
+        // Check that the destination holds the same bytes as were read from the source.
+        if let Some(src_checksum) = src_checksum {
+            let dst_res = dst_client
+                .get_object()
+                .bucket(dst_bucket)
+                .key(&key)
+                .send()
+                .await
+                .context("Failed to get copied object from S3")?;
+            let dst_data = dst_res
+                .body
+                .collect()
+                .await
+                .context("Failed to read copied S3 object body")?
+                .into_bytes();
+            verify_copy(&key, &src_checksum, &dst_data)?;
+        }
+
         Ok(())
     }
 }
 
+/// Options for [`Artifact::copy_with_options`].
+///
+/// The default skips the copy if the artifact already exists in the destination and doesn't
+/// verify it, like [`Artifact::copy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CopyOptions {
+    /// Whether to replace the artifact if it already exists in the destination.
+    pub overwrite: bool,
+    /// Whether to check that the contents of the copied artifact match the source.
+    ///
+    /// Both the source and the copy are read in full to compare their checksums, so this doubles
+    /// the data transferred by the copy.
+    pub verify: bool,
+}
+
+impl CopyOptions {
+    /// Set whether to replace the artifact if it already exists in the destination.
+    #[must_use]
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Set whether to check that the contents of the copied artifact match the source.
+    #[must_use]
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+}
+
+/// Check that a copied artifact has the same BLAKE3 checksum as its source.
+///
+/// Comparing the sizes alone would miss a copy whose bytes were corrupted or replaced.
+fn verify_copy(key: &str, src_checksum: &blake3::Hash, dst: &[u8]) -> Result<()> {
+    let dst_checksum = blake3::hash(dst);
+    if dst_checksum != *src_checksum {
+        return Err(anyhow!(
+            "Copied artifact {key} has checksum {dst_checksum}, expected {src_checksum}"
+        ));
+    }
+    Ok(())
+}
+
 /// Given a S3 URL (e.g.
 /// <s3://prover-network-staging/artifacts/artifact_01j92x39ngfnrra5br9n8zr07x>), extract the
 /// artifact name from the URL (e.g. `artifact_01j92x39ngfnrra5br9n8zr07x`).
@@ -452,6 +710,39 @@ async fn get_s3_client(s3_region: &str) -> Arc<S3Client> {
     }
 }
 
+/// Get the region of an S3 bucket.
+///
+/// The region is looked up with `GetBucketLocation` the first time a bucket is seen and cached
+/// alongside the S3 clients, so that callers only need to know the name of the bucket.
+pub async fn get_bucket_region(bucket: &str) -> Result<String> {
+    if let Some(region) = BUCKET_REGIONS.read().await.get(bucket) {
+        return Ok(region.clone());
+    }
+
+    let client = get_s3_client(BUCKET_LOOKUP_REGION).await;
+    let res = client
+        .get_bucket_location()
+        .bucket(bucket)
+        .send()
+        .await
+        .with_context(|| format!("Failed to get the location of S3 bucket {bucket}"))?;
+    let region = bucket_region(res.location_constraint().map(BucketLocationConstraint::as_str));
+    BUCKET_REGIONS.write().await.insert(bucket.to_string(), region.clone());
+    Ok(region)
+}
+
+/// Convert the location constraint of a bucket to its region.
+///
+/// Buckets in `us-east-1` have no location constraint, and some older buckets in `eu-west-1`
+/// report the legacy `EU` constraint.
+fn bucket_region(location_constraint: Option<&str>) -> String {
+    match location_constraint {
+        None | Some("") => "us-east-1".to_string(),
+        Some("EU") => "eu-west-1".to_string(),
+        Some(region) => region.to_string(),
+    }
+}
+
 async fn download_s3_file(
     client: &S3Client,
     bucket: &str,
@@ -460,20 +751,157 @@ async fn download_s3_file(
 ) -> Result<Bytes> {
     let key = get_s3_key(artifact_type, id);
 
-    let res = client
-        .get_object()
-        .bucket(bucket)
-        .key(key)
-        .send()
+    let start = Instant::now();
+    let result = async {
+        let res = client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .context("Failed to get object from S3")?;
+
+        let data = res.body.collect().await.context("Failed to read S3 object body")?;
+        Ok(data.into_bytes())
+    }
+    .await;
+    record_s3_metrics("download", artifact_type, start, result.is_ok());
+
+    result
+}
+
+/// A download staged in a `.partial` file next to its destination.
+///
+/// The `ETag` of the version of the artifact being downloaded is stored in a `.partial.etag` file,
+/// so that the staged bytes are only resumed for the same version.
+struct PartialDownload {
+    /// The path of the file the artifact is downloaded to.
+    path: PathBuf,
+    /// The path of the file holding the `ETag` of the artifact.
+    e_tag_path: PathBuf,
+}
+
+impl PartialDownload {
+    /// Create a new [`PartialDownload`] for the given destination.
+    fn new(dst: &Path) -> Self {
+        let mut path = dst.as_os_str().to_owned();
+        path.push(".partial");
+        let mut e_tag_path = path.clone();
+        e_tag_path.push(".etag");
+        Self { path: PathBuf::from(path), e_tag_path: PathBuf::from(e_tag_path) }
+    }
+
+    /// Prepare to download the version of the artifact with the given `ETag` and size.
+    ///
+    /// The staged bytes are kept if they were downloaded from the same version and can be a
+    /// prefix of it, and deleted otherwise. Artifacts without an `ETag` are always downloaded from
+    /// scratch, since the version of the staged bytes can't be checked.
+    async fn prepare(&self, e_tag: Option<&str>, size: u64) -> Result<()> {
+        let staged_e_tag = tokio::fs::read_to_string(&self.e_tag_path).await.ok();
+        let staged_len = tokio::fs::metadata(&self.path).await.map_or(0, |metadata| metadata.len());
+        if e_tag.is_some() && staged_e_tag.as_deref() == e_tag && staged_len <= size {
+            return Ok(());
+        }
+
+        self.discard().await?;
+        if let Some(e_tag) = e_tag {
+            tokio::fs::write(&self.e_tag_path, e_tag)
+                .await
+                .with_context(|| format!("Failed to write {}", self.e_tag_path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Delete the staged bytes and the `ETag` they were downloaded from.
+    async fn discard(&self) -> Result<()> {
+        for path in [&self.path, &self.e_tag_path] {
+            match tokio::fs::remove_file(path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(e).with_context(|| format!("Failed to remove {}", path.display()));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the completed download to its destination.
+    async fn finish(&self, dst: &Path) -> Result<()> {
+        tokio::fs::rename(&self.path, dst)
+            .await
+            .with_context(|| format!("Failed to move downloaded artifact to {}", dst.display()))?;
+        let _ = tokio::fs::remove_file(&self.e_tag_path).await;
+        Ok(())
+    }
+}
+
+/// Whether a download failed because the artifact no longer matches the `ETag` it was pinned to.
+fn is_precondition_failed(e: &anyhow::Error) -> bool {
+    e.chain().filter_map(|cause| cause.downcast_ref::<SdkError<GetObjectError>>()).any(
+        |e| matches!(e, SdkError::ServiceError(context) if context.raw().status().as_u16() == 412),
+    )
+}
+
+/// Downloads the bytes of an S3 object that are missing from the end of a partial file.
+///
+/// The bytes are appended to the file as they arrive, so that they are kept if the download fails
+/// partway through.
+async fn resume_s3_download(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    e_tag: Option<&str>,
+    partial: &Path,
+    size: u64,
+    artifact_type: ArtifactType,
+) -> Result<()> {
+    let offset = tokio::fs::metadata(partial).await.map_or(0, |metadata| metadata.len());
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(partial)
         .await
-        .context("Failed to get object from S3")?;
+        .with_context(|| format!("Failed to open {}", partial.display()))?;
+    if offset >= size {
+        return Ok(());
+    }
 
-    let data = res.body.collect().await.context("Failed to read S3 object body")?;
-    let bytes = data.into_bytes();
+    let start = Instant::now();
+    let result = async {
+        let res = client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .range(format!("bytes={offset}-"))
+            .set_if_match(e_tag.map(String::from))
+            .send()
+            .await
+            .context("Failed to get object from S3")?;
 
-    Ok(bytes)
+        let mut body = res.body;
+        while let Some(chunk) = body.try_next().await.context("Failed to read S3 object body")? {
+            file.write_all(&chunk).await.context("Failed to write S3 object body")?;
+        }
+        file.flush().await?;
+        Ok::<(), anyhow::Error>(())
+    }
+    .await;
+    record_s3_metrics("download", artifact_type, start, result.is_ok());
+    result?;
+
+    let written = file.metadata().await?.len();
+    if written != size {
+        return Err(anyhow!("Downloaded {written} bytes, expected {size}"));
+    }
+    Ok(())
 }
 
+/// Downloads an S3 object in parts of [`CHUNK_SIZE`] bytes, fetching up to `concurrency` parts at
+/// once.
+///
+/// The latency of each part is recorded in the metrics under the `download_part` operation and
+/// logged at debug level.
+#[instrument(skip(client), fields(size = Empty, parts = Empty))]
 async fn download_s3_file_par(
     client: &S3Client,
     bucket: &str,
@@ -506,6 +934,7 @@ async fn download_s3_file_par(
 
     let num_chunks = starts.len();
     let concurrency = std::cmp::min(concurrency, num_chunks);
+    Span::current().record("size", size).record("parts", num_chunks);
 
     let mut set = JoinSet::new();
     let (tx, mut rx) = tokio::sync::mpsc::channel(num_chunks);
@@ -517,46 +946,47 @@ async fn download_s3_file_par(
         let tx = tx.clone();
         let chunk_group = chunk_group.to_vec();
 
-        set.spawn(async move {
-            for (index, start) in chunk_group {
-                let end = std::cmp::min(start + CHUNK_SIZE as i64, size) - 1;
-                let range = format!("bytes={start}-{end}");
-
-                let mut retry_count = 0;
-                let max_retries = 5;
-                let mut delay = Duration::from_secs(1);
-
-                loop {
-                    match client.get_object().bucket(&bucket).key(&key).range(&range).send().await {
-                        Ok(res) => {
-                            let data = res.body.collect().await?;
-                            let bytes = data.into_bytes();
-                            tx.send((index, bytes)).await?;
-                            break;
-                        }
-                        Err(e) => {
-                            retry_count += 1;
-                            if retry_count >= max_retries {
-                                return Err(anyhow!(
-                                    "Failed to download S3 chunk after {max_retries} retries: {e}"
-                                ));
-                            }
-
-                            tracing::warn!(
-                                "retry attempt {} for downloading S3 chunk {}: {}",
-                                retry_count,
-                                index,
-                                e
-                            );
-
-                            tokio::time::sleep(delay).await;
-                            delay *= 2;
-                        }
+        set.spawn(
+            async move {
+                for (index, start) in chunk_group {
+                    let end = std::cmp::min(start + CHUNK_SIZE as i64, size) - 1;
+                    let range = format!("bytes={start}-{end}");
+
+                    let part_start = Instant::now();
+                    let result = async {
+                        let res = retry_with_policy(
+                            &download_retry_policy(),
+                            || client.get_object().bucket(&bucket).key(&key).range(&range).send(),
+                            |e| {
+                                tracing::warn!("failed to download S3 chunk {}: {}", index, e);
+                                is_retryable_s3_error(e)
+                            },
+                        )
+                        .await
+                        .map_err(|e| {
+                            anyhow!(
+                                "Failed to download S3 chunk after {MAX_DOWNLOAD_ATTEMPTS} \
+                                 attempts: {e}"
+                            )
+                        })?;
+                        let data = res.body.collect().await?;
+                        Ok::<Bytes, anyhow::Error>(data.into_bytes())
                     }
+                    .await;
+                    record_s3_metrics("download_part", artifact_type, part_start, result.is_ok());
+                    let bytes = result?;
+                    tracing::debug!(
+                        part = index,
+                        range = %range,
+                        elapsed_ms = part_start.elapsed().as_millis(),
+                        "downloaded S3 chunk"
+                    );
+                    tx.send((index, bytes)).await?;
                 }
+                Ok::<(), anyhow::Error>(())
             }
-            Ok::<(), anyhow::Error>(())
-        });
+            .in_current_span(),
+        );
     }
 
     drop(tx);
@@ -575,6 +1005,44 @@ async fn download_s3_file_par(
     Ok(Bytes::from(result))
 }
 
+/// The policy for retrying downloads, which doubles the delay from one second.
+fn download_retry_policy() -> RetryPolicy {
+    RetryPolicy::default().with_max_attempts(MAX_DOWNLOAD_ATTEMPTS)
+}
+
+/// Whether an error returned by the S3 SDK is transient and worth retrying.
+///
+/// Timeouts, connection failures, throttling, server errors and expired credentials, which are
+/// refreshed for the next request, are retried. Errors such as `NoSuchKey` or `AccessDenied` are
+/// not, since retrying them can't succeed.
+#[must_use]
+pub fn is_retryable_s3_error<E: ProvideErrorMetadata>(e: &SdkError<E>) -> bool {
+    match e {
+        SdkError::TimeoutError(_) | SdkError::ResponseError(_) => true,
+        SdkError::DispatchFailure(failure) => !failure.is_user(),
+        SdkError::ServiceError(context) => {
+            let status = context.raw().status();
+            context.err().code().is_some_and(|code| RETRYABLE_S3_ERROR_CODES.contains(&code)) ||
+                status.is_server_error() ||
+                status.as_u16() == 429
+        }
+        _ => false,
+    }
+}
+
+/// Whether an error returned while downloading an artifact from S3 is transient and worth
+/// retrying.
+///
+/// Errors returned by the S3 SDK are classified with [`is_retryable_s3_error`]. Any other error,
+/// such as a failure to read the body of the response, is assumed to be transient.
+#[must_use]
+pub fn is_retryable_download_error(e: &anyhow::Error) -> bool {
+    match e.chain().find_map(|cause| cause.downcast_ref::<SdkError<GetObjectError>>()) {
+        Some(e) => is_retryable_s3_error(e),
+        None => true,
+    }
+}
+
 async fn download_https_file(uri: &str) -> Result<Bytes> {
     let client = reqwest::Client::new();
     let res = client
@@ -735,25 +1203,166 @@ async fn upload_file(
 
     let body = ByteStream::new(SdkBody::from(data));
 
-    client
-        .put_object()
-        .bucket(bucket)
-        .key(key)
-        .body(body)
-        .send()
-        .await
-        .context("Failed to upload object to S3")?;
+    let start = Instant::now();
+    let result = client.put_object().bucket(bucket).key(key).body(body).send().await;
+    record_s3_metrics("upload", artifact_type, start, result.is_ok());
+    result.context("Failed to upload object to S3")?;
 
     Ok(())
 }
 
+/// Records the latency of an S3 operation on an artifact and whether it failed.
+///
+/// The metrics are labeled with the operation and the S3 prefix of the artifact type, so that
+/// error rates and latencies can be broken down by the kind of artifact.
+fn record_s3_metrics(
+    operation: &'static str,
+    artifact_type: ArtifactType,
+    start: Instant,
+    success: bool,
+) {
+    let artifact_type = get_s3_prefix(artifact_type);
+    let status = if success { "success" } else { "error" };
+    metrics::histogram!(
+        "artifacts.s3_duration_seconds",
+        "operation" => operation,
+        "artifact_type" => artifact_type,
+    )
+    .record(start.elapsed().as_secs_f64());
+    metrics::counter!(
+        "artifacts.s3_operations",
+        "operation" => operation,
+        "artifact_type" => artifact_type,
+        "status" => status,
+    )
+    .increment(1);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aws_sdk_s3::{
+        config::http::HttpResponse,
+        error::{ConnectorError, ErrorMetadata},
+        types::error::NoSuchKey,
+    };
     use spn_artifact_types::ArtifactType;
 
     #[test]
     fn private_stdin_has_its_own_prefix() {
         assert_eq!(get_s3_prefix(ArtifactType::PrivateStdin), "private-stdins");
     }
+
+    #[test]
+    fn test_bucket_region() {
+        assert_eq!(bucket_region(None), "us-east-1");
+        assert_eq!(bucket_region(Some("")), "us-east-1");
+        assert_eq!(bucket_region(Some("EU")), "eu-west-1");
+        assert_eq!(bucket_region(Some("us-west-2")), "us-west-2");
+    }
+
+    fn service_error(err: GetObjectError, status: u16) -> SdkError<GetObjectError> {
+        SdkError::service_error(
+            err,
+            HttpResponse::new(status.try_into().unwrap(), SdkBody::empty()),
+        )
+    }
+
+    fn generic_error(code: &str, status: u16) -> SdkError<GetObjectError> {
+        service_error(GetObjectError::generic(ErrorMetadata::builder().code(code).build()), status)
+    }
+
+    #[test]
+    fn test_is_retryable_s3_error() {
+        // Throttling, expired credentials and server errors are transient.
+        assert!(is_retryable_s3_error(&generic_error("SlowDown", 503)));
+        assert!(is_retryable_s3_error(&generic_error("ExpiredToken", 400)));
+        assert!(is_retryable_s3_error(&generic_error("InternalError", 500)));
+        assert!(is_retryable_s3_error(&generic_error("TooManyRequests", 429)));
+
+        // So are timeouts and connection failures.
+        assert!(is_retryable_s3_error(&SdkError::<GetObjectError>::timeout_error("timed out")));
+        let connection_reset = ConnectorError::io("connection reset".into());
+        assert!(is_retryable_s3_error(&SdkError::<GetObjectError>::dispatch_failure(
+            connection_reset
+        )));
+
+        // Missing objects, denied access and invalid requests are permanent.
+        let no_such_key = GetObjectError::NoSuchKey(NoSuchKey::builder().build());
+        assert!(!is_retryable_s3_error(&service_error(no_such_key, 404)));
+        assert!(!is_retryable_s3_error(&generic_error("AccessDenied", 403)));
+        assert!(!is_retryable_s3_error(&SdkError::<GetObjectError>::construction_failure(
+            "invalid bucket name"
+        )));
+    }
+
+    #[test]
+    fn test_is_retryable_download_error() {
+        // Errors returned by the S3 SDK are classified through the context.
+        let e = anyhow::Error::new(generic_error("NoSuchKey", 404)).context("failed to get object");
+        assert!(!is_retryable_download_error(&e));
+        let e = anyhow::Error::new(generic_error("SlowDown", 503)).context("failed to get object");
+        assert!(is_retryable_download_error(&e));
+
+        // Other errors, such as failing to read the body, are retried.
+        assert!(is_retryable_download_error(&anyhow!("Failed to read S3 object body")));
+    }
+    #[test]
+    fn test_verify_copy() {
+        let src_checksum = blake3::hash(b"proof");
+        assert!(verify_copy("proofs/id", &src_checksum, b"proof").is_ok());
+
+        // A copy of the same size with different contents is rejected.
+        let err = verify_copy("proofs/id", &src_checksum, b"pr00f").unwrap_err();
+        assert!(err.to_string().contains("proofs/id"));
+    }
+    #[test]
+    fn test_is_precondition_failed() {
+        let e = anyhow::Error::new(generic_error("PreconditionFailed", 412)).context("failed");
+        assert!(is_precondition_failed(&e));
+        let e = anyhow::Error::new(generic_error("SlowDown", 503)).context("failed");
+        assert!(!is_precondition_failed(&e));
+        assert!(!is_precondition_failed(&anyhow!("Failed to read S3 object body")));
+    }
+
+    #[tokio::test]
+    async fn test_partial_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let dst = dir.path().join("artifact");
+        let partial = PartialDownload::new(&dst);
+
+        // A fresh download starts from an empty file pinned to the artifact's ETag.
+        partial.prepare(Some("\"v1\""), 8).await.unwrap();
+        assert!(!partial.path.exists());
+        assert_eq!(std::fs::read_to_string(&partial.e_tag_path).unwrap(), "\"v1\"");
+
+        // An interrupted download of the same version is resumed.
+        std::fs::write(&partial.path, b"abcd").unwrap();
+        partial.prepare(Some("\"v1\""), 8).await.unwrap();
+        assert_eq!(std::fs::read(&partial.path).unwrap(), b"abcd");
+
+        // Once complete, the download is moved to its destination.
+        std::fs::write(&partial.path, b"abcdefgh").unwrap();
+        partial.finish(&dst).await.unwrap();
+        assert_eq!(std::fs::read(&dst).unwrap(), b"abcdefgh");
+        assert!(!partial.path.exists() && !partial.e_tag_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_partial_download_etag_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let partial = PartialDownload::new(&dir.path().join("artifact"));
+        partial.prepare(Some("\"v1\""), 8).await.unwrap();
+        std::fs::write(&partial.path, b"abcd").unwrap();
+
+        // The staged bytes of another version are deleted instead of being resumed.
+        partial.prepare(Some("\"v2\""), 8).await.unwrap();
+        assert!(!partial.path.exists());
+        assert_eq!(std::fs::read_to_string(&partial.e_tag_path).unwrap(), "\"v2\"");
+
+        // So are staged bytes without an ETag to check them against.
+        std::fs::write(&partial.path, b"abcd").unwrap();
+        partial.prepare(None, 8).await.unwrap();
+        assert!(!partial.path.exists() && !partial.e_tag_path.exists());
+    }
 }
@@ -0,0 +1,245 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use spn_network_types::prover_network_client::ProverNetworkClient;
+use tonic::{
+    body::BoxBody,
+    codec::CompressionEncoding,
+    codegen::{http, BoxFuture, InterceptedService, Service},
+    transport::{Channel, Endpoint, Error},
+};
+use tracing::{info, warn};
+
+use crate::AuthInterceptor;
+
+/// A client for the network that reconnects after transport errors.
+pub type NetworkClient = ProverNetworkClient<NetworkChannel>;
+
+/// The default maximum size of a message received from the network (256 MiB).
+///
+/// This is larger than tonic's default of 4 MiB so that responses carrying large proofs or public
+/// values aren't rejected.
+pub const DEFAULT_MAX_DECODING_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+/// The default maximum size of a message sent to the network (256 MiB).
+///
+/// This bounds the size of the proofs submitted with `FulfillProof`, which can be large for
+/// uncompressed proof modes.
+pub const DEFAULT_MAX_ENCODING_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+/// A gRPC channel to the network that reconnects after transport errors.
+///
+/// The underlying [`Channel`] connects lazily, so creating it never blocks or fails, and once the
+/// connection drops it is re-established on the next request instead of failing every request
+/// until the process restarts. Requests made while the network is unreachable still fail, so
+/// callers can keep polling with their usual retries until the connection comes back.
+///
+/// Clones share their connection state, which is logged when it changes and can be checked with
+/// [`NetworkChannel::is_connected`].
+///
+/// If enabled with [`NetworkChannel::with_metrics`], the latency and errors of each request are
+/// recorded into the [`metrics`] facade, labeled with the name of the RPC method:
+///
+/// - `rpc.request_duration_seconds`: a histogram of the time until the response headers arrive.
+/// - `rpc.request_errors`: a counter of requests that failed with a transport error or returned a
+///   non-OK gRPC status in their headers.
+///
+/// If enabled with [`NetworkChannel::with_compression`], the clients created with
+/// [`NetworkChannel::client`] accept responses compressed with gzip. Requests are only compressed
+/// if also enabled with [`NetworkChannel::with_request_compression`].
+///
+/// The clients also limit the size of the messages they send and receive, which defaults to
+/// [`DEFAULT_MAX_ENCODING_MESSAGE_SIZE`] and [`DEFAULT_MAX_DECODING_MESSAGE_SIZE`]. Messages are
+/// buffered in full, so the limits also bound the memory that a single message can take.
+#[derive(Debug, Clone)]
+pub struct NetworkChannel {
+    /// The underlying channel, which attaches the auth headers to each request.
+    inner: InterceptedService<Channel, AuthInterceptor>,
+    /// Whether the last request reached the network.
+    connected: Arc<AtomicBool>,
+    /// Whether to record the latency and errors of each request.
+    metrics: bool,
+    /// Whether the clients accept responses compressed with gzip.
+    compression: bool,
+    /// Whether the clients compress their requests with gzip.
+    request_compression: bool,
+    /// The maximum size of a message received by the clients.
+    max_decoding_message_size: usize,
+    /// The maximum size of a message sent by the clients.
+    max_encoding_message_size: usize,
+}
+
+impl NetworkChannel {
+    /// Create a new [`NetworkChannel`] that lazily connects to the given endpoint.
+    #[must_use]
+    pub fn new(endpoint: &Endpoint) -> Self {
+        Self::authenticated(endpoint, AuthInterceptor::default())
+    }
+
+    /// Create a new [`NetworkChannel`] that lazily connects to the given endpoint and attaches the
+    /// headers of the given [`AuthInterceptor`] to each request.
+    #[must_use]
+    pub fn authenticated(endpoint: &Endpoint, auth: AuthInterceptor) -> Self {
+        Self {
+            inner: InterceptedService::new(endpoint.connect_lazy(), auth),
+            connected: Arc::new(AtomicBool::new(true)),
+            metrics: false,
+            compression: false,
+            request_compression: false,
+            max_decoding_message_size: DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+            max_encoding_message_size: DEFAULT_MAX_ENCODING_MESSAGE_SIZE,
+        }
+    }
+
+    /// Record the latency and errors of each request into the [`metrics`] facade.
+    #[must_use]
+    pub fn with_metrics(mut self) -> Self {
+        self.metrics = true;
+        self
+    }
+
+    /// Accept responses compressed with gzip in the clients created from this channel.
+    ///
+    /// The clients merely advertise that they accept gzip, so responses are only compressed if the
+    /// server supports it and this is safe to enable against any server.
+    #[must_use]
+    pub fn with_compression(mut self) -> Self {
+        self.compression = true;
+        self
+    }
+
+    /// Also compress the requests of the clients created from this channel with gzip.
+    ///
+    /// Unlike responses, requests are compressed without negotiation, and servers that don't
+    /// accept gzip reject them as unimplemented. This should only be enabled against servers
+    /// known to accept gzip.
+    #[must_use]
+    pub fn with_request_compression(mut self) -> Self {
+        self.compression = true;
+        self.request_compression = true;
+        self
+    }
+
+    /// Set the maximum size of a message received from the network.
+    ///
+    /// Larger responses fail with `OutOfRange`. Raising the limit allows larger proofs to be
+    /// fetched at the cost of more memory per response. Defaults to
+    /// [`DEFAULT_MAX_DECODING_MESSAGE_SIZE`].
+    #[must_use]
+    pub fn with_max_decoding_message_size(mut self, limit: usize) -> Self {
+        self.max_decoding_message_size = limit;
+        self
+    }
+
+    /// Set the maximum size of a message sent to the network.
+    ///
+    /// Larger requests fail with `OutOfRange` before they are sent. Defaults to
+    /// [`DEFAULT_MAX_ENCODING_MESSAGE_SIZE`].
+    #[must_use]
+    pub fn with_max_encoding_message_size(mut self, limit: usize) -> Self {
+        self.max_encoding_message_size = limit;
+        self
+    }
+
+    /// Create a [`NetworkClient`] that uses this channel.
+    #[must_use]
+    pub fn client(self) -> NetworkClient {
+        let compression = self.compression;
+        let request_compression = self.request_compression;
+        let max_decoding_message_size = self.max_decoding_message_size;
+        let max_encoding_message_size = self.max_encoding_message_size;
+        let mut client = ProverNetworkClient::new(self)
+            .max_decoding_message_size(max_decoding_message_size)
+            .max_encoding_message_size(max_encoding_message_size);
+        if compression {
+            client = client.accept_compressed(CompressionEncoding::Gzip);
+        }
+        if request_compression {
+            client = client.send_compressed(CompressionEncoding::Gzip);
+        }
+        client
+    }
+
+    /// Whether the last request reached the network.
+    ///
+    /// This is `false` while the channel is reconnecting after a transport error.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+}
+
+impl Service<http::Request<BoxBody>> for NetworkChannel {
+    type Response = http::Response<BoxBody>;
+    type Error = Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        let connected = self.connected.clone();
+        let method = self.metrics.then(|| rpc_method(request.uri().path()).to_string());
+        let start = Instant::now();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let result = response.await;
+            if let Some(method) = method {
+                record_metrics(method, start, &result);
+            }
+            match &result {
+                Ok(_) => {
+                    if !connected.swap(true, Ordering::Relaxed) {
+                        info!("Reconnected to the network.");
+                    }
+                }
+                Err(e) => {
+                    if connected.swap(false, Ordering::Relaxed) {
+                        warn!("Lost connection to the network, reconnecting: {e}");
+                    }
+                }
+            }
+            result
+        })
+    }
+}
+
+/// Returns the name of the RPC method from the path of a gRPC request, e.g. `GetNonce` for
+/// `/network.ProverNetwork/GetNonce`.
+fn rpc_method(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Records the latency of a request and whether it failed.
+fn record_metrics(method: String, start: Instant, result: &Result<http::Response<BoxBody>, Error>) {
+    metrics::histogram!("rpc.request_duration_seconds", "method" => method.clone())
+        .record(start.elapsed().as_secs_f64());
+
+    // Statuses returned without a response body are sent in the headers. Statuses sent in the
+    // trailers of a streamed body are not inspected, since that would require buffering it.
+    let failed = match result {
+        Ok(response) => response.headers().get("grpc-status").is_some_and(|status| status != "0"),
+        Err(_) => true,
+    };
+    if failed {
+        metrics::counter!("rpc.request_errors", "method" => method).increment(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_method() {
+        assert_eq!(rpc_method("/network.ProverNetwork/GetProofRequest"), "GetProofRequest");
+        assert_eq!(rpc_method("GetNonce"), "GetNonce");
+    }
+}
@@ -2,16 +2,14 @@ use std::str::FromStr;
 
 use alloy_primitives::U256;
 use anyhow::Result;
-use spn_network_types::{
-    prover_network_client::ProverNetworkClient, GetBalanceRequest, GetOwnerRequest,
-};
-use tonic::{transport::Channel, Request};
+use spn_network_types::{GetBalanceRequest, GetDelegationRequest, GetOwnerRequest};
+use tonic::Request;
 use tracing::debug;
 
-use crate::RetryableRpc;
+use crate::{NetworkClient, RetryableRpc};
 
 /// Fetches the balance of an address on the network.
-pub async fn fetch_balance(network: &ProverNetworkClient<Channel>, address: &[u8]) -> Result<U256> {
+pub async fn fetch_balance(network: &NetworkClient, address: &[u8]) -> Result<U256> {
     let address = address.to_vec();
     let response = network
         .clone()
@@ -30,12 +28,29 @@ pub async fn fetch_balance(network: &ProverNetworkClient<Channel>, address: &[u8
 }
 
 /// Fetches the owner of an address/prover on the network.
-pub async fn fetch_owner(
-    network: &ProverNetworkClient<Channel>,
-    address: &[u8],
-) -> Result<Vec<u8>> {
+pub async fn fetch_owner(network: &NetworkClient, address: &[u8]) -> Result<Vec<u8>> {
     let address = address.to_vec();
     let req = Request::new(GetOwnerRequest { address });
     let resp = network.clone().get_owner(req).await?;
     Ok(resp.into_inner().owner)
 }
+
+/// Fetches the addresses allowed to sign bids on behalf of a prover on the network.
+///
+/// This follows the rule enforced by the vApp: a prover's signer is its owner until the owner
+/// delegates signing to another account, and the owner may authorize additional signers alongside
+/// it. The delegated signer comes first. Returns an empty list if the prover has no owner, i.e. it
+/// doesn't exist.
+pub async fn fetch_prover_signers(network: &NetworkClient, prover: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let req = Request::new(GetDelegationRequest { prover: prover.to_vec() });
+    let delegation = network.clone().get_delegation(req).await?.into_inner().delegation;
+    let (delegate, additional_delegates) = delegation
+        .map(|delegation| (delegation.delegate, delegation.additional_delegates))
+        .unwrap_or_default();
+
+    let signer = if delegate.is_empty() { fetch_owner(network, prover).await? } else { delegate };
+    if signer.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(std::iter::once(signer).chain(additional_delegates).collect())
+}
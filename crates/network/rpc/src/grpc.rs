@@ -3,11 +3,83 @@ use tonic::transport::{Endpoint, Error};
 
 /// Configure an endpoint with appropriate timeouts and keep-alive settings.
 pub fn configure_endpoint(addr: &str) -> Result<Endpoint, Error> {
+    configure_endpoint_with(addr, &EndpointConfig::default())
+}
+
+/// Configure an endpoint with the timeouts and keep-alive settings of the given config.
+pub fn configure_endpoint_with(addr: &str, config: &EndpointConfig) -> Result<Endpoint, Error> {
     Ok(Endpoint::new(addr.to_string())?
-        .timeout(Duration::from_secs(15))
-        .connect_timeout(Duration::from_secs(15))
+        .timeout(config.request_timeout)
+        .connect_timeout(config.connect_timeout)
         .keep_alive_while_idle(true)
-        .http2_keep_alive_interval(Duration::from_secs(15))
-        .keep_alive_timeout(Duration::from_secs(15))
-        .tcp_keepalive(Some(Duration::from_secs(30))))
+        .http2_keep_alive_interval(config.keep_alive_interval)
+        .keep_alive_timeout(config.keep_alive_timeout)
+        .tcp_keepalive(config.tcp_keepalive))
+}
+
+/// The timeouts and keep-alive settings of a gRPC endpoint.
+///
+/// The HTTP/2 keep-alive pings detect dead connections, e.g. behind a NAT that silently dropped
+/// them, within `keep_alive_interval + keep_alive_timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointConfig {
+    /// The timeout for each request.
+    pub request_timeout: Duration,
+    /// The timeout for establishing a connection.
+    pub connect_timeout: Duration,
+    /// The interval of TCP keep-alive probes, or `None` to disable them.
+    pub tcp_keepalive: Option<Duration>,
+    /// The interval at which HTTP/2 keep-alive pings are sent.
+    pub keep_alive_interval: Duration,
+    /// How long to wait for a reply to an HTTP/2 keep-alive ping before closing the connection.
+    pub keep_alive_timeout: Duration,
+}
+
+impl EndpointConfig {
+    /// Set the timeout for each request.
+    #[must_use]
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Set the timeout for establishing a connection.
+    #[must_use]
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the interval of TCP keep-alive probes, or `None` to disable them.
+    #[must_use]
+    pub fn with_tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.tcp_keepalive = interval;
+        self
+    }
+
+    /// Set the interval at which HTTP/2 keep-alive pings are sent.
+    #[must_use]
+    pub fn with_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.keep_alive_interval = interval;
+        self
+    }
+
+    /// Set how long to wait for a reply to an HTTP/2 keep-alive ping.
+    #[must_use]
+    pub fn with_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive_timeout = timeout;
+        self
+    }
+}
+
+impl Default for EndpointConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(15),
+            connect_timeout: Duration::from_secs(15),
+            tcp_keepalive: Some(Duration::from_secs(30)),
+            keep_alive_interval: Duration::from_secs(15),
+            keep_alive_timeout: Duration::from_secs(15),
+        }
+    }
 }
@@ -20,10 +20,14 @@
 #![allow(clippy::struct_excessive_bools)]
 #![warn(missing_docs)]
 
+mod auth;
+mod channel;
 mod fetch;
 mod grpc;
 mod retry;
 
+pub use auth::*;
+pub use channel::*;
 pub use fetch::*;
 pub use grpc::*;
 pub use retry::*;
@@ -1,8 +1,8 @@
 use anyhow::Result;
-use backoff::{future::retry, Error as BackoffError, ExponentialBackoff};
 use spn_network_types::prover_network_client::ProverNetworkClient;
+use spn_utils::{retry_with_policy, Jitter, RetryPolicy};
 use std::time::Duration;
-use tonic::{async_trait, transport::Channel, Code};
+use tonic::{async_trait, Code};
 use tracing::{error, warn};
 
 /// Default timeout for retry operations.
@@ -41,76 +41,66 @@ where
     F: Fn() -> Fut + Send + Sync,
     Fut: std::future::Future<Output = Result<T>> + Send,
 {
-    let backoff = ExponentialBackoff {
-        initial_interval: Duration::from_secs(1),
-        max_interval: Duration::from_secs(120),
-        max_elapsed_time: timeout,
-        ..Default::default()
-    };
-
-    retry(backoff, || async {
-        match operation().await {
-            Ok(result) => Ok(result),
-            Err(e) => {
-                // Check for tonic status errors.
-                if let Some(status) = e.downcast_ref::<tonic::Status>() {
-                    match status.code() {
-                        Code::Unavailable
-                        | Code::DeadlineExceeded
-                        | Code::Internal
-                        | Code::Aborted => {
-                            warn!(
-                                "Network temporarily unavailable when {} due to {}, retrying...",
-                                operation_name,
-                                status.message(),
-                            );
-                            Err(BackoffError::transient(e))
-                        }
-                        Code::NotFound => {
-                            error!("{} not found due to {}", operation_name, status.message(),);
-                            Err(BackoffError::permanent(e))
-                        }
-                        _ => {
-                            error!(
-                                "Permanent error encountered when {}: {} ({})",
-                                operation_name,
-                                status.message(),
-                                status.code()
-                            );
-                            Err(BackoffError::permanent(e))
-                        }
-                    }
-                } else {
-                    // Check for common transport errors.
-                    let error_msg = e.to_string().to_lowercase();
-                    let is_transient = error_msg.contains("tls handshake")
-                        || error_msg.contains("dns error")
-                        || error_msg.contains("connection reset")
-                        || error_msg.contains("broken pipe")
-                        || error_msg.contains("transport error")
-                        || error_msg.contains("failed to lookup")
-                        || error_msg.contains("timeout")
-                        || error_msg.contains("deadline exceeded");
+    retry_operation_with_jitter(operation, timeout, operation_name, Jitter::Proportional).await
+}
 
-                    if is_transient {
-                        warn!(
-                            "Transient transport error when {}: {}, retrying...",
-                            operation_name, error_msg
-                        );
-                        Err(BackoffError::transient(e))
-                    } else {
-                        error!("Permanent error when {}: {}", operation_name, error_msg);
-                        Err(BackoffError::permanent(e))
-                    }
-                }
-            }
+/// Execute an async operation with exponential backoff retries, randomizing the delays between
+/// retries according to the given [`Jitter`].
+pub async fn retry_operation_with_jitter<T, F, Fut>(
+    operation: F,
+    timeout: Option<Duration>,
+    operation_name: &str,
+    jitter: Jitter,
+) -> Result<T>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: std::future::Future<Output = Result<T>> + Send,
+{
+    let policy = RetryPolicy::default()
+        .with_multiplier(1.5)
+        .with_max_elapsed_time(timeout)
+        .with_jitter(jitter);
+    retry_with_policy(&policy, operation, |e| {
+        let retryable = is_retryable_rpc_error(e);
+        if retryable {
+            warn!("Transient error when {}: {}, retrying...", operation_name, e);
+        } else {
+            error!("Permanent error when {}: {}", operation_name, e);
         }
+        retryable
     })
     .await
 }
 
+/// Whether an error returned by an RPC operation is transient and worth retrying.
+///
+/// gRPC statuses are classified by their code: an unavailable network, an exceeded deadline, an
+/// internal error or an aborted request are retried, while any other status, such as
+/// `NOT_FOUND` or `INVALID_ARGUMENT`, is returned immediately. Other errors are retried if they
+/// look like a transport failure.
+#[must_use]
+pub fn is_retryable_rpc_error(e: &anyhow::Error) -> bool {
+    if let Some(status) = e.chain().find_map(|cause| cause.downcast_ref::<tonic::Status>()) {
+        return matches!(
+            status.code(),
+            Code::Unavailable | Code::DeadlineExceeded | Code::Internal | Code::Aborted
+        );
+    }
+
+    // Check for common transport errors.
+    let error_msg = e.to_string().to_lowercase();
+    error_msg.contains("tls handshake") ||
+        error_msg.contains("dns error") ||
+        error_msg.contains("connection reset") ||
+        error_msg.contains("broken pipe") ||
+        error_msg.contains("transport error") ||
+        error_msg.contains("failed to lookup") ||
+        error_msg.contains("timeout") ||
+        error_msg.contains("deadline exceeded")
+}
+
 #[async_trait]
-impl RetryableRpc for ProverNetworkClient<Channel> {
+impl<C: Send + Sync> RetryableRpc for ProverNetworkClient<C> {
     async fn with_retry<'a, T, F, Fut>(&'a self, operation: F, operation_name: &str) -> Result<T>
     where
         F: Fn() -> Fut + Send + Sync + 'a,
@@ -134,3 +124,27 @@ impl RetryableRpc for ProverNetworkClient<Channel> {
         retry_operation(operation, Some(timeout), operation_name).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Status;
+
+    #[test]
+    fn test_is_retryable_rpc_error() {
+        // Transient gRPC statuses are retried, even when wrapped in context.
+        assert!(is_retryable_rpc_error(&Status::unavailable("connection refused").into()));
+        assert!(is_retryable_rpc_error(&Status::deadline_exceeded("timed out").into()));
+        let e = anyhow::Error::from(Status::internal("oops")).context("failed to get nonce");
+        assert!(is_retryable_rpc_error(&e));
+
+        // Other gRPC statuses are permanent.
+        assert!(!is_retryable_rpc_error(&Status::not_found("request not found").into()));
+        assert!(!is_retryable_rpc_error(&Status::invalid_argument("invalid signature").into()));
+        assert!(!is_retryable_rpc_error(&Status::permission_denied("not whitelisted").into()));
+
+        // Other errors are retried if they look like a transport failure.
+        assert!(is_retryable_rpc_error(&anyhow::anyhow!("transport error: connection reset")));
+        assert!(!is_retryable_rpc_error(&anyhow::anyhow!("failed to decode response")));
+    }
+}
@@ -1,17 +1,93 @@
-use std::process::Command;
+use std::{process::Command, sync::OnceLock};
 use tracing::debug;
 
+/// Information about a CUDA GPU as reported by nvidia-smi.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuInfo {
+    /// The name of the GPU, e.g. `NVIDIA GeForce RTX 4090`.
+    pub name: String,
+    /// The total VRAM of the GPU in MiB.
+    pub memory_total_mib: u64,
+    /// The free VRAM of the GPU in MiB.
+    pub memory_free_mib: u64,
+}
+
+/// Whether CUDA is available, detected at most once per process.
+static CUDA_SUPPORT: OnceLock<bool> = OnceLock::new();
+
 /// Check if CUDA is available by testing if nvidia-smi is installed and CUDA GPUs are present.
+///
+/// GPU presence doesn't change during the lifetime of the process, so the result is cached after
+/// the first call.
+#[must_use]
 pub fn has_cuda_support() -> bool {
+    cuda_support(&CUDA_SUPPORT, detect_gpus)
+}
+
+/// Whether the probe finds any GPU, running it only if the cell isn't initialized yet.
+fn cuda_support(cell: &OnceLock<bool>, probe: impl FnOnce() -> Vec<GpuInfo>) -> bool {
+    *cell.get_or_init(|| !probe().is_empty())
+}
+
+/// Detect the CUDA GPUs available on the machine.
+///
+/// With the `nvml` feature enabled, the driver is queried directly through NVML. Otherwise, or if
+/// NVML fails to initialize, nvidia-smi is queried instead. Returns an empty list if no GPUs are
+/// present.
+#[must_use]
+pub fn detect_gpus() -> Vec<GpuInfo> {
+    #[cfg(feature = "nvml")]
+    if let Some(gpus) = detect_gpus_with_nvml() {
+        return gpus;
+    }
+
+    detect_gpus_with_nvidia_smi()
+}
+
+/// Detect the CUDA GPUs available on the machine using NVML.
+///
+/// Returns `None` if NVML cannot be initialized, e.g. because the driver is not installed.
+#[cfg(feature = "nvml")]
+fn detect_gpus_with_nvml() -> Option<Vec<GpuInfo>> {
+    const MIB: u64 = 1024 * 1024;
+
+    let nvml = match nvml_wrapper::Nvml::init() {
+        Ok(nvml) => nvml,
+        Err(e) => {
+            debug!("failed to initialize nvml: {}", e);
+            return None;
+        }
+    };
+    let count = nvml.device_count().ok()?;
+    let gpus = (0..count)
+        .filter_map(|index| {
+            let device = nvml.device_by_index(index).ok()?;
+            let name = device.name().ok()?;
+            let memory = device.memory_info().ok()?;
+            Some(GpuInfo {
+                name,
+                memory_total_mib: memory.total / MIB,
+                memory_free_mib: memory.free / MIB,
+            })
+        })
+        .collect();
+    Some(gpus)
+}
+
+/// Detect the CUDA GPUs available on the machine by querying nvidia-smi.
+fn detect_gpus_with_nvidia_smi() -> Vec<GpuInfo> {
     // Common paths where nvidia-smi might be installed.
     let nvidia_smi_paths = ["nvidia-smi", "/usr/bin/nvidia-smi", "/usr/local/bin/nvidia-smi"];
 
     for path in nvidia_smi_paths {
-        match Command::new(path).output() {
+        match Command::new(path)
+            .args(["--query-gpu=name,memory.total,memory.free", "--format=csv,noheader"])
+            .output()
+        {
             Ok(output) => {
                 if output.status.success() {
                     debug!("found working nvidia-smi at: {}", path);
-                    return true;
+                    return parse_gpu_info(&String::from_utf8_lossy(&output.stdout));
                 }
                 debug!("nvidia-smi at {} exists but returned error status", path);
             }
@@ -23,5 +99,84 @@ pub fn has_cuda_support() -> bool {
 
     debug!("no working nvidia-smi found in any standard location");
 
-    false
+    Vec::new()
+}
+
+/// Parse the CSV output of the nvidia-smi query used by [`detect_gpus`].
+///
+/// Each line has the form `NVIDIA GeForce RTX 4090, 24564 MiB, 24080 MiB`. Lines that cannot be
+/// parsed are skipped.
+fn parse_gpu_info(output: &str) -> Vec<GpuInfo> {
+    let parse_mib = |field: &str| field.trim().trim_end_matches("MiB").trim().parse::<u64>().ok();
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(',');
+            let name = fields.next()?.trim().to_string();
+            let memory_total_mib = parse_mib(fields.next()?)?;
+            let memory_free_mib = parse_mib(fields.next()?)?;
+            Some(GpuInfo { name, memory_total_mib, memory_free_mib })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_cuda_support_probes_once() {
+        let calls = AtomicUsize::new(0);
+        let probe = |gpus: usize| {
+            let calls = &calls;
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let gpu = GpuInfo {
+                    name: "NVIDIA L4".to_string(),
+                    memory_total_mib: 23034,
+                    memory_free_mib: 22700,
+                };
+                vec![gpu; gpus]
+            }
+        };
+
+        // The GPUs found by the first probe are cached.
+        let cell = OnceLock::new();
+        assert!(cuda_support(&cell, probe(1)));
+        assert!(cuda_support(&cell, probe(0)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // So is the absence of GPUs.
+        let cell = OnceLock::new();
+        assert!(!cuda_support(&cell, probe(0)));
+        assert!(!cuda_support(&cell, probe(1)));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_parse_gpu_info() {
+        let output = "NVIDIA GeForce RTX 4090, 24564 MiB, 24080 MiB\n\
+                      NVIDIA L4, 23034 MiB, 22700 MiB\n\
+                      garbage\n";
+        let gpus = parse_gpu_info(output);
+        assert_eq!(
+            gpus,
+            vec![
+                GpuInfo {
+                    name: "NVIDIA GeForce RTX 4090".to_string(),
+                    memory_total_mib: 24564,
+                    memory_free_mib: 24080,
+                },
+                GpuInfo {
+                    name: "NVIDIA L4".to_string(),
+                    memory_total_mib: 23034,
+                    memory_free_mib: 22700,
+                },
+            ]
+        );
+        assert!(parse_gpu_info("").is_empty());
+    }
 }
@@ -2,9 +2,15 @@ use alloy_primitives::B256;
 use alloy_sol_types::eip712_domain;
 use anyhow::Result;
 use serde::{Deserialize, Deserializer};
-use std::sync::LazyLock;
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, PoisonError, RwLock},
+};
 
 /// Deserializes a domain name into a [B256] domain separator.
+///
+/// The name is resolved against the process-global registry, so domains added with
+/// [`register_domain`] are recognized.
 pub fn deserialize_domain<'de, D>(deserializer: D) -> Result<B256, D::Error>
 where
     D: Deserializer<'de>,
@@ -14,30 +20,127 @@ where
 }
 
 /// Returns the domain separator for the given domain name.
+///
+/// The name is resolved against the process-global registry, which always contains the mainnet and
+/// Sepolia domains.
 pub fn get_domain(name: &str) -> Result<B256> {
-    match name {
-        "SPN_MAINNET_V1_DOMAIN" => Ok(*SPN_MAINNET_V1_DOMAIN),
-        "SPN_SEPOLIA_V1_DOMAIN" => Ok(*SPN_SEPOLIA_V1_DOMAIN),
-        _ => Err(anyhow::anyhow!("Invalid domain name: {}", name)),
+    DOMAIN_REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .resolve(name)
+        .ok_or_else(|| anyhow::anyhow!("Invalid domain name: {name}"))
+}
+
+/// Registers a domain in the process-global registry used by [`get_domain`] and
+/// [`deserialize_domain`], returning the domain previously registered under the name, if any.
+///
+/// This lets services add the domains of other networks at startup, e.g. from their config.
+pub fn register_domain(name: impl Into<String>, domain: B256) -> Option<B256> {
+    DOMAIN_REGISTRY.write().unwrap_or_else(PoisonError::into_inner).register(name, domain)
+}
+
+/// The process-global domain registry.
+static DOMAIN_REGISTRY: LazyLock<RwLock<DomainRegistry>> =
+    LazyLock::new(|| RwLock::new(DomainRegistry::new()));
+
+/// A registry mapping domain names to their domain separators.
+#[derive(Debug, Clone)]
+pub struct DomainRegistry {
+    /// The registered domains, keyed by name.
+    domains: HashMap<String, B256>,
+}
+
+impl DomainRegistry {
+    /// Create a new [`DomainRegistry`] seeded with the mainnet and Sepolia domains.
+    #[must_use]
+    pub fn new() -> Self {
+        let domains = HashMap::from([
+            ("SPN_MAINNET_V1_DOMAIN".to_string(), *SPN_MAINNET_V1_DOMAIN),
+            ("SPN_SEPOLIA_V1_DOMAIN".to_string(), *SPN_SEPOLIA_V1_DOMAIN),
+        ]);
+        Self { domains }
+    }
+
+    /// Register a domain under the given name, returning the domain previously registered under
+    /// the name, if any.
+    pub fn register(&mut self, name: impl Into<String>, domain: B256) -> Option<B256> {
+        self.domains.insert(name.into(), domain)
+    }
+
+    /// Returns the domain registered under the given name, if any.
+    #[must_use]
+    pub fn resolve(&self, name: &str) -> Option<B256> {
+        self.domains.get(name).copied()
     }
 }
 
-/// The [`alloy_sol_types::Eip712Domain`] separator for the vApp on Sepolia.
-pub static SPN_SEPOLIA_V1_DOMAIN: LazyLock<B256> = LazyLock::new(|| {
+impl Default for DomainRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The chain id of Ethereum mainnet.
+const MAINNET_CHAIN_ID: u64 = 1;
+
+/// The chain id of Sepolia.
+const SEPOLIA_CHAIN_ID: u64 = 11155111;
+
+/// Returns the [`alloy_sol_types::Eip712Domain`] separator for the vApp on the given chain.
+///
+/// This allows deployments on other chains, like Holesky or a local Anvil node, to sign with their
+/// own domain.
+#[must_use]
+pub fn domain_for_chain_id(chain_id: u64) -> B256 {
     let domain = eip712_domain! {
         name: "Succinct Prover Network",
         version: "1.0.0",
-        chain_id: 11155111,
+        chain_id: chain_id,
     };
     domain.separator()
-});
+}
 
 /// The [`alloy_sol_types::Eip712Domain`] separator for the vApp on Sepolia.
-pub static SPN_MAINNET_V1_DOMAIN: LazyLock<B256> = LazyLock::new(|| {
-    let domain = eip712_domain! {
-        name: "Succinct Prover Network",
-        version: "1.0.0",
-        chain_id: 1,
-    };
-    domain.separator()
-});
+pub static SPN_SEPOLIA_V1_DOMAIN: LazyLock<B256> =
+    LazyLock::new(|| domain_for_chain_id(SEPOLIA_CHAIN_ID));
+
+/// The [`alloy_sol_types::Eip712Domain`] separator for the vApp on mainnet.
+pub static SPN_MAINNET_V1_DOMAIN: LazyLock<B256> =
+    LazyLock::new(|| domain_for_chain_id(MAINNET_CHAIN_ID));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_for_chain_id() {
+        assert_eq!(get_domain("SPN_MAINNET_V1_DOMAIN").unwrap(), *SPN_MAINNET_V1_DOMAIN);
+        assert_eq!(get_domain("SPN_SEPOLIA_V1_DOMAIN").unwrap(), *SPN_SEPOLIA_V1_DOMAIN);
+        assert!(get_domain("SPN_HOLESKY_V1_DOMAIN").is_err());
+
+        // Each chain gets its own separator.
+        let holesky = domain_for_chain_id(17000);
+        assert_ne!(holesky, *SPN_MAINNET_V1_DOMAIN);
+        assert_ne!(holesky, *SPN_SEPOLIA_V1_DOMAIN);
+        assert_eq!(holesky, domain_for_chain_id(17000));
+    }
+
+    #[test]
+    fn test_domain_registry() {
+        let mut registry = DomainRegistry::new();
+        assert_eq!(registry.resolve("SPN_MAINNET_V1_DOMAIN"), Some(*SPN_MAINNET_V1_DOMAIN));
+        assert_eq!(registry.resolve("SPN_LOCAL_V1_DOMAIN"), None);
+
+        let local = domain_for_chain_id(31337);
+        assert_eq!(registry.register("SPN_LOCAL_V1_DOMAIN", local), None);
+        assert_eq!(registry.resolve("SPN_LOCAL_V1_DOMAIN"), Some(local));
+        assert_eq!(registry.register("SPN_LOCAL_V1_DOMAIN", local), Some(local));
+
+        // Domains registered globally are picked up by deserialization.
+        register_domain("SPN_ANVIL_V1_DOMAIN", local);
+        let deserializer = serde::de::value::StrDeserializer::<serde::de::value::Error>::new(
+            "SPN_ANVIL_V1_DOMAIN",
+        );
+        assert_eq!(deserialize_domain(deserializer).unwrap(), local);
+    }
+}
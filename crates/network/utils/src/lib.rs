@@ -24,10 +24,16 @@ mod cuda;
 mod domain;
 mod error;
 mod logger;
+mod redact;
+#[cfg(feature = "retry")]
+mod retry;
 mod time;
 
 pub use cuda::*;
 pub use domain::*;
 pub use error::*;
 pub use logger::*;
+pub use redact::*;
+#[cfg(feature = "retry")]
+pub use retry::*;
 pub use time::*;
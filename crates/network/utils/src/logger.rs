@@ -1,10 +1,16 @@
+use std::{io, path::PathBuf};
+
 use serde::Deserialize;
+use tracing_appender::{non_blocking::WorkerGuard, rolling};
 use tracing_subscriber::{
-    fmt::{self},
+    fmt::{self, MakeWriter},
+    layer::Layered,
     prelude::*,
-    EnvFilter,
+    EnvFilter, Layer, Registry,
 };
 
+use crate::RedactingMakeWriter;
+
 /// Format for log output.
 #[derive(Debug, Deserialize, Clone, Copy)]
 pub enum LogFormat {
@@ -16,20 +22,348 @@ pub enum LogFormat {
     Minimal,
 }
 
+/// How often a rolling log file is rotated.
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+pub enum LogRotation {
+    /// Rotate the log file every minute.
+    Minutely,
+    /// Rotate the log file every hour.
+    Hourly,
+    /// Rotate the log file every day.
+    #[default]
+    Daily,
+    /// Never rotate the log file.
+    Never,
+}
+
+impl From<LogRotation> for rolling::Rotation {
+    fn from(rotation: LogRotation) -> Self {
+        match rotation {
+            LogRotation::Minutely => rolling::Rotation::MINUTELY,
+            LogRotation::Hourly => rolling::Rotation::HOURLY,
+            LogRotation::Daily => rolling::Rotation::DAILY,
+            LogRotation::Never => rolling::Rotation::NEVER,
+        }
+    }
+}
+
+/// Configuration for writing logs to a rolling file in addition to stdout.
+#[derive(Debug, Clone)]
+pub struct RollingFileConfig {
+    /// The directory to write the log files to.
+    pub directory: PathBuf,
+    /// The prefix of the log file names, e.g. `spn-node.log`.
+    pub file_name_prefix: String,
+    /// How often the log file is rotated.
+    pub rotation: LogRotation,
+}
+
+impl RollingFileConfig {
+    /// Create a new [`RollingFileConfig`] that rotates daily.
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>, file_name_prefix: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_name_prefix: file_name_prefix.into(),
+            rotation: LogRotation::default(),
+        }
+    }
+
+    /// Set how often the log file is rotated.
+    #[must_use]
+    pub fn with_rotation(mut self, rotation: LogRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+}
+
+/// Configuration for exporting spans to an OpenTelemetry collector over OTLP.
+#[cfg(feature = "otlp")]
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// The gRPC endpoint of the collector, e.g. `http://localhost:4317`.
+    pub endpoint: String,
+    /// The service name the spans are reported under.
+    pub service_name: String,
+}
+
+#[cfg(feature = "otlp")]
+impl OtlpConfig {
+    /// Create a new [`OtlpConfig`].
+    #[must_use]
+    pub fn new(endpoint: impl Into<String>, service_name: impl Into<String>) -> Self {
+        Self { endpoint: endpoint.into(), service_name: service_name.into() }
+    }
+}
+
+/// The subscriber that output layers are attached to.
+type FilteredRegistry = Layered<EnvFilter, Registry>;
+
+/// A type-erased output layer.
+type BoxedLayer = Box<dyn Layer<FilteredRegistry> + Send + Sync>;
+
+/// A builder for configuring and initializing the logging system.
+///
+/// Logs are always written to stdout in the configured [`LogFormat`].
+///
+/// # Filtering
+///
+/// Directives are applied in the following order, with later directives taking precedence over
+/// earlier ones for the same target:
+///
+/// 1. The `RUST_LOG` environment variable, defaulting to `info`.
+/// 2. The built-in directives, which silence noisy dependencies like the AWS SDK and hyper.
+/// 3. The directives passed to [`LoggerBuilder::with_directives`].
+///
+/// For example, `RUST_LOG=sqlx=debug` has no effect because the built-ins reset `sqlx` to `warn`,
+/// but `.with_directives(["sqlx=debug"])` re-enables its debug logs.
+#[derive(Debug, Clone)]
+pub struct LoggerBuilder {
+    /// The format of the log output.
+    format: LogFormat,
+    /// The rolling file to also write logs to, if any.
+    file: Option<RollingFileConfig>,
+    /// The filter directives to apply after the built-in ones.
+    directives: Vec<String>,
+    /// Whether to redact sensitive values from the output.
+    redact: bool,
+    /// The OTLP collector to export spans to, if any.
+    #[cfg(feature = "otlp")]
+    otlp: Option<OtlpConfig>,
+}
+
+impl LoggerBuilder {
+    /// Create a new [`LoggerBuilder`] that logs to stdout in the given format.
+    #[must_use]
+    pub fn new(format: LogFormat) -> Self {
+        Self {
+            format,
+            file: None,
+            directives: Vec::new(),
+            redact: false,
+            #[cfg(feature = "otlp")]
+            otlp: None,
+        }
+    }
+
+    /// Also write logs to a rolling file.
+    #[must_use]
+    pub fn with_rolling_file(mut self, file: RollingFileConfig) -> Self {
+        self.file = Some(file);
+        self
+    }
+
+    /// Add filter directives, e.g. `sqlx=debug`, that take precedence over the built-in ones.
+    ///
+    /// Directives that fail to parse are skipped with a warning.
+    #[must_use]
+    pub fn with_directives<I, D>(mut self, directives: I) -> Self
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<String>,
+    {
+        self.directives.extend(directives.into_iter().map(Into::into));
+        self
+    }
+
+    /// Redact sensitive values, like private keys and signatures, from the stdout and file output.
+    ///
+    /// See [`redact`](crate::redact) for what gets redacted. Spans exported over OTLP are not
+    /// redacted.
+    #[must_use]
+    pub fn with_redaction(mut self) -> Self {
+        self.redact = true;
+        self
+    }
+
+    /// Also export spans to an OpenTelemetry collector over OTLP.
+    ///
+    /// Spans are exported in batches on a background task, so [`LoggerBuilder::init`] must be
+    /// called from within a Tokio runtime.
+    #[cfg(feature = "otlp")]
+    #[must_use]
+    pub fn with_otlp(mut self, otlp: OtlpConfig) -> Self {
+        self.otlp = Some(otlp);
+        self
+    }
+
+    /// Initializes the logging system.
+    ///
+    /// Filters out crate dependencies to reduce noise. The returned [`LoggerHandle`] must be held
+    /// for as long as logs should be written, see its documentation for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the log filter directives fail to parse.
+    pub fn init(self) -> LoggerHandle {
+        let mut layers: Vec<BoxedLayer> = vec![if self.redact {
+            stdout_layer(self.format, RedactingMakeWriter::new(io::stdout))
+        } else {
+            stdout_layer(self.format, io::stdout)
+        }];
+
+        let guard = self.file.map(|file| {
+            let appender = rolling::RollingFileAppender::new(
+                file.rotation.into(),
+                file.directory,
+                file.file_name_prefix,
+            );
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            layers.push(if self.redact {
+                file_layer(self.format, RedactingMakeWriter::new(writer))
+            } else {
+                file_layer(self.format, writer)
+            });
+            guard
+        });
+
+        #[cfg(feature = "otlp")]
+        let (tracer_provider, otlp_error) = match self.otlp.map(otlp_layer).transpose() {
+            Ok(Some((layer, tracer_provider))) => {
+                layers.push(layer);
+                (Some(tracer_provider), None)
+            }
+            Ok(None) => (None, None),
+            Err(e) => (None, Some(e)),
+        };
+
+        let (filter, invalid_directives) = add_directives(default_filter(), &self.directives);
+        tracing_subscriber::registry().with(filter).with(layers).init();
+
+        for directive in invalid_directives {
+            tracing::warn!("ignoring invalid log directive: {directive}");
+        }
+
+        // The exporter can only fail to build before the subscriber exists, so report it now.
+        #[cfg(feature = "otlp")]
+        if let Some(e) = otlp_error {
+            tracing::warn!("failed to initialize the otlp exporter: {e}");
+        }
+
+        LoggerHandle {
+            _guard: guard,
+            #[cfg(feature = "otlp")]
+            tracer_provider,
+        }
+    }
+}
+
+/// A handle to the logging system that flushes any buffered output when dropped.
+///
+/// Logs written to a rolling file are handed off to a background thread, and spans exported over
+/// OTLP are sent in batches, so the tail of the output is lost if the process exits without
+/// flushing them. Bind the handle to a variable in `main` so it lives until shutdown.
+#[derive(Debug)]
+#[must_use = "dropping the handle stops buffered logs from being flushed"]
+pub struct LoggerHandle {
+    /// The guard of the non-blocking rolling file writer, if any.
+    _guard: Option<WorkerGuard>,
+    /// The provider exporting spans over OTLP, if any.
+    #[cfg(feature = "otlp")]
+    tracer_provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+}
+
+impl Drop for LoggerHandle {
+    fn drop(&mut self) {
+        // The global provider keeps a reference alive, so it has to be shut down explicitly. The
+        // file writer is only flushed once the fields are dropped, so the failure is still logged.
+        #[cfg(feature = "otlp")]
+        if let Some(tracer_provider) = self.tracer_provider.take() {
+            if let Err(e) = tracer_provider.shutdown() {
+                tracing::warn!("failed to shut down the otlp exporter: {e}");
+            }
+        }
+    }
+}
+
 /// Initializes the logging system.
 ///
-/// Filters out crate dependencies to reduce noise.
+/// Filters out crate dependencies to reduce noise. The returned [`LoggerHandle`] must be held for
+/// as long as logs should be written.
 ///
 /// # Panics
 ///
 /// Panics if any of the log filter directives fail to parse.
-pub fn init_logger(log_format: LogFormat) {
+pub fn init_logger(log_format: LogFormat) -> LoggerHandle {
+    LoggerBuilder::new(log_format).init()
+}
+
+/// Returns the stdout layer for the given format.
+fn stdout_layer<W>(log_format: LogFormat, writer: W) -> BoxedLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = fmt::layer().with_writer(writer);
+    match log_format {
+        LogFormat::Pretty => layer
+            .pretty()
+            .with_file(false)
+            .with_target(false)
+            .with_line_number(false)
+            .with_thread_ids(false)
+            .with_thread_names(false)
+            .boxed(),
+        LogFormat::Json => layer.json().boxed(),
+        LogFormat::Minimal => layer.with_level(true).compact().boxed(),
+    }
+}
+
+/// Returns the rolling file layer for the given format.
+fn file_layer<W>(log_format: LogFormat, writer: W) -> BoxedLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = fmt::layer().with_writer(writer).with_ansi(false);
+    match log_format {
+        LogFormat::Json => layer.json().boxed(),
+        LogFormat::Pretty | LogFormat::Minimal => layer.boxed(),
+    }
+}
+
+/// Returns a layer that exports spans to the configured OTLP collector.
+#[cfg(feature = "otlp")]
+fn otlp_layer(
+    config: OtlpConfig,
+) -> Result<(BoxedLayer, opentelemetry_sdk::trace::TracerProvider), opentelemetry::trace::TraceError>
+{
+    use opentelemetry::{trace::TracerProvider as _, KeyValue};
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{runtime, trace::TracerProvider, Resource};
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(config.endpoint)
+        .build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new([KeyValue::new("service.name", config.service_name)]))
+        .build();
+    let tracer = provider.tracer("spn");
+    opentelemetry::global::set_tracer_provider(provider.clone());
+
+    Ok((tracing_opentelemetry::layer().with_tracer(tracer).boxed(), provider))
+}
+
+/// Adds the directives to the filter, returning the ones that failed to parse.
+fn add_directives(mut filter: EnvFilter, directives: &[String]) -> (EnvFilter, Vec<String>) {
+    let mut invalid = Vec::new();
+    for directive in directives {
+        match directive.parse() {
+            Ok(parsed) => filter = filter.add_directive(parsed),
+            Err(e) => invalid.push(format!("{directive} ({e})")),
+        }
+    }
+    (filter, invalid)
+}
+
+/// Returns the default filter, which silences noisy dependencies.
+fn default_filter() -> EnvFilter {
     // Set default log level to info if RUST_LOG is not set.
     if std::env::var("RUST_LOG").is_err() {
         std::env::set_var("RUST_LOG", "info");
     }
 
-    let filter = EnvFilter::from_default_env()
+    EnvFilter::from_default_env()
         .add_directive("aws_runtime=warn".parse().unwrap())
         .add_directive("aws_sdk_s3=warn".parse().unwrap())
         .add_directive("aws_sdk_sts=warn".parse().unwrap())
@@ -60,22 +394,25 @@ pub fn init_logger(log_format: LogFormat) {
         .add_directive("p3_uni_stark=warn".parse().unwrap())
         .add_directive("p3_keccak_air=warn".parse().unwrap())
         .add_directive("spn_artifacts=warn".parse().unwrap())
-        .add_directive("sp1_circuit_compiler=warn".parse().unwrap());
-    let base = tracing_subscriber::registry().with(filter);
+        .add_directive("sp1_circuit_compiler=warn".parse().unwrap())
+}
 
-    match log_format {
-        LogFormat::Pretty => base
-            .with(
-                fmt::layer()
-                    .pretty()
-                    .with_file(false)
-                    .with_target(false)
-                    .with_line_number(false)
-                    .with_thread_ids(false)
-                    .with_thread_names(false),
-            )
-            .init(),
-        LogFormat::Json => base.with(fmt::layer().json()).init(),
-        LogFormat::Minimal => base.with(fmt::layer().with_level(true).compact()).init(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_directives() {
+        let directives = ["sqlx=debug".to_string(), "tonic=loud".to_string()];
+        let (filter, invalid) = add_directives(default_filter(), &directives);
+
+        // The caller's directive replaces the built-in one for the same target.
+        let filter = filter.to_string();
+        assert!(filter.contains("sqlx=debug"));
+        assert!(!filter.contains("sqlx=warn"));
+        assert!(filter.contains("hyper=warn"));
+
+        assert_eq!(invalid.len(), 1);
+        assert!(invalid[0].starts_with("tonic=loud"));
     }
 }
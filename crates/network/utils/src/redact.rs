@@ -0,0 +1,236 @@
+use std::io::{self, Write};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// The placeholder that redacted values are replaced with.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Field names that are always redacted. A field is considered sensitive if its lowercase name
+/// contains any of these, e.g. `signer_private_key` or `proof_signature`.
+const SENSITIVE_FIELDS: [&str; 3] = ["private_key", "privatekey", "signature"];
+
+/// The minimum number of hex characters for a standalone hex string to be redacted.
+///
+/// This matches the length of a 65-byte ECDSA signature. Shorter strings are left untouched
+/// because 32-byte keys can't be told apart from the request ids and hashes that are logged
+/// throughout the network, so those are only redacted when they appear in a sensitive field.
+const MIN_REDACTED_HEX_LEN: usize = 130;
+
+/// The escape character that starts an ANSI control sequence.
+const ESC: u8 = 0x1b;
+
+/// Redact sensitive values from a formatted log line.
+///
+/// Masks the values of fields named like `private_key` or `signature`, in any of the `key=value`,
+/// `key: value`, or `"key":"value"` forms, as well as any hex string long enough to be a
+/// signature.
+#[must_use]
+pub fn redact(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut output = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == ESC {
+            // Copy control sequences as-is so they aren't mistaken for part of a token.
+            let end = skip_ansi(bytes, i);
+            output.push_str(&input[i..end]);
+            i = end;
+        } else if is_token_byte(bytes[i]) {
+            let start = i;
+            while i < bytes.len() && is_token_byte(bytes[i]) {
+                i += 1;
+            }
+            let token = &input[start..i];
+            if is_sensitive_field(token) {
+                if let Some((value_start, value_end)) = field_value(bytes, i) {
+                    output.push_str(&input[start..value_start]);
+                    output.push_str(REDACTED);
+                    i = value_end;
+                    continue;
+                }
+            }
+            output.push_str(if is_long_hex(token) { REDACTED } else { token });
+        } else {
+            let start = i;
+            while i < bytes.len() && bytes[i] != ESC && !is_token_byte(bytes[i]) {
+                i += 1;
+            }
+            output.push_str(&input[start..i]);
+        }
+    }
+    output
+}
+
+/// A [`MakeWriter`] that redacts sensitive values from everything written through it.
+///
+/// Since it operates on the formatted output, it composes with every [`LogFormat`].
+///
+/// [`LogFormat`]: crate::LogFormat
+#[derive(Debug, Clone)]
+pub struct RedactingMakeWriter<M> {
+    /// The writer to write the redacted output to.
+    inner: M,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    /// Create a new [`RedactingMakeWriter`] that wraps the given writer.
+    #[must_use]
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter { inner: self.inner.make_writer() }
+    }
+
+    fn make_writer_for(&'a self, meta: &tracing::Metadata<'_>) -> Self::Writer {
+        RedactingWriter { inner: self.inner.make_writer_for(meta) }
+    }
+}
+
+/// A writer that redacts sensitive values before writing them to the inner writer.
+///
+/// Each call to [`Write::write`] is expected to contain whole log lines, which holds for the
+/// writers created by [`tracing_subscriber::fmt`] as they write each event at once.
+#[derive(Debug)]
+pub struct RedactingWriter<W> {
+    /// The writer to write the redacted output to.
+    inner: W,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let redacted = redact(&String::from_utf8_lossy(buf));
+        self.inner.write_all(redacted.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Whether the byte can be part of a field name or hex string.
+fn is_token_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Whether the field name is one whose value should be redacted.
+fn is_sensitive_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_FIELDS.iter().any(|field| name.contains(field))
+}
+
+/// Whether the token is a hex string long enough to be a signature.
+fn is_long_hex(token: &str) -> bool {
+    let hex = token.strip_prefix("0x").unwrap_or(token);
+    hex.len() >= MIN_REDACTED_HEX_LEN && hex.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Returns the index just past the ANSI control sequence starting at `start`.
+fn skip_ansi(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    if bytes.get(i) == Some(&b'[') {
+        i += 1;
+        while i < bytes.len() && !bytes[i].is_ascii_alphabetic() {
+            i += 1;
+        }
+        i += 1;
+    }
+    i.min(bytes.len())
+}
+
+/// Returns the index of the first byte from `i` on that isn't a space or part of an ANSI
+/// control sequence.
+fn skip_padding(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        if bytes[i] == ESC {
+            i = skip_ansi(bytes, i);
+        } else if bytes[i] == b' ' {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+/// Returns the range of the value of the field whose name ends at `i`, if it is followed by one.
+fn field_value(bytes: &[u8], mut i: usize) -> Option<(usize, usize)> {
+    // Skip the closing quote of a JSON key.
+    if bytes.get(i) == Some(&b'"') {
+        i += 1;
+    }
+    i = skip_padding(bytes, i);
+    if !matches!(bytes.get(i), Some(b'=' | b':')) {
+        return None;
+    }
+    i = skip_padding(bytes, i + 1);
+
+    let quoted = bytes.get(i) == Some(&b'"');
+    if quoted {
+        i += 1;
+    }
+    let start = i;
+    let mut depth = 0usize;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if quoted {
+            match byte {
+                b'\\' => i += 1,
+                b'"' => break,
+                _ => {}
+            }
+        } else {
+            // Debug output of collections contains separators, so only stop at the top level.
+            match byte {
+                b'[' | b'(' | b'{' => depth += 1,
+                b']' | b')' | b'}' if depth > 0 => depth -= 1,
+                b',' | b']' | b')' | b'}' | ESC if depth == 0 => break,
+                _ if byte.is_ascii_whitespace() && depth == 0 => break,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    let end = i.min(bytes.len());
+    (end > start).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        // Sensitive fields are redacted in every format.
+        assert_eq!(redact("private_key=0xabc signer=0x123"), "private_key=[REDACTED] signer=0x123");
+        assert_eq!(redact("    signature: 0xabc\n"), "    signature: [REDACTED]\n");
+        assert_eq!(
+            redact(r#"{"fields":{"signature":"0x\"abc","request_id":"ff"}}"#),
+            r#"{"fields":{"signature":"[REDACTED]","request_id":"ff"}}"#
+        );
+        assert_eq!(
+            redact("\x1b[3mprivate_key\x1b[0m\x1b[2m=\x1b[0m0xabc"),
+            "\x1b[3mprivate_key\x1b[0m\x1b[2m=\x1b[0m[REDACTED]"
+        );
+        assert_eq!(
+            redact("Signer { signer_private_key: [1, 2, 3], chain_id: 1 }"),
+            "Signer { signer_private_key: [REDACTED], chain_id: 1 }"
+        );
+
+        // Signature-length hex strings are redacted wherever they appear.
+        let signature = format!("0x{}", "ab".repeat(65));
+        assert_eq!(redact(&format!("proof {signature} submitted")), "proof [REDACTED] submitted");
+
+        // Request ids, hashes, and prose are left untouched.
+        let request_id = "cd".repeat(32);
+        assert_eq!(redact(&format!("request_id={request_id}")), format!("request_id={request_id}"));
+        assert_eq!(redact("verify proto signature"), "verify proto signature");
+        assert_eq!(redact("prover ✓ ready"), "prover ✓ ready");
+    }
+}
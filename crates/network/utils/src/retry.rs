@@ -0,0 +1,227 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+/// How the delays between retries are randomized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Jitter {
+    /// The delays are not randomized.
+    #[default]
+    None,
+    /// Each delay is randomized by ±50%.
+    Proportional,
+    /// Each delay is drawn uniformly from `[0, delay]`.
+    ///
+    /// This spreads out the retries of many clients that failed at the same time, e.g. when an
+    /// RPC endpoint goes down, instead of having them retry in waves.
+    Full,
+}
+
+impl Jitter {
+    /// Randomize the given delay.
+    #[must_use]
+    pub fn apply(self, delay: Duration) -> Duration {
+        match self {
+            Jitter::None => delay,
+            Jitter::Proportional => delay.mul_f64(rand::random::<f64>() + 0.5),
+            Jitter::Full => delay.mul_f64(rand::random::<f64>()),
+        }
+    }
+}
+
+/// A policy for retrying a fallible operation with exponential backoff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+    /// The maximum delay between retries.
+    pub max_delay: Duration,
+    /// The factor by which the delay grows after each retry.
+    pub multiplier: f64,
+    /// The maximum number of attempts, including the first one, or `None` for no limit.
+    pub max_attempts: Option<u32>,
+    /// The maximum time to keep retrying for, or `None` for no limit.
+    pub max_elapsed_time: Option<Duration>,
+    /// How the delays between retries are randomized.
+    pub jitter: Jitter,
+}
+
+impl RetryPolicy {
+    /// Set the delay before the first retry.
+    #[must_use]
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// Set the maximum delay between retries.
+    #[must_use]
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Set the factor by which the delay grows after each retry.
+    #[must_use]
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set the maximum number of attempts, including the first one.
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Set the maximum time to keep retrying for.
+    #[must_use]
+    pub fn with_max_elapsed_time(mut self, max_elapsed_time: Option<Duration>) -> Self {
+        self.max_elapsed_time = max_elapsed_time;
+        self
+    }
+
+    /// Set how the delays between retries are randomized.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The delay before the retry that follows the given delay.
+    fn next_delay(&self, delay: Duration) -> Duration {
+        delay.mul_f64(self.multiplier).min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Doubles the delay from one second up to two minutes, retrying forever without jitter.
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(120),
+            multiplier: 2.0,
+            max_attempts: None,
+            max_elapsed_time: None,
+            jitter: Jitter::None,
+        }
+    }
+}
+
+/// Run an async operation, retrying it with exponential backoff according to the policy.
+///
+/// After each failure that leaves attempts to spare, `is_retryable` decides whether the error is
+/// transient. The first error that isn't, or the last error once the policy's attempts or time run
+/// out, is returned.
+pub async fn retry_with_policy<T, E, F, Fut, C>(
+    policy: &RetryPolicy,
+    mut operation: F,
+    is_retryable: C,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    C: Fn(&E) -> bool,
+{
+    let start = Instant::now();
+    let mut delay = policy.initial_delay;
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let error = match operation().await {
+            Ok(result) => return Ok(result),
+            Err(error) => error,
+        };
+
+        if policy.max_attempts.is_some_and(|max| attempts >= max) || !is_retryable(&error) {
+            return Err(error);
+        }
+        let wait = policy.jitter.apply(delay);
+        if policy.max_elapsed_time.is_some_and(|max| start.elapsed() + wait > max) {
+            return Err(error);
+        }
+
+        tokio::time::sleep(wait).await;
+        delay = policy.next_delay(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy::default()
+            .with_initial_delay(Duration::from_millis(1))
+            .with_max_delay(Duration::from_millis(4))
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_policy() {
+        // Transient errors are retried until the operation succeeds.
+        let mut calls = 0;
+        let result: Result<u32, &str> = retry_with_policy(
+            &fast_policy(),
+            || {
+                calls += 1;
+                let result = if calls < 3 { Err("transient") } else { Ok(calls) };
+                async move { result }
+            },
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Ok(3));
+
+        // Fatal errors are returned immediately.
+        let mut calls = 0;
+        let result: Result<(), &str> = retry_with_policy(
+            &fast_policy(),
+            || {
+                calls += 1;
+                async { Err("fatal") }
+            },
+            |e| *e != "fatal",
+        )
+        .await;
+        assert_eq!(result, Err("fatal"));
+        assert_eq!(calls, 1);
+
+        // The last error is returned once the attempts run out.
+        let mut calls = 0;
+        let result: Result<(), u32> = retry_with_policy(
+            &fast_policy().with_max_attempts(4),
+            || {
+                calls += 1;
+                let attempt = calls;
+                async move { Err(attempt) }
+            },
+            |_| true,
+        )
+        .await;
+        assert_eq!(result, Err(4));
+    }
+
+    #[test]
+    fn test_backoff_delays() {
+        // The delay grows by the multiplier up to the maximum.
+        let policy = RetryPolicy::default().with_max_delay(Duration::from_secs(5));
+        let mut delay = policy.initial_delay;
+        let mut delays = Vec::new();
+        for _ in 0..5 {
+            delays.push(delay.as_secs());
+            delay = policy.next_delay(delay);
+        }
+        assert_eq!(delays, [1, 2, 4, 5, 5]);
+
+        // Jitter keeps the delay within its bounds.
+        let delay = Duration::from_secs(10);
+        assert_eq!(Jitter::None.apply(delay), delay);
+        for _ in 0..100 {
+            assert!(Jitter::Full.apply(delay) <= delay);
+            let proportional = Jitter::Proportional.apply(delay);
+            assert!(proportional >= delay / 2 && proportional <= delay * 3 / 2);
+        }
+    }
+}
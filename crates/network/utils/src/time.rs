@@ -1,4 +1,8 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 /// Returns the current Unix timestamp.
 ///
@@ -7,5 +11,135 @@ use std::time::{SystemTime, UNIX_EPOCH};
 /// Panics if the system time is before the Unix epoch (January 1, 1970).
 #[must_use]
 pub fn time_now() -> u64 {
-    SystemTime::now().duration_since(UNIX_EPOCH).expect("time went backwards").as_secs()
+    SystemClock.unix_timestamp()
+}
+
+/// Formats a duration for humans, e.g. `1h 23m 4s` or `450ms`.
+///
+/// Durations under a second are shown in whole milliseconds. Longer durations are shown in whole
+/// seconds, with every unit from the largest non-zero one down to seconds, so that values of the
+/// same magnitude line up in logs and tables.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        return format!("{}ms", duration.as_millis());
+    }
+
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m {seconds}s")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m {seconds}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// A source of the current time.
+///
+/// Code that produces timestamps takes a [`Clock`] instead of reading the system time directly, so
+/// that tests can control the time with a [`MockClock`] instead of sleeping.
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+
+    /// Returns the current Unix timestamp.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time is before the Unix epoch (January 1, 1970).
+    fn unix_timestamp(&self) -> u64 {
+        self.now().duration_since(UNIX_EPOCH).expect("time went backwards").as_secs()
+    }
+}
+
+/// A [`Clock`] that reads the system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A [`Clock`] that only moves when it is told to.
+///
+/// Clones share the same time, so a test can keep a clone to advance the clock that it passed to
+/// the code under test.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<SystemTime>>,
+}
+
+impl MockClock {
+    /// Create a new [`MockClock`] stopped at the given Unix timestamp.
+    #[must_use]
+    pub fn new(unix_timestamp: u64) -> Self {
+        Self { now: Arc::new(Mutex::new(UNIX_EPOCH + Duration::from_secs(unix_timestamp))) }
+    }
+
+    /// Move the clock forward by the given duration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another thread panicked while holding the clock.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+
+    /// Set the clock to the given time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another thread panicked while holding the clock.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap() = now;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock() {
+        let clock = MockClock::new(1_700_000_000);
+        assert_eq!(clock.unix_timestamp(), 1_700_000_000);
+
+        // Clones share the same time.
+        let handle = clock.clone();
+        handle.advance(Duration::from_secs(90));
+        assert_eq!(clock.unix_timestamp(), 1_700_000_090);
+
+        handle.set(UNIX_EPOCH);
+        assert_eq!(clock.now(), UNIX_EPOCH);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::ZERO), "0ms");
+        assert_eq!(format_duration(Duration::from_micros(450_900)), "450ms");
+        assert_eq!(format_duration(Duration::from_millis(4_999)), "4s");
+        assert_eq!(format_duration(Duration::from_secs(60)), "1m 0s");
+        assert_eq!(format_duration(Duration::from_secs(3_600 + 23 * 60 + 4)), "1h 23m 4s");
+        assert_eq!(format_duration(Duration::from_secs(2 * 86_400 + 5)), "2d 0h 0m 5s");
+    }
+
+    #[test]
+    fn test_system_clock() {
+        let before = time_now();
+        let now = SystemClock.unix_timestamp();
+        assert!(now >= before && now <= time_now());
+    }
 }
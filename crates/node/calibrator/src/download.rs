@@ -0,0 +1,133 @@
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use spn_artifact_types::ArtifactType;
+use spn_artifacts::{extract_artifact_name, Artifact};
+use spn_utils::format_duration;
+use tracing::info;
+
+use crate::CalibratorMetrics;
+
+/// Metrics for the calibration of the artifact downloads of the prover.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DownloadMetrics {
+    /// The size of the downloaded artifact in bytes.
+    pub bytes: u64,
+    /// The throughput at which the artifact was downloaded, in bytes per second.
+    pub bytes_per_second: f64,
+}
+
+/// A calibrator that measures how fast the prover downloads the artifacts of requests.
+///
+/// Before proving a request, the prover downloads its program and stdin, which can be the
+/// bottleneck on a thin uplink. This times the download of a representative artifact the same way
+/// the prover downloads them, averaging over several passes.
+#[derive(Debug, Clone)]
+pub struct DownloadCalibrator {
+    /// The URI of the artifact to download, e.g. `s3://bucket/stdins/artifact_01...`.
+    pub uri: String,
+    /// The type of the artifact to download.
+    pub artifact_type: ArtifactType,
+    /// The number of downloads to perform.
+    pub passes: usize,
+}
+
+impl DownloadCalibrator {
+    /// Create a new [`DownloadCalibrator`] that downloads the stdin artifact at the given URI.
+    #[must_use]
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self { uri: uri.into(), artifact_type: ArtifactType::Stdin, passes: 1 }
+    }
+
+    /// Set the type of the artifact to download.
+    ///
+    /// Defaults to [`ArtifactType::Stdin`], as stdins are usually the largest artifacts of a
+    /// request.
+    #[must_use]
+    pub fn with_artifact_type(mut self, artifact_type: ArtifactType) -> Self {
+        self.artifact_type = artifact_type;
+        self
+    }
+
+    /// Set the number of downloads to average over.
+    ///
+    /// Defaults to a single download.
+    #[must_use]
+    pub fn with_passes(mut self, passes: usize) -> Self {
+        self.passes = passes.max(1);
+        self
+    }
+
+    /// Calibrate the download throughput of the prover.
+    pub async fn calibrate(&self) -> Result<DownloadMetrics> {
+        let artifact = Artifact {
+            id: extract_artifact_name(&self.uri)?,
+            label: "calibration".to_string(),
+            expiry: None,
+        };
+
+        let mut bytes = 0;
+        let mut total_bytes = 0;
+        let start = Instant::now();
+        for pass in 1..=self.passes {
+            let data = artifact
+                .download_raw_from_uri(&self.uri, "", self.artifact_type)
+                .await
+                .with_context(|| format!("failed to download the artifact for pass {pass}"))?;
+            bytes = data.len() as u64;
+            total_bytes += bytes;
+        }
+        let duration = start.elapsed();
+        let bytes_per_second = total_bytes as f64 / duration.as_secs_f64();
+        info!(
+            duration = %format_duration(duration),
+            bytes,
+            bytes_per_second,
+            "Completed download calibration."
+        );
+
+        Ok(DownloadMetrics { bytes, bytes_per_second })
+    }
+}
+
+impl CalibratorMetrics {
+    /// Account for the time spent downloading artifacts in the recommended bid.
+    ///
+    /// The downloaded artifact stands for the artifacts of a request using the given prover gas.
+    /// The prover downloads the artifacts of a request before proving it, so each PGU costs the
+    /// time to prove it plus the time to download its share of the artifacts. The bid is raised
+    /// by the factor by which this end-to-end throughput falls short of the proving throughput.
+    /// [`CalibratorMetrics::pgus_per_second`] is left as is, since the node uses it to estimate
+    /// how long proving takes.
+    #[must_use]
+    pub fn with_download(mut self, download: DownloadMetrics, request_pgus: u64) -> Self {
+        let artifact_bytes_per_pgu = download.bytes as f64 / request_pgus.max(1) as f64;
+        let seconds_per_pgu =
+            1.0 / self.pgus_per_second + artifact_bytes_per_pgu / download.bytes_per_second;
+        let end_to_end_pgus_per_second = 1.0 / seconds_per_pgu;
+        self.pgu_price *= self.pgus_per_second / end_to_end_pgus_per_second;
+        self.download_bytes_per_second = Some(download.bytes_per_second);
+        self.end_to_end_pgus_per_second = Some(end_to_end_pgus_per_second);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_download() {
+        let metrics =
+            CalibratorMetrics { pgus_per_second: 100.0, pgu_price: 1.0, ..Default::default() };
+        let download = DownloadMetrics { bytes: 1000, bytes_per_second: 1000.0 };
+
+        // Downloading 10 bytes per PGU at 1000 bytes per second halves the throughput.
+        let metrics = metrics.with_download(download, 100);
+        assert!((metrics.pgus_per_second - 100.0).abs() < f64::EPSILON);
+        assert!((metrics.download_bytes_per_second.unwrap() - 1000.0).abs() < f64::EPSILON);
+        assert!((metrics.end_to_end_pgus_per_second.unwrap() - 50.0).abs() < 1e-9);
+        assert!((metrics.pgu_price - 2.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,193 @@
+use std::{ffi::OsString, path::PathBuf, process::Stdio};
+
+use anyhow::{bail, Context, Result};
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+use spn_utils::time_now;
+use tokio::process::Command;
+use tracing::info;
+
+use crate::{hardware_fingerprint, pgu_price, Calibrator, CalibratorMetrics};
+
+/// The calibration of a single GPU by a [`MultiGpuCalibrator`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceCalibration {
+    /// The CUDA index of the GPU.
+    pub device: usize,
+    /// The metrics measured on the GPU alone.
+    pub metrics: CalibratorMetrics,
+}
+
+/// A calibrator that measures the aggregate throughput of a host with several GPUs.
+///
+/// A [`crate::SinglePassCalibrator`] proves on a single device, which underestimates the capacity
+/// of a multi-GPU prover. CUDA picks the devices of a process once, so this calibrator instead
+/// runs a calibration command per GPU, pinned to it with `CUDA_VISIBLE_DEVICES`, concurrently on
+/// all GPUs. The command must print the [`CalibratorMetrics`] of its device as JSON to stdout, like
+/// `spn-node calibrate --json` does.
+///
+/// The aggregate throughput is the sum of the throughputs of the devices, and the bid is priced
+/// for the cost of the whole host.
+#[derive(Debug, Clone)]
+pub struct MultiGpuCalibrator {
+    /// The program that calibrates a single device.
+    pub program: PathBuf,
+    /// The arguments of the program.
+    pub args: Vec<OsString>,
+    /// The CUDA indices of the GPUs to calibrate.
+    pub devices: Vec<usize>,
+    /// The cost per hour of the instance (USD).
+    pub cost_per_hour: f64,
+    /// The expected average utilization rate of the instance.
+    pub utilization_rate: f64,
+    /// The target profit margin for the prover.
+    pub profit_margin: f64,
+}
+
+impl MultiGpuCalibrator {
+    /// Create a new [`MultiGpuCalibrator`] that calibrates the given devices with the given
+    /// command.
+    #[must_use]
+    pub fn new(
+        program: impl Into<PathBuf>,
+        args: impl IntoIterator<Item = impl Into<OsString>>,
+        devices: Vec<usize>,
+        cost_per_hour: f64,
+        utilization_rate: f64,
+        profit_margin: f64,
+    ) -> Self {
+        Self {
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+            devices,
+            cost_per_hour,
+            utilization_rate,
+            profit_margin,
+        }
+    }
+
+    /// Calibrate each device concurrently, returning the metrics of each.
+    pub async fn calibrate_devices(&self) -> Result<Vec<DeviceCalibration>> {
+        if self.devices.is_empty() {
+            bail!("no GPUs to calibrate");
+        }
+        info!(devices = ?self.devices, "Calibrating GPUs concurrently.");
+        try_join_all(self.devices.iter().map(|&device| self.calibrate_device(device))).await
+    }
+
+    /// Combine the metrics of the devices into the metrics of the whole host.
+    ///
+    /// The throughputs and their confidence intervals are summed, and the deviations are combined
+    /// assuming the devices are independent. The deviation and confidence interval are `None` if
+    /// any device was calibrated with a single pass.
+    #[must_use]
+    pub fn aggregate(&self, devices: &[DeviceCalibration]) -> CalibratorMetrics {
+        let pgus_per_second = devices.iter().map(|device| device.metrics.pgus_per_second).sum();
+        let variance = devices
+            .iter()
+            .map(|device| device.metrics.std_dev.map(|std_dev| std_dev.powi(2)))
+            .sum::<Option<f64>>();
+        let confidence_interval = devices.iter().try_fold((0.0, 0.0), |(low, high), device| {
+            let (device_low, device_high) = device.metrics.confidence_interval?;
+            Some((low + device_low, high + device_high))
+        });
+        CalibratorMetrics {
+            pgus_per_second,
+            pgu_price: pgu_price(
+                pgus_per_second,
+                self.cost_per_hour,
+                self.utilization_rate,
+                self.profit_margin,
+            ),
+            std_dev: variance.map(f64::sqrt),
+            confidence_interval,
+            calibrated_at: time_now(),
+            hardware: hardware_fingerprint(),
+            download_bytes_per_second: None,
+            end_to_end_pgus_per_second: None,
+        }
+    }
+
+    /// Run the calibration command pinned to a single device.
+    async fn calibrate_device(&self, device: usize) -> Result<DeviceCalibration> {
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .env("CUDA_VISIBLE_DEVICES", device.to_string())
+            .stdin(Stdio::null())
+            .output()
+            .await
+            .with_context(|| format!("failed to run the calibration of GPU {device}"))?;
+        if !output.status.success() {
+            bail!(
+                "the calibration of GPU {device} failed with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let metrics = parse_metrics(&String::from_utf8_lossy(&output.stdout))
+            .with_context(|| format!("failed to parse the calibration of GPU {device}"))?;
+        info!(device, pgus_per_second = metrics.pgus_per_second, "Calibrated GPU.");
+        Ok(DeviceCalibration { device, metrics })
+    }
+}
+
+#[async_trait::async_trait]
+impl Calibrator for MultiGpuCalibrator {
+    async fn calibrate(&self) -> Result<CalibratorMetrics> {
+        let devices = self.calibrate_devices().await?;
+        Ok(self.aggregate(&devices))
+    }
+}
+
+/// Parse the metrics printed by a calibration command, skipping anything it printed before them.
+fn parse_metrics(stdout: &str) -> Result<CalibratorMetrics> {
+    let start = stdout.find('{').context("the calibration printed no metrics")?;
+    Ok(serde_json::from_str(&stdout[start..])?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(device: usize, pgus_per_second: f64, std_dev: Option<f64>) -> DeviceCalibration {
+        DeviceCalibration {
+            device,
+            metrics: CalibratorMetrics {
+                pgus_per_second,
+                std_dev,
+                confidence_interval: std_dev
+                    .map(|_| (pgus_per_second - 1.0, pgus_per_second + 1.0)),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let calibrator =
+            MultiGpuCalibrator::new("spn-node", ["calibrate"], vec![0, 1], 1.0, 0.5, 0.1);
+        let metrics =
+            calibrator.aggregate(&[device(0, 100.0, Some(3.0)), device(1, 200.0, Some(4.0))]);
+        assert!((metrics.pgus_per_second - 300.0).abs() < f64::EPSILON);
+        assert!((metrics.std_dev.unwrap() - 5.0).abs() < f64::EPSILON);
+        assert_eq!(metrics.confidence_interval, Some((298.0, 302.0)));
+
+        // The bid covers the cost of the host with the throughput of all of its GPUs.
+        assert!((metrics.pgu_price - pgu_price(300.0, 1.0, 0.5, 0.1)).abs() < f64::EPSILON);
+
+        // The deviation is unknown if any GPU was calibrated with a single pass.
+        let metrics = calibrator.aggregate(&[device(0, 100.0, Some(3.0)), device(1, 200.0, None)]);
+        assert_eq!(metrics.std_dev, None);
+        assert_eq!(metrics.confidence_interval, None);
+    }
+
+    #[test]
+    fn test_parse_metrics() {
+        let stdout = "warming up\n{\"pgus_per_second\":10.0,\"pgu_price\":0.1,\"std_dev\":0.0,\
+                      \"calibrated_at\":1,\"hardware\":\"cpu\",\"throughput\":10.0,\"bid\":1.0}";
+        let metrics = parse_metrics(stdout).unwrap();
+        assert!((metrics.pgus_per_second - 10.0).abs() < f64::EPSILON);
+        assert!(parse_metrics("no metrics").is_err());
+    }
+}
@@ -5,9 +5,19 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::cast_precision_loss)]
 
+mod download;
+mod gpu;
+mod multi;
+mod persist;
+
+pub use download::*;
+pub use gpu::*;
+pub use multi::*;
+pub use persist::*;
+
 use anyhow::Result;
-use sp1_sdk::{ProveRequest, Prover, ProverClient, ProvingKey, SP1Stdin};
-use tracing::error;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{SP1ProofMode, SP1Stdin};
 
 /// Trait for calibrating the prover.
 #[async_trait::async_trait]
@@ -17,12 +27,30 @@ pub trait Calibrator {
 }
 
 /// Metrics for the calibration of the prover.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CalibratorMetrics {
     /// The prover gas per second that the prover can process.
     pub pgus_per_second: f64,
     /// The recommended bid amount for the prover.
     pub pgu_price: f64,
+    /// The standard deviation of the measured prover gas per second, or `None` if it was
+    /// measured over a single pass.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub std_dev: Option<f64>,
+    /// The 95% confidence interval of the measured prover gas per second, or `None` if it was
+    /// measured over a single pass.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence_interval: Option<(f64, f64)>,
+    /// The Unix timestamp of when the calibration was run.
+    pub calibrated_at: u64,
+    /// A fingerprint of the hardware the calibration was run on.
+    pub hardware: String,
+    /// The throughput at which the prover downloads artifacts in bytes per second, if measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_bytes_per_second: Option<f64>,
+    /// The prover gas per second including the time to download the artifacts, if measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_to_end_pgus_per_second: Option<f64>,
 }
 
 /// The default implementation of a calibrator.
@@ -38,6 +66,10 @@ pub struct SinglePassCalibrator {
     pub utilization_rate: f64,
     /// The target profit margin for the prover.
     pub profit_margin: f64,
+    /// The proof mode to calibrate for.
+    pub proof_mode: SP1ProofMode,
+    /// The number of untimed proving runs to perform before timing begins.
+    pub warmup: usize,
 }
 
 impl SinglePassCalibrator {
@@ -50,61 +82,79 @@ impl SinglePassCalibrator {
         utilization_rate: f64,
         profit_margin: f64,
     ) -> Self {
-        Self { elf, stdin, cost_per_hour, utilization_rate, profit_margin }
+        Self {
+            elf,
+            stdin,
+            cost_per_hour,
+            utilization_rate,
+            profit_margin,
+            proof_mode: SP1ProofMode::Compressed,
+            warmup: 1,
+        }
+    }
+
+    /// Set the proof mode to calibrate for.
+    ///
+    /// Defaults to [`SP1ProofMode::Compressed`].
+    #[must_use]
+    pub fn with_proof_mode(mut self, proof_mode: SP1ProofMode) -> Self {
+        self.proof_mode = proof_mode;
+        self
+    }
+
+    /// Set the number of untimed proving runs to perform before timing begins.
+    ///
+    /// The first proof in a process is cold: it pays for lazy initialization such as setting up
+    /// the GPU context, which would understate the steady-state throughput. Defaults to one
+    /// warmup run, so the cold run is excluded from the measurement.
+    #[must_use]
+    pub fn with_warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
     }
 }
 
 #[async_trait::async_trait]
 impl Calibrator for SinglePassCalibrator {
     async fn calibrate(&self) -> Result<CalibratorMetrics> {
-        // Initialize the prover client from environment.
-        let client = ProverClient::from_env().await;
-
-        // Setup the proving key.
-        let pk = client.setup(self.elf.clone().into()).await.inspect_err(|e| {
-            error!("Failed to setup the prover: {e}");
-        })?;
-
-        // Execute to get the prover gas.
-        let (_, report) =
-            client.execute(pk.elf().clone(), self.stdin.clone()).await.inspect_err(|e| {
-                error!("Failed to execute the prover: {e}");
-            })?;
-        let prover_gas = report.gas().unwrap_or(0);
-
-        // Start timing.
-        let start = std::time::Instant::now();
-
-        // Generate the proof.
-        let _ = client.prove(&pk, self.stdin.clone()).compressed().await.inspect_err(|e| {
-            error!("Failed to generate the proof: {e}");
-        })?;
-
-        // Calculate duration and throughput.
-        let duration = start.elapsed();
-        let pgus_per_second = prover_gas as f64 / duration.as_secs_f64();
-
-        // Calculate the price per pgu using a simple economic model..
-        //
-        // The economic model is based on the following assumptions:
-        // - The prover has a consistent cost per hour.
-        // - The prover has a consistent utilization rate.
-        // - The prover wants to maximize its profit.
-        //
-        // The model is based on the following formula:
-        //
-        // bidPricePerPGU = (costPerHour / averageUtilizationRate) * (1 + profitMargin) /
-        // maxThroughputPerHour
-        let pgus_per_hour = pgus_per_second * 3600.0;
-        let utilized_pgus_per_hour = pgus_per_hour * self.utilization_rate;
-        let optimal_pgu_price = self.cost_per_hour / utilized_pgus_per_hour;
-        let pgu_price = optimal_pgu_price * (1.0 + self.profit_margin);
-
-        // Return the metrics.
-        Ok(CalibratorMetrics { pgus_per_second, pgu_price })
+        // A single pass is a multi-pass calibration with one pass.
+        let calibrator = MultiPassCalibrator {
+            elf: self.elf.clone(),
+            stdin: self.stdin.clone(),
+            cost_per_hour: self.cost_per_hour,
+            utilization_rate: self.utilization_rate,
+            profit_margin: self.profit_margin,
+            proof_mode: self.proof_mode,
+            warmup: self.warmup,
+            passes: 1,
+        };
+        calibrator.calibrate().await
     }
 }
 
+/// Calculate the price per pgu using a simple economic model.
+///
+/// The economic model is based on the following assumptions:
+/// - The prover has a consistent cost per hour.
+/// - The prover has a consistent utilization rate.
+/// - The prover wants to maximize its profit.
+///
+/// The model is based on the following formula:
+///
+/// `bidPricePerPGU = (costPerHour / averageUtilizationRate) * (1 + profitMargin) /
+/// maxThroughputPerHour`
+pub(crate) fn pgu_price(
+    pgus_per_second: f64,
+    cost_per_hour: f64,
+    utilization_rate: f64,
+    profit_margin: f64,
+) -> f64 {
+    let pgus_per_hour = pgus_per_second * 3600.0;
+    let utilized_pgus_per_hour = pgus_per_hour * utilization_rate;
+    let optimal_pgu_price = cost_per_hour / utilized_pgus_per_hour;
+    optimal_pgu_price * (1.0 + profit_margin)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,4 +183,29 @@ mod tests {
         let metrics = calibrator.calibrate().await.unwrap();
         println!("metrics: {metrics:?}");
     }
+
+    #[test]
+    fn test_save_and_load() {
+        let metrics = CalibratorMetrics {
+            pgus_per_second: 1_000_000.0,
+            pgu_price: 0.5,
+            std_dev: Some(10.0),
+            confidence_interval: Some((990_000.0, 1_010_000.0)),
+            calibrated_at: 1_700_000_000,
+            hardware: hardware_fingerprint(),
+            download_bytes_per_second: None,
+            end_to_end_pgus_per_second: None,
+        };
+
+        // Save and reload the metrics.
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("calibration.json");
+        metrics.save(&path).unwrap();
+        let loaded = CalibratorMetrics::load(&path).unwrap();
+
+        assert_eq!(loaded.calibrated_at, metrics.calibrated_at);
+        assert_eq!(loaded.hardware, metrics.hardware);
+        assert!((loaded.pgus_per_second - metrics.pgus_per_second).abs() < f64::EPSILON);
+        assert!(!loaded.is_stale());
+    }
 }
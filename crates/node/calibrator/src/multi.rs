@@ -0,0 +1,242 @@
+use anyhow::{Context, Result};
+use sp1_sdk::{ProveRequest, Prover, ProverClient, ProvingKey, SP1ProofMode, SP1Stdin};
+use spn_utils::{format_duration, time_now};
+use tracing::{error, info};
+
+use crate::{hardware_fingerprint, pgu_price, Calibrator, CalibratorMetrics};
+
+/// A calibrator that averages the throughput over several proving runs.
+///
+/// When at least three passes are run, the fastest and slowest passes are discarded before
+/// averaging, so that a single outlier (e.g. a thermal throttle) does not skew the result.
+#[derive(Debug, Clone)]
+pub struct MultiPassCalibrator {
+    /// The ELF to use for the calibration.
+    pub elf: Vec<u8>,
+    /// The input stream to use for the calibration.
+    pub stdin: SP1Stdin,
+    /// The cost per hour of the instance (USD).
+    pub cost_per_hour: f64,
+    /// The expected average utilization rate of the instance.
+    pub utilization_rate: f64,
+    /// The target profit margin for the prover.
+    pub profit_margin: f64,
+    /// The proof mode to calibrate for.
+    pub proof_mode: SP1ProofMode,
+    /// The number of untimed proving runs to perform before timing begins.
+    pub warmup: usize,
+    /// The number of proving runs to perform.
+    pub passes: usize,
+}
+
+impl MultiPassCalibrator {
+    /// Create a new [`MultiPassCalibrator`].
+    #[must_use]
+    pub fn new(
+        elf: Vec<u8>,
+        stdin: SP1Stdin,
+        cost_per_hour: f64,
+        utilization_rate: f64,
+        profit_margin: f64,
+        passes: usize,
+    ) -> Self {
+        Self {
+            elf,
+            stdin,
+            cost_per_hour,
+            utilization_rate,
+            profit_margin,
+            proof_mode: SP1ProofMode::Compressed,
+            warmup: 1,
+            passes: passes.max(1),
+        }
+    }
+
+    /// Set the proof mode to calibrate for.
+    ///
+    /// Defaults to [`SP1ProofMode::Compressed`].
+    #[must_use]
+    pub fn with_proof_mode(mut self, proof_mode: SP1ProofMode) -> Self {
+        self.proof_mode = proof_mode;
+        self
+    }
+
+    /// Set the number of untimed proving runs to perform before the timed passes.
+    ///
+    /// Defaults to one, which keeps the cold first proof out of the average. See
+    /// [`crate::SinglePassCalibrator::with_warmup`].
+    #[must_use]
+    pub fn with_warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Calibrator for MultiPassCalibrator {
+    async fn calibrate(&self) -> Result<CalibratorMetrics> {
+        // Initialize the prover client from environment.
+        let client = ProverClient::from_env().await;
+
+        // Setup the proving key.
+        let pk = client
+            .setup(self.elf.clone().into())
+            .await
+            .inspect_err(|e| {
+                error!("Failed to setup the prover: {e}");
+            })
+            .context("failed to setup the prover")?;
+
+        // Execute to get the prover gas.
+        let (_, report) = client
+            .execute(pk.elf().clone(), self.stdin.clone())
+            .await
+            .inspect_err(|e| {
+                error!("Failed to execute the prover: {e}");
+            })
+            .context("failed to execute the program")?;
+        let prover_gas = report.gas().context("execution report is missing the prover gas")?;
+
+        // Generate the warmup proofs, which are not timed.
+        for run in 1..=self.warmup {
+            info!(run, warmup = self.warmup, "Running calibration warmup.");
+            let _ = client
+                .prove(&pk, self.stdin.clone())
+                .mode(self.proof_mode)
+                .await
+                .inspect_err(|e| {
+                    error!("Failed to generate the warmup proof: {e}");
+                })
+                .with_context(|| format!("failed to generate the proof for warmup run {run}"))?;
+        }
+
+        // Generate the proof once per pass and measure the throughput of each.
+        let mut samples = Vec::with_capacity(self.passes);
+        for pass in 1..=self.passes {
+            let start = std::time::Instant::now();
+            let _ = client
+                .prove(&pk, self.stdin.clone())
+                .mode(self.proof_mode)
+                .await
+                .inspect_err(|e| {
+                    error!("Failed to generate the proof: {e}");
+                })
+                .with_context(|| format!("failed to generate the proof for pass {pass}"))?;
+            let duration = start.elapsed();
+            let pgus_per_second = prover_gas as f64 / duration.as_secs_f64();
+            info!(
+                pass,
+                passes = self.passes,
+                duration = %format_duration(duration),
+                pgus_per_second,
+                "Completed calibration pass."
+            );
+            samples.push(pgus_per_second);
+        }
+
+        // Average the throughput and calculate the price per pgu.
+        let stats = ThroughputStats::from_samples(&mut samples);
+        let pgu_price =
+            pgu_price(stats.mean, self.cost_per_hour, self.utilization_rate, self.profit_margin);
+
+        // Return the metrics.
+        Ok(CalibratorMetrics {
+            pgus_per_second: stats.mean,
+            pgu_price,
+            std_dev: stats.std_dev,
+            confidence_interval: stats.confidence_interval(),
+            calibrated_at: time_now(),
+            hardware: hardware_fingerprint(),
+            download_bytes_per_second: None,
+            end_to_end_pgus_per_second: None,
+        })
+    }
+}
+
+/// Summary statistics of the throughput measured over several passes.
+#[derive(Debug, Clone, Copy)]
+struct ThroughputStats {
+    /// The mean throughput.
+    mean: f64,
+    /// The sample standard deviation of the throughput, or `None` with fewer than two samples.
+    std_dev: Option<f64>,
+    /// The number of samples the statistics were computed over.
+    count: usize,
+}
+
+impl ThroughputStats {
+    /// Calculate the statistics of the samples.
+    ///
+    /// If there are at least three samples, the smallest and largest samples are discarded first.
+    fn from_samples(samples: &mut [f64]) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let samples = if samples.len() >= 3 { &samples[1..samples.len() - 1] } else { &*samples };
+        if samples.is_empty() {
+            return Self { mean: 0.0, std_dev: None, count: 0 };
+        }
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        if samples.len() < 2 {
+            return Self { mean, std_dev: None, count: samples.len() };
+        }
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        Self { mean, std_dev: Some(variance.sqrt()), count: samples.len() }
+    }
+
+    /// The 95% confidence interval of the mean, using the Student's t-distribution.
+    ///
+    /// Returns `None` with fewer than two samples, since the deviation is unknown.
+    fn confidence_interval(&self) -> Option<(f64, f64)> {
+        let std_dev = self.std_dev?;
+        let margin = t_critical_95(self.count - 1) * std_dev / (self.count as f64).sqrt();
+        Some((self.mean - margin, self.mean + margin))
+    }
+}
+
+/// The two-sided 95% critical value of the Student's t-distribution for the degrees of freedom.
+fn t_critical_95(degrees_of_freedom: usize) -> f64 {
+    const TABLE: [f64; 10] =
+        [12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228];
+    match degrees_of_freedom {
+        0 => f64::INFINITY,
+        1..=10 => TABLE[degrees_of_freedom - 1],
+        11..=20 => 2.086,
+        21..=30 => 2.042,
+        _ => 1.96,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throughput_stats() {
+        // The outliers are discarded before averaging.
+        let mut samples = [100.0, 10.0, 12.0, 11.0, 1.0];
+        let stats = ThroughputStats::from_samples(&mut samples);
+        assert_eq!(stats.count, 3);
+        assert!((stats.mean - 11.0).abs() < f64::EPSILON);
+        assert!((stats.std_dev.unwrap() - 1.0).abs() < f64::EPSILON);
+
+        // The confidence interval is centered on the mean.
+        let (low, high) = stats.confidence_interval().unwrap();
+        let margin = 4.303 / 3f64.sqrt();
+        assert!((low - (11.0 - margin)).abs() < 1e-9);
+        assert!((high - (11.0 + margin)).abs() < 1e-9);
+
+        // With fewer than three samples nothing is discarded.
+        let mut samples = [10.0, 20.0];
+        let stats = ThroughputStats::from_samples(&mut samples);
+        assert_eq!(stats.count, 2);
+        assert!((stats.mean - 15.0).abs() < f64::EPSILON);
+
+        // A single sample has no known deviation.
+        let mut samples = [10.0];
+        let stats = ThroughputStats::from_samples(&mut samples);
+        assert!((stats.mean - 10.0).abs() < f64::EPSILON);
+        assert_eq!(stats.std_dev, None);
+        assert_eq!(stats.confidence_interval(), None);
+    }
+}
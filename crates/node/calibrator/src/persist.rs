@@ -0,0 +1,47 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::CalibratorMetrics;
+
+impl CalibratorMetrics {
+    /// Save the metrics to a JSON file at the given path.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).context("failed to serialize calibration")?;
+        fs::write(path, json)
+            .with_context(|| format!("failed to write calibration to {}", path.display()))
+    }
+
+    /// Load previously saved metrics from a JSON file at the given path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read calibration from {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("failed to parse calibration from {}", path.display()))
+    }
+
+    /// Whether the metrics were calibrated on different hardware than the current machine.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.hardware != hardware_fingerprint()
+    }
+}
+
+/// Returns a fingerprint of the hardware of the current machine.
+///
+/// Calibrations are only valid for the hardware they were run on, so this is stored alongside the
+/// metrics to detect when a saved calibration no longer applies. It includes the model of each
+/// detected GPU, or `cpu` if there are none.
+#[must_use]
+pub fn hardware_fingerprint() -> String {
+    let gpus = spn_utils::detect_gpus();
+    let accelerator = if gpus.is_empty() {
+        "cpu".to_string()
+    } else {
+        gpus.iter().map(|gpu| gpu.name.as_str()).collect::<Vec<_>>().join("+")
+    };
+    let cpus = std::thread::available_parallelism().map(std::num::NonZeroUsize::get).unwrap_or(1);
+    format!("{accelerator}/{}/{cpus}", std::env::consts::ARCH)
+}
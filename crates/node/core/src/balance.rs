@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use alloy_primitives::U256;
+use anyhow::{bail, Context, Result};
+use backoff::{backoff::Backoff, ExponentialBackoff};
+use spn_rpc::{fetch_balance, fetch_owner};
+use tracing::{info, warn};
+
+use crate::NodeContext;
+
+/// The default interval at which the balance is re-checked while it is above the minimum.
+pub const DEFAULT_BALANCE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Pauses bidding while the balance of the prover's owner is below a minimum.
+///
+/// While the balance is too low, it is re-checked with exponential backoff, and bidding resumes
+/// as soon as the account is funded, so that the node doesn't have to be restarted.
+#[derive(Debug)]
+pub(crate) struct BalanceGate {
+    minimum: U256,
+    check_interval: Duration,
+    backoff: ExponentialBackoff,
+    next_check: Option<Instant>,
+    funded: bool,
+}
+
+impl BalanceGate {
+    /// Create a new [`BalanceGate`] for the given minimum balance, which is re-checked at the
+    /// given interval while it is met.
+    pub(crate) fn new(minimum: U256, check_interval: Duration) -> Self {
+        let backoff = ExponentialBackoff {
+            initial_interval: Duration::from_secs(5),
+            max_interval: Duration::from_secs(300),
+            max_elapsed_time: None,
+            ..Default::default()
+        };
+        Self { minimum, check_interval, backoff, next_check: None, funded: true }
+    }
+
+    /// Whether the balance is above the minimum, re-checking it on the network when it is due.
+    ///
+    /// If the balance can't be fetched, the last known state is kept.
+    pub(crate) async fn is_funded<C: NodeContext>(&mut self, ctx: &C) -> bool {
+        if self.next_check.is_some_and(|next_check| Instant::now() < next_check) {
+            return self.funded;
+        }
+
+        match fetch_owner_balance(ctx).await {
+            Ok(balance) => self.record(balance),
+            Err(e) => {
+                warn!("Failed to check the balance: {e:?}");
+                self.next_check = Some(Instant::now() + self.retry_delay());
+            }
+        }
+        self.funded
+    }
+
+    /// Record a freshly fetched balance and schedule the next check.
+    fn record(&mut self, balance: U256) {
+        if balance >= self.minimum {
+            if !self.funded {
+                info!(%balance, minimum = %self.minimum, "Balance is funded, resuming bidding.");
+            }
+            self.funded = true;
+            self.backoff.reset();
+            self.next_check = Some(Instant::now() + self.check_interval);
+        } else {
+            let delay = self.retry_delay();
+            if self.funded {
+                warn!(%balance, minimum = %self.minimum, "Balance is below the minimum, pausing bidding until it is funded.");
+            } else {
+                info!(%balance, minimum = %self.minimum, ?delay, "Waiting for the balance to be funded...");
+            }
+            self.funded = false;
+            self.next_check = Some(Instant::now() + delay);
+        }
+    }
+
+    /// The delay before the next check while the balance is too low or can't be fetched.
+    fn retry_delay(&mut self) -> Duration {
+        self.backoff.next_backoff().unwrap_or(self.backoff.max_interval)
+    }
+}
+
+/// Check that the node is ready to bid: the network is reachable and, if a minimum is given, the
+/// balance of the prover's owner is above it.
+///
+/// Fetching the balance doubles as a lightweight ping of the network, so this can back a
+/// readiness probe.
+pub async fn check_readiness<C: NodeContext>(ctx: &C, minimum_balance: Option<U256>) -> Result<()> {
+    let balance = fetch_owner_balance(ctx).await.context("failed to reach the network")?;
+    if let Some(minimum) = minimum_balance {
+        if balance < minimum {
+            bail!("the balance {balance} is below the minimum {minimum}");
+        }
+    }
+    Ok(())
+}
+
+/// Fetch the balance of the owner of the node's signer.
+async fn fetch_owner_balance<C: NodeContext>(ctx: &C) -> Result<U256> {
+    let signer = ctx.signer().address().to_vec();
+    let owner = fetch_owner(ctx.network(), &signer).await?;
+    fetch_balance(ctx.network(), &owner).await
+}
@@ -7,22 +7,41 @@
 #![allow(clippy::cast_sign_loss)]
 #![allow(clippy::cast_possible_truncation)]
 
+mod balance;
+mod restart;
 mod serial;
+mod strategy;
+mod subscription;
+mod vram;
 
+pub use balance::*;
+pub use restart::*;
 pub use serial::*;
+pub use strategy::*;
+pub use subscription::*;
+pub use vram::*;
 
 use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    panic::AssertUnwindSafe,
     sync::Arc,
     time::{Duration, SystemTime},
 };
 
+use alloy_primitives::{utils::format_units, U256};
 use alloy_signer_local::PrivateKeySigner;
 use anyhow::Result;
+use futures::FutureExt;
 use tokio::{sync::Mutex, time::sleep};
-use tonic::{async_trait, transport::Channel};
+use tokio_util::sync::CancellationToken;
+use tonic::async_trait;
+use tracing::{error, warn};
 
 use sp1_sdk::SP1_CIRCUIT_VERSION;
-use spn_network_types::prover_network_client::ProverNetworkClient;
+use spn_network_types::ProofRequest;
+use spn_rpc::NetworkClient;
+use spn_utils::{Clock, ErrorCapture, SystemClock};
 
 /// The version identifier for SP1 used on the network.
 pub const SP1_NETWORK_VERSION: &str = const_str::concat!("sp1-", SP1_CIRCUIT_VERSION);
@@ -30,6 +49,21 @@ pub const SP1_NETWORK_VERSION: &str = const_str::concat!("sp1-", SP1_CIRCUIT_VER
 /// The base URL for viewing requests on the network.
 pub const EXPLORER_REQUEST_BASE_URL: &str = "https://explorer.succinct.xyz/request";
 
+/// The number of recent proving durations kept for computing percentiles.
+pub const PROVING_DURATION_WINDOW: usize = 1024;
+
+/// The weight of the latest proof in the moving-average throughput.
+///
+/// With a weight of 0.2, a proof's influence halves about every three proofs, so the average
+/// follows a sustained change in throughput within ten or so proofs.
+pub const THROUGHPUT_SMOOTHING_FACTOR: f64 = 0.2;
+
+/// The default interval at which the node polls the network for requests to bid on and prove.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// The default interval at which the node records metrics.
+pub const DEFAULT_MONITOR_INTERVAL: Duration = Duration::from_secs(30);
+
 /// A node on the Succinct Prover Network.
 ///
 /// It consists of a context, a bidder, and a prover. It periodically bids and proves requests based
@@ -44,6 +78,25 @@ pub struct Node<C, B, P, M> {
     pub prover: Arc<P>,
     /// The monitor for the node.
     pub monitor: Arc<M>,
+    /// The token used to signal the node to shut down.
+    pub cancellation_token: CancellationToken,
+    /// The interval at which the bidder and prover poll the network.
+    pub poll_interval: Duration,
+    /// The interval at which the monitor records metrics.
+    pub monitor_interval: Duration,
+    /// The policy used to restart the bidder and prover after failures.
+    ///
+    /// If unset, failures of the bidder and prover are ignored.
+    pub restart_policy: Option<RestartPolicy>,
+    /// The minimum balance of the prover's owner required to bid on requests.
+    ///
+    /// If unset, the balance is not checked.
+    pub minimum_balance: Option<U256>,
+    /// The interval at which the balance is re-checked while it is above the minimum.
+    pub balance_check_interval: Duration,
+    /// Whether to stream new requests from the network and bid on them as they appear, instead
+    /// of polling for them.
+    pub stream_requests: bool,
 }
 
 impl<C, B, P, M> Node<C, B, P, M> {
@@ -54,8 +107,85 @@ impl<C, B, P, M> Node<C, B, P, M> {
             bidder: Arc::new(bidder),
             prover: Arc::new(prover),
             monitor: Arc::new(metrics),
+            cancellation_token: CancellationToken::new(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            monitor_interval: DEFAULT_MONITOR_INTERVAL,
+            restart_policy: None,
+            minimum_balance: None,
+            balance_check_interval: DEFAULT_BALANCE_CHECK_INTERVAL,
+            stream_requests: false,
         }
     }
+
+    /// Set the [CancellationToken] used to shut down the node.
+    ///
+    /// Once the token is cancelled, the node finishes its current iteration, waits for the
+    /// requests being proven with [NodeProver::drain], and [Node::run] returns `Ok(())`.
+    #[must_use]
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = cancellation_token;
+        self
+    }
+
+    /// Set the interval at which the bidder and prover poll the network.
+    ///
+    /// Polling more often wins more auctions at the cost of more load on the network. Defaults to
+    /// [`DEFAULT_POLL_INTERVAL`].
+    #[must_use]
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set the interval at which the monitor records metrics.
+    ///
+    /// Defaults to [`DEFAULT_MONITOR_INTERVAL`].
+    #[must_use]
+    pub fn with_monitor_interval(mut self, monitor_interval: Duration) -> Self {
+        self.monitor_interval = monitor_interval;
+        self
+    }
+
+    /// Set the [RestartPolicy] used to supervise the bidder and prover.
+    ///
+    /// Failed iterations are restarted with exponential backoff, and [Node::run] returns the error
+    /// once the policy's maximum number of consecutive failures is reached.
+    #[must_use]
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = Some(restart_policy);
+        self
+    }
+
+    /// Set the minimum balance of the prover's owner required to bid on requests.
+    ///
+    /// While the balance is below the minimum, the node stops bidding but keeps proving the
+    /// requests already assigned to it, and periodically re-checks the balance until the account
+    /// is funded again.
+    #[must_use]
+    pub fn with_minimum_balance(mut self, minimum_balance: U256) -> Self {
+        self.minimum_balance = Some(minimum_balance);
+        self
+    }
+
+    /// Set the interval at which the balance is re-checked while it is above the minimum.
+    ///
+    /// Defaults to [`DEFAULT_BALANCE_CHECK_INTERVAL`]. Has no effect without a minimum balance.
+    #[must_use]
+    pub fn with_balance_check_interval(mut self, balance_check_interval: Duration) -> Self {
+        self.balance_check_interval = balance_check_interval;
+        self
+    }
+
+    /// Set whether to stream new requests from the network and bid on them as they appear.
+    ///
+    /// This narrows the window between a request appearing and the bid landing. While the stream
+    /// is down, the node falls back to polling every poll interval and re-subscribes after
+    /// [`DEFAULT_RESUBSCRIBE_DELAY`]. Defaults to `false`.
+    #[must_use]
+    pub fn with_request_streaming(mut self, stream_requests: bool) -> Self {
+        self.stream_requests = stream_requests;
+        self
+    }
 }
 
 /// The standard context for a node.
@@ -64,7 +194,7 @@ impl<C, B, P, M> Node<C, B, P, M> {
 /// the prover.
 pub trait NodeContext: Send + Sync + 'static {
     /// The network client for the node.
-    fn network(&self) -> &ProverNetworkClient<Channel>;
+    fn network(&self) -> &NetworkClient;
     /// The signer for the node.
     fn signer(&self) -> &PrivateKeySigner;
     /// The metrics for the node.
@@ -74,11 +204,19 @@ pub trait NodeContext: Send + Sync + 'static {
 /// The bidder for a node.
 ///
 /// The bidder is responsible for bidding on requests. The bidding logic gets periodically called by
-/// the [Node].
+/// the [Node], or for each new request if the node streams requests from the network.
 #[async_trait]
 pub trait NodeBidder<C>: Send + Sync + 'static {
     /// Bid on requests.
     async fn bid(&self, ctx: &C) -> Result<()>;
+
+    /// Bid on a single request that was just streamed from the network.
+    ///
+    /// Defaults to running a full [`NodeBidder::bid`], which picks up the new request along with
+    /// any others.
+    async fn bid_on(&self, ctx: &C, _request: &ProofRequest) -> Result<()> {
+        self.bid(ctx).await
+    }
 }
 
 /// The prover for a node.
@@ -88,7 +226,18 @@ pub trait NodeBidder<C>: Send + Sync + 'static {
 #[async_trait]
 pub trait NodeProver<C>: Send + Sync + 'static {
     /// Prove requests.
-    async fn prove(&self, ctx: &C) -> Result<()>;
+    ///
+    /// The context is shared, so that the prover can keep proving requests in background tasks
+    /// after the call returns.
+    async fn prove(&self, ctx: &Arc<C>) -> Result<()>;
+
+    /// Wait for the requests still being proven in background tasks to finish.
+    ///
+    /// This is called once when the node shuts down, so that proofs in progress are submitted
+    /// before the process exits. Defaults to returning immediately.
+    async fn drain(&self, _ctx: &Arc<C>) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// The monitor for a node.
@@ -113,6 +262,301 @@ pub struct NodeMetrics {
     pub total_cycles: Mutex<u64>,
     /// The total time the node has spent proving.
     pub total_proving_time: Mutex<Duration>,
+    /// The proving durations of the most recently proven requests.
+    pub proving_durations: Mutex<VecDeque<Duration>>,
+    /// The exponentially weighted moving average of the throughput in cycles per second, or
+    /// `None` if no proofs were recorded yet.
+    pub recent_throughput: Mutex<Option<f64>>,
+    /// The number of panics caught in the node's tasks.
+    pub panics: Mutex<u64>,
+    /// The number of assigned requests whose proof failed to generate.
+    pub failed: Mutex<u64>,
+    /// The number of assigned requests whose proving panicked.
+    pub panicked: Mutex<u64>,
+    /// The number of bids placed.
+    pub bids_placed: Mutex<u64>,
+    /// The number of bids that won their auction.
+    pub bids_won: Mutex<u64>,
+    /// An upper bound on the total amount of $PROVE earned from fulfilled requests, before fees.
+    ///
+    /// The cost of each request is estimated from its gas limit, since the PGUs it used are only
+    /// known once it is fulfilled. See [`NodeMetrics::record_estimated_earnings`].
+    pub estimated_earnings: Mutex<U256>,
+    /// The deadlines of requests that were bid on but not yet seen assigned, keyed by request ID.
+    pub open_bids: Mutex<HashMap<Vec<u8>, u64>>,
+    /// The clock used to timestamp the metrics.
+    pub clock: Arc<dyn Clock>,
+}
+
+impl Default for NodeMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeMetrics {
+    /// Create a new [`NodeMetrics`] with the node coming online now.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            fulfilled: Mutex::new(0),
+            online_since: SystemClock.now(),
+            total_cycles: Mutex::new(0),
+            total_proving_time: Mutex::new(Duration::from_secs(0)),
+            proving_durations: Mutex::new(VecDeque::with_capacity(PROVING_DURATION_WINDOW)),
+            recent_throughput: Mutex::new(None),
+            panics: Mutex::new(0),
+            failed: Mutex::new(0),
+            panicked: Mutex::new(0),
+            bids_placed: Mutex::new(0),
+            bids_won: Mutex::new(0),
+            estimated_earnings: Mutex::new(U256::ZERO),
+            open_bids: Mutex::new(HashMap::new()),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replace the clock used to timestamp the metrics, e.g. with a
+    /// [`MockClock`](spn_utils::MockClock) in tests.
+    ///
+    /// The node is considered to come online at the current time of the new clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.online_since = clock.now();
+        self.clock = clock;
+        self
+    }
+
+    /// Record a fulfilled request with the given number of cycles and proving time.
+    ///
+    /// All counters are locked together so that [`NodeMetrics::snapshot`] never observes a
+    /// partially applied update. The throughput of the proof is also folded into
+    /// [`NodeMetrics::recent_throughput`].
+    pub async fn record_fulfillment(&self, cycles: u64, proving_time: Duration) {
+        let mut fulfilled = self.fulfilled.lock().await;
+        let mut total_cycles = self.total_cycles.lock().await;
+        let mut total_proving_time = self.total_proving_time.lock().await;
+        *fulfilled += 1;
+        *total_cycles += cycles;
+        *total_proving_time += proving_time;
+
+        // Proofs that took no measurable time don't have a meaningful throughput.
+        if proving_time.is_zero() {
+            return;
+        }
+        let throughput = cycles as f64 / proving_time.as_secs_f64();
+        let mut recent_throughput = self.recent_throughput.lock().await;
+        let average = recent_throughput.map_or(throughput, |average| {
+            average + THROUGHPUT_SMOOTHING_FACTOR * (throughput - average)
+        });
+        *recent_throughput = Some(average);
+        metrics::gauge!("node.recent_throughput_hz").set(average);
+    }
+
+    /// The moving-average throughput of the recent proofs in cycles per second.
+    ///
+    /// Unlike the lifetime average of [`NodeMetricsSnapshot::total_cycles`] over
+    /// [`NodeMetricsSnapshot::total_proving_time`], this weighs recent proofs the most, so it
+    /// surfaces a node slowing down, e.g. due to thermal throttling. Returns `None` if no proofs
+    /// were recorded yet.
+    pub async fn recent_throughput(&self) -> Option<f64> {
+        *self.recent_throughput.lock().await
+    }
+
+    /// Take a consistent snapshot of the metrics.
+    ///
+    /// All counters are locked at once, so the returned values reflect a single point in time.
+    pub async fn snapshot(&self) -> NodeMetricsSnapshot {
+        let fulfilled = self.fulfilled.lock().await;
+        let total_cycles = self.total_cycles.lock().await;
+        let total_proving_time = self.total_proving_time.lock().await;
+        let panics = self.panics.lock().await;
+        let failed = self.failed.lock().await;
+        let panicked = self.panicked.lock().await;
+        let bids_placed = self.bids_placed.lock().await;
+        let bids_won = self.bids_won.lock().await;
+        let estimated_earnings = self.estimated_earnings.lock().await;
+        NodeMetricsSnapshot {
+            fulfilled: *fulfilled,
+            total_cycles: *total_cycles,
+            total_proving_time: *total_proving_time,
+            panics: *panics,
+            failed: *failed,
+            panicked: *panicked,
+            bids_placed: *bids_placed,
+            bids_won: *bids_won,
+            estimated_earnings: *estimated_earnings,
+            uptime: self.clock.now().duration_since(self.online_since).unwrap_or_default(),
+        }
+    }
+
+    /// Record a bid placed on the request with the given ID and deadline.
+    ///
+    /// The bid counts as won if the request is later passed to
+    /// [`NodeMetrics::record_assignment`] before its deadline.
+    pub async fn record_bid(&self, request_id: &[u8], deadline: u64) {
+        metrics::counter!("node.bids_placed").increment(1);
+
+        // Forget the bids on requests that can no longer be assigned.
+        let mut open_bids = self.open_bids.lock().await;
+        let now = self.clock.unix_timestamp();
+        open_bids.retain(|_, bid_deadline| *bid_deadline >= now);
+        open_bids.insert(request_id.to_vec(), deadline);
+        drop(open_bids);
+
+        let mut bids_placed = self.bids_placed.lock().await;
+        let bids_won = self.bids_won.lock().await;
+        *bids_placed += 1;
+        record_win_rate(*bids_placed, *bids_won);
+    }
+
+    /// Record that the request with the given ID is assigned to the node.
+    ///
+    /// If the node bid on the request, the bid is counted as won. Requests are only counted once,
+    /// so this can be called every time an assigned request is seen.
+    pub async fn record_assignment(&self, request_id: &[u8]) {
+        if self.open_bids.lock().await.remove(request_id).is_none() {
+            return;
+        }
+        metrics::counter!("node.bids_won").increment(1);
+
+        let bids_placed = self.bids_placed.lock().await;
+        let mut bids_won = self.bids_won.lock().await;
+        *bids_won += 1;
+        record_win_rate(*bids_placed, *bids_won);
+    }
+
+    /// Record the estimated amount of $PROVE earned by fulfilling a request.
+    ///
+    /// The estimate is an upper bound: requests are charged for the PGUs they used, which is at
+    /// most their gas limit and only known to the network once the proof is fulfilled.
+    ///
+    /// The total is exported in $PROVE as the `node.estimated_earnings_prove` gauge, since
+    /// counters can't hold amounts in wei.
+    pub async fn record_estimated_earnings(&self, amount: U256) {
+        let mut estimated_earnings = self.estimated_earnings.lock().await;
+        *estimated_earnings = estimated_earnings.saturating_add(amount);
+        let prove =
+            format_units(*estimated_earnings, 18).ok().and_then(|prove| prove.parse::<f64>().ok());
+        if let Some(prove) = prove {
+            metrics::gauge!("node.estimated_earnings_prove").set(prove);
+        }
+    }
+
+    /// Record a panic caught in one of the node's tasks.
+    pub async fn record_panic(&self) {
+        metrics::counter!("node.panics").increment(1);
+        *self.panics.lock().await += 1;
+    }
+
+    /// Record an assigned request whose proof failed to generate.
+    pub async fn record_failed_proof(&self) {
+        metrics::counter!("node.proofs_failed").increment(1);
+        *self.failed.lock().await += 1;
+    }
+
+    /// Record an assigned request whose proving panicked.
+    ///
+    /// Unlike [`NodeMetrics::record_panic`], which counts panics that escape the node's tasks,
+    /// this counts panics caught while proving a single request.
+    pub async fn record_panicked_proof(&self) {
+        metrics::counter!("node.proofs_panicked").increment(1);
+        *self.panicked.lock().await += 1;
+    }
+
+    /// Record the proving duration of a single request.
+    ///
+    /// The duration is added to the `node.proving_duration_seconds` histogram and kept in a window
+    /// of the last [`PROVING_DURATION_WINDOW`] durations used by
+    /// [`NodeMetrics::proving_duration_percentiles`].
+    pub async fn record_proving_duration(&self, duration: Duration) {
+        metrics::histogram!("node.proving_duration_seconds").record(duration.as_secs_f64());
+
+        let mut durations = self.proving_durations.lock().await;
+        if durations.len() == PROVING_DURATION_WINDOW {
+            durations.pop_front();
+        }
+        durations.push_back(duration);
+    }
+
+    /// Compute percentiles over the recently recorded proving durations.
+    ///
+    /// Returns `None` if no proving durations have been recorded yet.
+    pub async fn proving_duration_percentiles(&self) -> Option<ProvingDurationPercentiles> {
+        let mut durations: Vec<Duration> =
+            self.proving_durations.lock().await.iter().copied().collect();
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_unstable();
+
+        // Use the nearest-rank method to pick each percentile.
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * durations.len() as f64).ceil() as usize;
+            durations[rank.clamp(1, durations.len()) - 1]
+        };
+        Some(ProvingDurationPercentiles {
+            p50: percentile(50.0),
+            p90: percentile(90.0),
+            p99: percentile(99.0),
+            max: durations[durations.len() - 1],
+        })
+    }
+}
+
+/// A point-in-time view of the counters in [`NodeMetrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeMetricsSnapshot {
+    /// The number of requests fulfilled.
+    pub fulfilled: u64,
+    /// The total number of cycles the node has proven.
+    pub total_cycles: u64,
+    /// The total time the node has spent proving.
+    pub total_proving_time: Duration,
+    /// The number of panics caught in the node's tasks.
+    pub panics: u64,
+    /// The number of assigned requests whose proof failed to generate.
+    pub failed: u64,
+    /// The number of assigned requests whose proving panicked.
+    pub panicked: u64,
+    /// The number of bids placed.
+    pub bids_placed: u64,
+    /// The number of bids that won their auction.
+    pub bids_won: u64,
+    /// An upper bound on the total amount of $PROVE earned from fulfilled requests, before fees.
+    pub estimated_earnings: U256,
+    /// The time elapsed since the node was started.
+    pub uptime: Duration,
+}
+
+impl NodeMetricsSnapshot {
+    /// The fraction of bids that won their auction, or `None` if no bids were placed.
+    #[must_use]
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.bids_placed > 0).then(|| self.bids_won as f64 / self.bids_placed as f64)
+    }
+
+    /// The fraction of proving attempts that failed or panicked, or `None` if nothing was proven
+    /// yet.
+    #[must_use]
+    pub fn failure_rate(&self) -> Option<f64> {
+        let failures = self.failed + self.panicked;
+        let attempts = self.fulfilled + failures;
+        (attempts > 0).then(|| failures as f64 / attempts as f64)
+    }
+}
+
+/// Percentiles of the proving durations recorded in [`NodeMetrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvingDurationPercentiles {
+    /// The median proving duration.
+    pub p50: Duration,
+    /// The 90th percentile proving duration.
+    pub p90: Duration,
+    /// The 99th percentile proving duration.
+    pub p99: Duration,
+    /// The longest proving duration.
+    pub max: Duration,
 }
 
 impl<C: NodeContext, B: NodeBidder<C>, P: NodeProver<C>, M: NodeMonitor<C>> Node<C, B, P, M> {
@@ -122,15 +566,82 @@ impl<C: NodeContext, B: NodeBidder<C>, P: NodeProver<C>, M: NodeMonitor<C>> Node
         let ctx = self.ctx.clone();
         let bidder = self.bidder.clone();
         let prover = self.prover.clone();
-        let bid_and_prove_task = tokio::spawn(async move {
+        let token = self.cancellation_token.clone();
+        let mut restart_states = self
+            .restart_policy
+            .map(|policy| (RestartState::new(policy), RestartState::new(policy)));
+        let poll_interval = self.poll_interval;
+        let balance_check_interval = self.balance_check_interval;
+        let mut balance_gate =
+            self.minimum_balance.map(|minimum| BalanceGate::new(minimum, balance_check_interval));
+        let mut subscription =
+            self.stream_requests.then(|| RequestSubscription::new(DEFAULT_RESUBSCRIBE_DELAY));
+        let mut bid_and_prove_task = tokio::spawn(async move {
             let result: Result<()> = async {
-                loop {
-                    let bid_future = bidder.bid(&ctx);
-                    let prove_future = prover.prove(&ctx);
-                    let _ = tokio::join!(bid_future, prove_future);
+                'run: while !token.is_cancelled() {
+                    // Subscribe to the request stream if enabled, and only poll while it is down.
+                    if let Some(subscription) = subscription.as_mut() {
+                        subscription.connect(ctx.as_ref()).await;
+                    }
+                    let streaming =
+                        subscription.as_ref().is_some_and(RequestSubscription::is_connected);
+
+                    // Only bid while the balance is above the minimum.
+                    let bid = async {
+                        if streaming {
+                            return Ok(());
+                        }
+                        if let Some(gate) = balance_gate.as_mut() {
+                            if !gate.is_funded(ctx.as_ref()).await {
+                                return Ok(());
+                            }
+                        }
+                        bidder.bid(&ctx).await
+                    };
+                    let bid_future = catch_panic(ctx.metrics(), "Bidder", bid);
+                    let prove_future = catch_panic(ctx.metrics(), "Prover", prover.prove(&ctx));
+                    let (bid_result, prove_result) = tokio::join!(bid_future, prove_future);
+
+                    // If supervised, back off after failures and give up after too many.
+                    let mut delay = poll_interval;
+                    if let Some((bidder_state, prover_state)) = restart_states.as_mut() {
+                        let bid_delay = bidder_state.record("Bidder", bid_result)?;
+                        let prove_delay = prover_state.record("Prover", prove_result)?;
+                        if let Some(backoff) = bid_delay.max(prove_delay) {
+                            delay = backoff;
+                        }
+                    }
 
-                    sleep(Duration::from_secs(3)).await;
+                    // Bid on the streamed requests as they appear until the next iteration.
+                    let next_iteration = sleep(delay);
+                    tokio::pin!(next_iteration);
+                    loop {
+                        let next_request = async {
+                            match subscription.as_mut() {
+                                Some(subscription) => subscription.next().await,
+                                None => std::future::pending().await,
+                            }
+                        };
+                        tokio::select! {
+                            () = token.cancelled() => break 'run,
+                            () = &mut next_iteration => break,
+                            Some(request) = next_request => {
+                                if let Some(gate) = balance_gate.as_mut() {
+                                    if !gate.is_funded(ctx.as_ref()).await {
+                                        continue;
+                                    }
+                                }
+                                let bid = bidder.bid_on(&ctx, &request);
+                                if let Err(e) = catch_panic(ctx.metrics(), "Bidder", bid).await {
+                                    warn!("Failed to bid on streamed request: {e:?}");
+                                }
+                            }
+                        }
+                    }
                 }
+
+                // Let the requests being proven finish before the node exits.
+                catch_panic(ctx.metrics(), "Prover", prover.drain(&ctx)).await
             }
             .await;
             result
@@ -139,31 +650,72 @@ impl<C: NodeContext, B: NodeBidder<C>, P: NodeProver<C>, M: NodeMonitor<C>> Node
         // Run the system monitor task.
         let ctx = self.ctx.clone();
         let monitor = self.monitor.clone();
-        let monitor_task = tokio::spawn(async move {
+        let token = self.cancellation_token.clone();
+        let monitor_interval = self.monitor_interval;
+        let mut monitor_task = tokio::spawn(async move {
             let result: Result<()> = async {
-                loop {
-                    monitor.record(&ctx).await?;
-                    sleep(Duration::from_secs(30)).await;
+                while !token.is_cancelled() {
+                    catch_panic(ctx.metrics(), "Monitor", monitor.record(&ctx)).await?;
+
+                    tokio::select! {
+                        () = token.cancelled() => break,
+                        () = sleep(monitor_interval) => {}
+                    }
                 }
+                Ok(())
             }
             .await;
             result
         });
 
-        // Wait until one of the tasks fails.
+        // Wait until one of the tasks fails or the node is shut down.
         tokio::select! {
-            result = bid_and_prove_task => {
-                if let Err(e) = result {
-                    return Err(e.into());
+            result = &mut bid_and_prove_task => {
+                result??;
+
+                // If the node is shutting down, let the other task finish its iteration.
+                if self.cancellation_token.is_cancelled() {
+                    let _ = monitor_task.await?;
                 }
             },
-            result = monitor_task => {
+            result = &mut monitor_task => {
                 if let Err(e) = result {
                     return Err(e.into());
                 }
+
+                // If the node is shutting down, let the other task finish its iteration.
+                if self.cancellation_token.is_cancelled() {
+                    let _ = bid_and_prove_task.await?;
+                }
             },
         }
 
         Ok(())
     }
 }
+
+/// Run a single iteration of a node task, catching any panic it raises.
+///
+/// A panic is logged and counted in the [`NodeMetrics`], and the iteration is treated as skipped
+/// so that the surrounding loop can continue with the next one.
+async fn catch_panic<F>(metrics: &NodeMetrics, name: &str, future: F) -> Result<()>
+where
+    F: Future<Output = Result<()>>,
+{
+    match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(result) => result,
+        Err(panic) => {
+            let message = ErrorCapture::extract_panic_message(&panic);
+            error!("{name} panicked, skipping iteration: {message}");
+            metrics.record_panic().await;
+            Ok(())
+        }
+    }
+}
+
+/// Record the fraction of bids that won their auction in the `node.bid_win_rate` gauge.
+fn record_win_rate(bids_placed: u64, bids_won: u64) {
+    if bids_placed > 0 {
+        metrics::gauge!("node.bid_win_rate").set(bids_won as f64 / bids_placed as f64);
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use backoff::{backoff::Backoff, ExponentialBackoff};
+use tracing::{error, warn};
+
+/// The policy used by the [`Node`](crate::Node) to restart its bidder and prover after failures.
+///
+/// Failed iterations are retried with exponential backoff, and the error is only propagated once
+/// [`RestartPolicy::max_consecutive_failures`] iterations have failed in a row.
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    /// The delay before the first restart.
+    pub initial_backoff: Duration,
+    /// The maximum delay between restarts.
+    pub max_backoff: Duration,
+    /// The number of consecutive failures after which the error is propagated.
+    pub max_consecutive_failures: u32,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_consecutive_failures: 10,
+        }
+    }
+}
+
+/// Tracks consecutive failures of a supervised task under a [`RestartPolicy`].
+#[derive(Debug)]
+pub(crate) struct RestartState {
+    policy: RestartPolicy,
+    backoff: ExponentialBackoff,
+    failures: u32,
+}
+
+impl RestartState {
+    /// Create a new [`RestartState`] for the given policy.
+    pub(crate) fn new(policy: RestartPolicy) -> Self {
+        // The first delay is the current interval rather than the initial one, so both are set.
+        let backoff = ExponentialBackoff {
+            current_interval: policy.initial_backoff,
+            initial_interval: policy.initial_backoff,
+            max_interval: policy.max_backoff,
+            max_elapsed_time: None,
+            ..Default::default()
+        };
+        Self { policy, backoff, failures: 0 }
+    }
+
+    /// Record the outcome of an iteration of the supervised task.
+    ///
+    /// Returns the delay to wait before restarting if the iteration failed, or `None` if it
+    /// succeeded. Returns the error once the maximum number of consecutive failures is reached.
+    pub(crate) fn record(&mut self, name: &str, result: Result<()>) -> Result<Option<Duration>> {
+        let Err(e) = result else {
+            self.failures = 0;
+            self.backoff.reset();
+            return Ok(None);
+        };
+
+        self.failures += 1;
+        if self.failures >= self.policy.max_consecutive_failures {
+            error!(failures = self.failures, "{name} failed too many times in a row: {e:?}");
+            return Err(e);
+        }
+
+        let delay = self.backoff.next_backoff().unwrap_or(self.policy.max_backoff);
+        warn!(failures = self.failures, ?delay, "{name} failed, restarting: {e:?}");
+        Ok(Some(delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    /// A policy whose delays are easy to follow: 1s, 1.5s, 2.25s, 3.375s and then 4s.
+    fn policy(max_consecutive_failures: u32) -> RestartPolicy {
+        RestartPolicy {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(4),
+            max_consecutive_failures,
+        }
+    }
+
+    #[test]
+    fn test_restart_backoff_schedule() {
+        // Without jitter, the delay grows by half after each failure up to the maximum.
+        let mut state = RestartState::new(policy(10));
+        state.backoff.randomization_factor = 0.0;
+        let delays: Vec<Duration> = (0..6)
+            .map(|_| state.record("Task", Err(anyhow!("failed"))).unwrap().unwrap())
+            .collect();
+        let expected = [1_000, 1_500, 2_250, 3_375, 4_000, 4_000].map(Duration::from_millis);
+        assert_eq!(delays, expected);
+
+        // A success resets the schedule.
+        assert_eq!(state.record("Task", Ok(())).unwrap(), None);
+        let delay = state.record("Task", Err(anyhow!("failed"))).unwrap();
+        assert_eq!(delay, Some(Duration::from_secs(1)));
+
+        // With the default jitter, each delay stays within half of the interval either way.
+        let mut state = RestartState::new(policy(10));
+        for interval in expected {
+            let delay = state.record("Task", Err(anyhow!("failed"))).unwrap().unwrap();
+            assert!(delay >= interval / 2 && delay <= interval * 3 / 2, "{delay:?}");
+        }
+    }
+
+    #[test]
+    fn test_restart_cutoff() {
+        let mut state = RestartState::new(policy(3));
+        assert!(state.record("Task", Err(anyhow!("failed"))).unwrap().is_some());
+        assert!(state.record("Task", Err(anyhow!("failed"))).unwrap().is_some());
+
+        // A success in between resets the count of consecutive failures.
+        assert_eq!(state.record("Task", Ok(())).unwrap(), None);
+        assert!(state.record("Task", Err(anyhow!("failed"))).unwrap().is_some());
+        assert!(state.record("Task", Err(anyhow!("failed"))).unwrap().is_some());
+
+        // The error is propagated once the maximum number of failures in a row is reached.
+        let err = state.record("Task", Err(anyhow!("failed again"))).unwrap_err();
+        assert_eq!(err.to_string(), "failed again");
+    }
+}
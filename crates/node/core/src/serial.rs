@@ -1,31 +1,43 @@
 use std::{
-    collections::HashSet,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     env,
+    future::Future,
     sync::{atomic, Arc},
-    time::{Duration, Instant, SystemTime},
+    time::{Duration, Instant},
 };
 
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{utils::format_units, Address, U256};
 use alloy_signer_local::PrivateKeySigner;
 use anyhow::{Context, Result};
+use bytes::Bytes;
 use chrono::{self, DateTime};
 use nvml_wrapper::Nvml;
-use sp1_sdk::{env::EnvProver, ProveRequest, Prover, ProvingKey, SP1ProofMode, SP1Stdin};
-use spn_artifacts::{extract_artifact_name, Artifact};
+use sp1_sdk::{
+    env::EnvProver, HashableKey, ProveRequest, Prover, ProvingKey, SP1ProofMode, SP1Stdin,
+};
+use spn_artifact_types::ArtifactType;
+use spn_artifacts::{extract_artifact_name, Artifact, ArtifactCache};
 use spn_network_types::{
-    prover_network_client::ProverNetworkClient, BidRequest, BidRequestBody, ExecutionStatus,
-    FailFulfillmentRequest, FailFulfillmentRequestBody, FulfillProofRequest,
-    FulfillProofRequestBody, FulfillmentStatus, GetFilteredProofRequestsRequest, GetNonceRequest,
-    GetProofRequestDetailsRequest, MessageFormat, ProofMode, Signable, TransactionVariant,
+    BidRequest, BidRequestBody, ExecutionStatus, FailFulfillmentRequest,
+    FailFulfillmentRequestBody, FulfillProofRequest, FulfillProofRequestBody, FulfillmentStatus,
+    GetFilteredProofRequestsRequest, GetNonceRequest, GetProofRequestDetailsRequest, MessageFormat,
+    ProofMode, ProofRequest, Signable, TransactionVariant,
 };
-use spn_rpc::{fetch_owner, RetryableRpc};
-use spn_utils::{time_now, SPN_MAINNET_V1_DOMAIN};
+use spn_rpc::{fetch_owner, NetworkClient, RetryableRpc};
+use spn_utils::{format_duration, time_now, ErrorCapture, SPN_MAINNET_V1_DOMAIN};
 use sysinfo::{CpuExt, System, SystemExt};
-use tokio::sync::Mutex;
-use tonic::{async_trait, transport::Channel};
+use tokio::{
+    sync::{Mutex, Semaphore},
+    task::{self, JoinError, JoinSet},
+};
+use tonic::async_trait;
 use tracing::{error, info, warn};
 
-use crate::{NodeBidder, NodeContext, NodeMetrics, NodeMonitor, NodeProver, SP1_NETWORK_VERSION};
+use crate::{
+    BiddingStrategy, FixedBidStrategy, NodeBidder, NodeContext, NodeMetrics, NodeMetricsSnapshot,
+    NodeMonitor, NodeProver, RequestContext, VramPreflight, SP1_NETWORK_VERSION,
+};
 
 /// A context that implements [`NodeContext`] for a serial node.
 ///
@@ -33,7 +45,7 @@ use crate::{NodeBidder, NodeContext, NodeMetrics, NodeMonitor, NodeProver, SP1_N
 #[derive(Debug)]
 pub struct SerialContext {
     /// The network client for the node.
-    pub network: ProverNetworkClient<Channel>,
+    pub network: NetworkClient,
     /// The signer for the node.
     pub signer: PrivateKeySigner,
     /// The metrics for the node.
@@ -42,22 +54,13 @@ pub struct SerialContext {
 
 impl SerialContext {
     /// Create a new [`SerialContext`].
-    pub fn new(network: ProverNetworkClient<Channel>, signer: PrivateKeySigner) -> Self {
-        Self {
-            network,
-            signer,
-            metrics: NodeMetrics {
-                fulfilled: Mutex::new(0),
-                online_since: SystemTime::now(),
-                total_cycles: Mutex::new(0),
-                total_proving_time: Mutex::new(Duration::from_secs(0)),
-            },
-        }
+    pub fn new(network: NetworkClient, signer: PrivateKeySigner) -> Self {
+        Self { network, signer, metrics: NodeMetrics::new() }
     }
 }
 
 impl NodeContext for SerialContext {
-    fn network(&self) -> &ProverNetworkClient<Channel> {
+    fn network(&self) -> &NetworkClient {
         &self.network
     }
 
@@ -70,32 +73,135 @@ impl NodeContext for SerialContext {
     }
 }
 
+/// The proof modes that the serial prover can produce.
+pub const SUPPORTED_PROOF_MODES: [ProofMode; 4] =
+    [ProofMode::Core, ProofMode::Compressed, ProofMode::Plonk, ProofMode::Groth16];
+
+/// The maximum number of assigned requests fetched to pick the next ones to prove from.
+pub const MAX_SCHEDULED_REQUESTS: u32 = 100;
+
+/// The order in which the [`SerialProver`] proves its assigned requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProvingOrder {
+    /// Prove the requests with the soonest deadline first, to miss as few deadlines as possible.
+    #[default]
+    Deadline,
+    /// Prove the requests that pay the most first, breaking ties by the soonest deadline.
+    ///
+    /// Assigned requests don't include the PGUs they use until they are fulfilled, so they are
+    /// ranked by the price of the winning bid times their gas limit plus their base fee.
+    Profit,
+}
+
+impl ProvingOrder {
+    /// Sort the requests in the order they should be proven.
+    fn sort(self, requests: &mut [ProofRequest]) {
+        match self {
+            Self::Deadline => requests.sort_by_key(|request| request.deadline),
+            Self::Profit => requests.sort_by_key(|request| {
+                (Reverse(estimated_request_cost(request).unwrap_or_default()), request.deadline)
+            }),
+        }
+    }
+}
+
 /// A serial bidder.
 ///
-/// This bidder will bid on requests sequentially. It will bid on the first request and then wait
-/// for the request to be fulfilled before bidding on the next request. It uses the provided
-/// parameters to control how much it bids and how much throughput it can handle.
+/// This bidder will bid on requests sequentially. It will bid on up to `concurrency` requests and
+/// then wait for them to be fulfilled before bidding on more requests. It uses the provided
+/// [`BiddingStrategy`] to control how much it bids and the throughput to control which requests it
+/// can handle.
 #[derive(Debug, Clone)]
-pub struct SerialBidder {
-    /// The $PROVE price per billion proving gas units (PGUs) for the bidder.
-    pub bid: U256,
+pub struct SerialBidder<S = FixedBidStrategy> {
+    /// The strategy used to price bids.
+    pub strategy: S,
     /// The throughput for the prover in proving gas units (PGUs) per second.
     pub throughput: f64,
     /// The prover we are bidding on behalf of.
     pub prover: Address,
+    /// The maximum number of requests the prover can have assigned at once.
+    pub concurrency: usize,
+    /// The proof modes the prover can produce, which are the only ones it bids on.
+    pub supported_modes: Vec<ProofMode>,
+    /// The check that the GPU has enough memory to prove a request before bidding on it, if any.
+    pub vram_preflight: Option<VramPreflight>,
+    /// Whether to log the bids instead of submitting them to the network.
+    pub dry_run: bool,
 }
 
 impl SerialBidder {
-    /// Create a new [`SerialBidder`].
+    /// Create a new [`SerialBidder`] that bids a fixed amount on every request.
     #[must_use]
     pub fn new(bid: U256, throughput: f64, prover: Address) -> Self {
-        Self { bid, throughput, prover }
+        Self {
+            strategy: FixedBidStrategy::new(bid),
+            throughput,
+            prover,
+            concurrency: 1,
+            supported_modes: SUPPORTED_PROOF_MODES.to_vec(),
+            vram_preflight: None,
+            dry_run: false,
+        }
+    }
+}
+
+impl<S: BiddingStrategy> SerialBidder<S> {
+    /// Replace the [`BiddingStrategy`] used to price bids.
+    #[must_use]
+    pub fn with_strategy<T: BiddingStrategy>(self, strategy: T) -> SerialBidder<T> {
+        SerialBidder {
+            strategy,
+            throughput: self.throughput,
+            prover: self.prover,
+            concurrency: self.concurrency,
+            supported_modes: self.supported_modes,
+            vram_preflight: self.vram_preflight,
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Set the maximum number of requests the prover can have assigned at once.
+    ///
+    /// Defaults to 1.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the proof modes the prover can produce.
+    ///
+    /// Requests for other modes are skipped, so that the prover never wins an auction for a proof
+    /// it can't produce. Defaults to [`SUPPORTED_PROOF_MODES`].
+    #[must_use]
+    pub fn with_supported_modes(mut self, modes: impl IntoIterator<Item = ProofMode>) -> Self {
+        self.supported_modes = modes.into_iter().collect();
+        self
+    }
+
+    /// Set the check that the GPU has enough memory to prove a request before bidding on it.
+    ///
+    /// Requests whose estimated memory exceeds the free memory of the GPU are skipped. The check
+    /// is skipped if no GPU is detected. Defaults to no check.
+    #[must_use]
+    pub fn with_vram_preflight(mut self, vram_preflight: VramPreflight) -> Self {
+        self.vram_preflight = Some(vram_preflight);
+        self
+    }
+
+    /// Set whether to log the bids instead of submitting them to the network.
+    ///
+    /// In dry-run mode, the bidder still picks and prices requests as usual, but never places a
+    /// bid, so it can't win an auction that the prover then fails to fulfill. Defaults to `false`.
+    #[must_use]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
     }
 }
 
 #[async_trait]
-impl<C: NodeContext> NodeBidder<C> for SerialBidder {
-    #[allow(clippy::too_many_lines)]
+impl<C: NodeContext, S: BiddingStrategy> NodeBidder<C> for SerialBidder<S> {
     async fn bid(&self, ctx: &C) -> Result<()> {
         const SERIAL_BIDDER_TAG: &str = "\x1b[34m[SerialBidder]\x1b[0m";
 
@@ -104,27 +210,9 @@ impl<C: NodeContext> NodeBidder<C> for SerialBidder {
         let owner = fetch_owner(ctx.network(), &signer).await?;
         info!(owner = %hex::encode(&owner), signer = %hex::encode(&signer), "{SERIAL_BIDDER_TAG} Fetched owner.");
 
-        // Fetch for assigned requests.
-        let assigned_requests = ctx
-            .network()
-            .clone()
-            .get_filtered_proof_requests(GetFilteredProofRequestsRequest {
-                version: Some(SP1_NETWORK_VERSION.to_string()),
-                fulfillment_status: Some(FulfillmentStatus::Assigned.into()),
-                minimum_deadline: Some(time_now()),
-                fulfiller: Some(owner.clone()),
-                limit: Some(1),
-                ..Default::default()
-            })
-            .await?
-            .into_inner()
-            .requests;
-        info!(count = %assigned_requests.len(), "{SERIAL_BIDDER_TAG} Fetched assigned proof requests.");
-
-        if !assigned_requests.is_empty() {
-            info!(
-                "{SERIAL_BIDDER_TAG} At least one assigned proof request found. Skipping the bidding process for now."
-            );
+        // Check that the prover can take on more requests.
+        let capacity = self.capacity(ctx, &owner).await?;
+        if capacity == 0 {
             return Ok(());
         }
 
@@ -136,7 +224,7 @@ impl<C: NodeContext> NodeBidder<C> for SerialBidder {
                 version: Some(SP1_NETWORK_VERSION.to_string()),
                 fulfillment_status: Some(FulfillmentStatus::Requested.into()),
                 minimum_deadline: Some(time_now()),
-                limit: Some(1),
+                limit: Some(u32::try_from(capacity).unwrap_or(u32::MAX)),
                 not_bid_by: Some(owner.clone()),
                 ..Default::default()
             })
@@ -151,19 +239,67 @@ impl<C: NodeContext> NodeBidder<C> for SerialBidder {
             return Ok(());
         }
 
-        // There should only be at most one request.
-        if unassigned_requests.len() > 1 {
+        // Bid on each request.
+        for request in unassigned_requests {
+            self.bid_on_request(ctx, &request.request_id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn bid_on(&self, ctx: &C, request: &ProofRequest) -> Result<()> {
+        // Check that the prover can take on another request.
+        let signer = ctx.signer().address().to_vec();
+        let owner = fetch_owner(ctx.network(), &signer).await?;
+        if self.capacity(ctx, &owner).await? == 0 {
+            return Ok(());
+        }
+
+        self.bid_on_request(ctx, &request.request_id).await
+    }
+}
+
+impl<S: BiddingStrategy> SerialBidder<S> {
+    /// The number of additional requests the prover can take on, given the requests already
+    /// assigned to the owner.
+    async fn capacity<C: NodeContext>(&self, ctx: &C, owner: &[u8]) -> Result<usize> {
+        const SERIAL_BIDDER_TAG: &str = "\x1b[34m[SerialBidder]\x1b[0m";
+
+        // Fetch for assigned requests.
+        let assigned_requests = ctx
+            .network()
+            .clone()
+            .get_filtered_proof_requests(GetFilteredProofRequestsRequest {
+                version: Some(SP1_NETWORK_VERSION.to_string()),
+                fulfillment_status: Some(FulfillmentStatus::Assigned.into()),
+                minimum_deadline: Some(time_now()),
+                fulfiller: Some(owner.to_vec()),
+                limit: Some(u32::try_from(self.concurrency).unwrap_or(u32::MAX)),
+                ..Default::default()
+            })
+            .await?
+            .into_inner()
+            .requests;
+        info!(count = %assigned_requests.len(), "{SERIAL_BIDDER_TAG} Fetched assigned proof requests.");
+
+        if assigned_requests.len() >= self.concurrency {
             info!(
-                "{SERIAL_BIDDER_TAG} Found more than one unassigned request to bid on. Skipping..."
+                "{SERIAL_BIDDER_TAG} Prover is at capacity with assigned proof requests. Skipping the bidding process for now."
             );
-            return Ok(());
+            return Ok(0);
         }
+        Ok(self.concurrency - assigned_requests.len())
+    }
 
-        let request = unassigned_requests.first().unwrap();
-        let request_id = hex::encode(&request.request_id);
+    /// Bid on a single unassigned request if there is enough time left to prove it.
+    #[allow(clippy::too_many_lines)]
+    async fn bid_on_request<C: NodeContext>(&self, ctx: &C, request_id: &[u8]) -> Result<()> {
+        const SERIAL_BIDDER_TAG: &str = "\x1b[34m[SerialBidder]\x1b[0m";
+
+        let request_id = hex::encode(request_id);
         let address = ctx.signer().address().to_vec();
 
-        info!("{SERIAL_BIDDER_TAG} Found one unassigned request to bid on.");
+        info!(request_id = %request_id, "{SERIAL_BIDDER_TAG} Found unassigned request to bid on.");
         ctx.network()
             .clone()
             .with_retry(
@@ -194,6 +330,8 @@ impl<C: NodeContext> NodeBidder<C> for SerialBidder {
                     let current_time = time_now();
                     let remaining_time = request.deadline.saturating_sub(current_time);
                     let required_time = ((request.gas_limit as f64) / self.throughput) as u64;
+                    let context =
+                        RequestContext { request: request.clone(), remaining_time, required_time };
 
                     info!(
                         request_id = %request_id,
@@ -220,30 +358,35 @@ impl<C: NodeContext> NodeBidder<C> for SerialBidder {
                         "{SERIAL_BIDDER_TAG} Fetched request details."
                     );
 
+                    let mode = ProofMode::try_from(request.mode).unwrap_or_default();
+                    if !self.supported_modes.contains(&mode) {
+                        info!(request_id = %request_id, mode = %mode.as_str_name(), "{SERIAL_BIDDER_TAG} Proof mode is not supported. Skipping...");
+                        return Ok(());
+                    }
+
+                    if let Some(preflight) = &self.vram_preflight {
+                        let required_mib = preflight.required_mib(&request);
+                        if let Some(available_mib) = preflight.available_mib() {
+                            if required_mib > available_mib {
+                                info!(request_id = %request_id, required_mib = %required_mib, available_mib = %available_mib, "{SERIAL_BIDDER_TAG} Not enough GPU memory to prove request. Skipping...");
+                                return Ok(());
+                            }
+                        }
+                    }
+
                     if remaining_time < required_time {
                         info!(request_id = %request_id, remaining_time = %remaining_time, required_time = %required_time, "{SERIAL_BIDDER_TAG} Not enough time to bid on request. Skipping...");
                         return Ok(());
                     }
 
-                    // Bid on the request.
-                    info!(request_id = %request_id, bid = %self.bid, "{SERIAL_BIDDER_TAG} Submitting a bid for request");
-                    let body = BidRequestBody {
-                        nonce,
-                        request_id: hex::decode(request_id.clone())
-                            .context("failed to decode request_id")?,
-                        amount: self.bid.to_string(),
-                        prover: self.prover.to_vec(),
-                        domain: SPN_MAINNET_V1_DOMAIN.to_vec(),
-                        variant: TransactionVariant::BidVariant as i32,
-                    };
-                    let bid_request = BidRequest {
-                        format: MessageFormat::Binary.into(),
-                        signature: body.sign(&ctx.signer()).into(),
-                        body: Some(body),
+                    // Price the bid using the strategy.
+                    let Some(bid) = self.strategy.bid(&context) else {
+                        info!(request_id = %request_id, "{SERIAL_BIDDER_TAG} Bidding strategy declined to bid on request. Skipping...");
+                        return Ok(());
                     };
-                    ctx.network().clone().bid(bid_request).await?;
 
-                    Ok(())
+                    // Bid on the request.
+                    self.submit_bid(ctx, &request, nonce, bid).await
                 },
                 "Bid",
             )
@@ -251,16 +394,77 @@ impl<C: NodeContext> NodeBidder<C> for SerialBidder {
 
         Ok(())
     }
+
+    /// Sign and submit a bid on the request, or only log it in dry-run mode.
+    async fn submit_bid<C: NodeContext>(
+        &self,
+        ctx: &C,
+        request: &ProofRequest,
+        nonce: u64,
+        bid: U256,
+    ) -> Result<()> {
+        const SERIAL_BIDDER_TAG: &str = "\x1b[34m[SerialBidder]\x1b[0m";
+
+        let request_id = hex::encode(&request.request_id);
+        if self.dry_run {
+            info!(request_id = %request_id, bid = %bid, "{SERIAL_BIDDER_TAG} Dry run, skipping bid for request");
+            return Ok(());
+        }
+
+        info!(request_id = %request_id, bid = %bid, "{SERIAL_BIDDER_TAG} Submitting a bid for request");
+        let body = BidRequestBody {
+            nonce,
+            request_id: request.request_id.clone(),
+            amount: bid.to_string(),
+            prover: self.prover.to_vec(),
+            domain: SPN_MAINNET_V1_DOMAIN.to_vec(),
+            variant: TransactionVariant::BidVariant as i32,
+        };
+        let signature = body.sign(&ctx.signer()).context("failed to sign bid request")?;
+        let bid_request = BidRequest {
+            format: MessageFormat::Binary.into(),
+            signature: signature.into(),
+            body: Some(body),
+        };
+        ctx.network().clone().bid(bid_request).await?;
+        ctx.metrics().record_bid(&request.request_id, request.deadline).await;
+
+        Ok(())
+    }
 }
 
 /// A serial prover.
 ///
-/// This prover will generate proofs for requests sequentially using an [`EnvProver`].
+/// This prover will generate proofs for requests using an [`EnvProver`]. By default it proves one
+/// request at a time, but it can be configured to prove up to `concurrency` requests at once.
+/// Requests are proven in background tasks, so that the node keeps bidding while they are proven.
+/// The tasks are owned by the prover and awaited by [`NodeProver::drain`] when the node shuts down.
+#[derive(Clone)]
 pub struct SerialProver {
     /// The underlying prover for the node that will be used to generate proofs.
     prover: Arc<EnvProver>,
     /// Registry of unexecutable request IDs that should be cancelled.
     unexecutable_requests: Arc<Mutex<HashSet<Vec<u8>>>>,
+    /// The maximum number of requests to prove at once.
+    concurrency: usize,
+    /// The permits limiting the number of requests being proven at once.
+    ///
+    /// Each proving task holds a permit until its request is proven, across calls to
+    /// [`NodeProver::prove`].
+    permits: Arc<Semaphore>,
+    /// The tasks proving requests, whose requests aren't picked again until their task finishes.
+    tasks: Arc<Mutex<ProvingTasks>>,
+    /// Whether to verify each proof locally before submitting it to the network.
+    verify_proofs: bool,
+    /// The cache of downloaded programs and stdins, if enabled.
+    artifact_cache: Option<Arc<ArtifactCache>>,
+    /// Whether to prove requests without submitting the proofs to the network.
+    dry_run: bool,
+    /// The requests already handled in dry-run mode, which stay assigned since neither their
+    /// proofs nor their failures are submitted.
+    dry_run_handled: Arc<Mutex<HashSet<Vec<u8>>>>,
+    /// The order in which the assigned requests are proven.
+    proving_order: ProvingOrder,
 }
 
 impl SerialProver {
@@ -278,6 +482,79 @@ impl SerialProver {
         Self {
             prover: Arc::new(EnvProver::new().await),
             unexecutable_requests: Arc::new(Mutex::new(HashSet::new())),
+            concurrency: 1,
+            permits: Arc::new(Semaphore::new(1)),
+            tasks: Arc::new(Mutex::new(ProvingTasks::default())),
+            verify_proofs: false,
+            artifact_cache: None,
+            dry_run: false,
+            dry_run_handled: Arc::new(Mutex::new(HashSet::new())),
+            proving_order: ProvingOrder::default(),
+        }
+    }
+
+    /// Set the maximum number of requests to prove at once.
+    ///
+    /// Defaults to 1, since a single GPU can only prove one request at a time.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self.permits = Arc::new(Semaphore::new(self.concurrency));
+        self
+    }
+
+    /// Set whether to verify each proof locally before submitting it to the network.
+    ///
+    /// A proof that fails verification is reported as a failed fulfillment instead of being
+    /// submitted, since the network would reject it anyway. Defaults to `false`.
+    #[must_use]
+    pub fn with_proof_verification(mut self, verify_proofs: bool) -> Self {
+        self.verify_proofs = verify_proofs;
+        self
+    }
+
+    /// Set the [`ArtifactCache`] used to avoid downloading the same program or stdin again.
+    ///
+    /// This helps provers that serve many requests for the same program. Defaults to no cache.
+    #[must_use]
+    pub fn with_artifact_cache(mut self, artifact_cache: ArtifactCache) -> Self {
+        self.artifact_cache = Some(Arc::new(artifact_cache));
+        self
+    }
+
+    /// Set whether to prove requests without submitting the proofs to the network.
+    ///
+    /// In dry-run mode, the prover logs the fulfillment or failure it would have submitted instead
+    /// of sending it, and proves each assigned request only once. This lets operators check that a
+    /// new prover generates valid proofs for live requests. Defaults to `false`.
+    #[must_use]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the order in which the assigned requests are proven.
+    ///
+    /// When more requests are assigned than can be proven at once, the prover picks the first ones
+    /// in this order and leaves the rest for later. Defaults to [`ProvingOrder::Deadline`].
+    #[must_use]
+    pub fn with_proving_order(mut self, proving_order: ProvingOrder) -> Self {
+        self.proving_order = proving_order;
+        self
+    }
+
+    /// Download a program or stdin artifact from a URI, through the artifact cache if enabled.
+    async fn download_artifact(
+        &self,
+        artifact: &Artifact,
+        uri: &str,
+        artifact_type: ArtifactType,
+    ) -> Result<Bytes> {
+        match &self.artifact_cache {
+            Some(cache) => {
+                artifact.download_raw_from_uri_cached(uri, "", artifact_type, cache).await
+            }
+            None => artifact.download_raw_from_uri(uri, "", artifact_type).await,
         }
     }
 
@@ -359,6 +636,18 @@ impl SerialProver {
     }
 }
 
+/// An upper bound on the cost paid by the requester for a request, which is the price per PGU of
+/// the winning bid times the PGUs used plus the base fee.
+///
+/// The network only sets the PGUs used once a request is fulfilled, so for the assigned requests
+/// the prover works on, the cost is estimated from the gas limit instead, which bounds the PGUs
+/// the request can be charged for. Returns `None` if the request doesn't include its price.
+fn estimated_request_cost(request: &ProofRequest) -> Option<U256> {
+    let base_fee = request.base_fee.as_deref().and_then(|fee| fee.parse().ok()).unwrap_or_default();
+    let pgus = request.gas_used.unwrap_or(request.gas_limit);
+    spn_vapp_core::fee::request_cost(U256::from(request.gas_price?), pgus, base_fee).ok()
+}
+
 /// Attempts to notify the network that proving a request failed.
 async fn fail_request<C: NodeContext>(ctx: &C, request_id: Vec<u8>) -> Result<()> {
     const SERIAL_PROVER_TAG: &str = "\x1b[33m[SerialProver]\x1b[0m";
@@ -382,9 +671,10 @@ async fn fail_request<C: NodeContext>(ctx: &C, request_id: Vec<u8>) -> Result<()
                     request_id: request_id.clone(),
                     error: None,
                 };
+                let signature = body.sign(&ctx.signer()).context("failed to sign fail request")?;
                 let fail_request = FailFulfillmentRequest {
                     format: MessageFormat::Binary.into(),
-                    signature: body.sign(&ctx.signer()).into(),
+                    signature: signature.into(),
                     body: Some(body),
                 };
                 ctx.network().clone().fail_fulfillment(fail_request).await?;
@@ -446,25 +736,79 @@ impl NodeMonitor<SerialContext> for SerialMonitor {
 
         // Log the node metrics.
         let metrics = ctx.metrics();
-        let fulfilled = *metrics.fulfilled.lock().await;
-        let total_cycles = *metrics.total_cycles.lock().await;
-        let total_proving_time = *metrics.total_proving_time.lock().await;
+        let snapshot = metrics.snapshot().await;
+        let NodeMetricsSnapshot {
+            fulfilled,
+            total_cycles,
+            total_proving_time,
+            panics,
+            failed,
+            panicked,
+            bids_placed,
+            bids_won,
+            estimated_earnings,
+            uptime,
+        } = snapshot;
         let throughput = total_cycles as f64 / total_proving_time.as_secs() as f64;
         let throughput = if throughput.is_nan() {
             "0 MHz".to_string()
         } else {
             format!("{:.2} MHz", throughput / 1_000_000.0)
         };
+        let recent_throughput = metrics.recent_throughput().await.map_or_else(
+            || "n/a".to_string(),
+            |throughput| format!("{:.2} MHz", throughput / 1_000_000.0),
+        );
         let total_cycles = format!("{:.2}M", total_cycles as f64 / 1_000_000.0);
-        let total_proving_time = humantime::format_duration(total_proving_time).to_string();
+        let total_proving_time = format_duration(total_proving_time);
+        let uptime = format_duration(uptime);
         info!(
+            uptime = %uptime,
             fulfilled = %fulfilled,
             total_cycles = %total_cycles,
             total_proving_time = %total_proving_time,
             throughput = %throughput,
+            recent_throughput = %recent_throughput,
+            panics = %panics,
             "{SERIAL_MONITOR_TAG} Checking node metrics..."
         );
 
+        // Log the proving failures.
+        let failure_rate = snapshot
+            .failure_rate()
+            .map_or_else(|| "n/a".to_string(), |rate| format!("{:.2}%", rate * 100.0));
+        info!(
+            failed = %failed,
+            panicked = %panicked,
+            failure_rate = %failure_rate,
+            "{SERIAL_MONITOR_TAG} Checking proving failures..."
+        );
+
+        // Log the bidding outcomes.
+        let win_rate = snapshot
+            .win_rate()
+            .map_or_else(|| "n/a".to_string(), |rate| format!("{:.2}%", rate * 100.0));
+        let estimated_earnings =
+            format_units(estimated_earnings, 18).unwrap_or_else(|_| estimated_earnings.to_string());
+        info!(
+            bids_placed = %bids_placed,
+            bids_won = %bids_won,
+            win_rate = %win_rate,
+            estimated_earnings = %format!("{estimated_earnings} $PROVE"),
+            "{SERIAL_MONITOR_TAG} Checking bidding outcomes..."
+        );
+
+        // Log the proving duration percentiles.
+        if let Some(percentiles) = metrics.proving_duration_percentiles().await {
+            info!(
+                p50 = %format_duration(percentiles.p50),
+                p90 = %format_duration(percentiles.p90),
+                p99 = %format_duration(percentiles.p99),
+                max = %format_duration(percentiles.max),
+                "{SERIAL_MONITOR_TAG} Checking proving durations..."
+            );
+        }
+
         // Get system metrics.
         let mut system = System::new_all();
         system.refresh_all();
@@ -509,8 +853,7 @@ impl NodeMonitor<SerialContext> for SerialMonitor {
 
 #[async_trait]
 impl<C: NodeContext> NodeProver<C> for SerialProver {
-    #[allow(clippy::too_many_lines)]
-    async fn prove(&self, ctx: &C) -> Result<()> {
+    async fn prove(&self, ctx: &Arc<C>) -> Result<()> {
         const SERIAL_PROVER_TAG: &str = "\x1b[33m[SerialProver]\x1b[0m";
 
         // Ensure the background check task is running.
@@ -522,7 +865,7 @@ impl<C: NodeContext> NodeProver<C> for SerialProver {
         info!(owner = %hex::encode(&owner), signer = %hex::encode(&signer), "{SERIAL_PROVER_TAG} Fetched owner.");
 
         // Fetch for assigned requests.
-        let requests = ctx
+        let mut requests = ctx
             .network()
             .clone()
             .get_filtered_proof_requests(GetFilteredProofRequestsRequest {
@@ -530,7 +873,7 @@ impl<C: NodeContext> NodeProver<C> for SerialProver {
                 fulfillment_status: Some(FulfillmentStatus::Assigned.into()),
                 minimum_deadline: Some(time_now()),
                 fulfiller: Some(owner.clone()),
-                limit: Some(1),
+                limit: Some(MAX_SCHEDULED_REQUESTS),
                 ..Default::default()
             })
             .await?
@@ -538,264 +881,476 @@ impl<C: NodeContext> NodeProver<C> for SerialProver {
             .requests;
         info!(count = %requests.len(), "{SERIAL_PROVER_TAG} Fetched assigned proof requests.");
 
+        // In dry-run mode, skip the requests that were already handled but never fulfilled.
+        if self.dry_run {
+            let handled = self.dry_run_handled.lock().await;
+            requests.retain(|request| !handled.contains(&request.request_id));
+        }
+
+        // Collect the tasks that finished since the last iteration, and skip the requests that
+        // are still being proven.
+        let mut tasks = self.tasks.lock().await;
+        while let Some(result) = tasks.set.try_join_next_with_id() {
+            tasks.finish(ctx.metrics(), result).await;
+        }
+        requests.retain(|request| !tasks.contains(&request.request_id));
+
         // If there are no assigned requests, return.
         if requests.is_empty() {
             info!("{SERIAL_PROVER_TAG} Found no assigned requests to prove.");
             return Ok(());
         }
 
+        // Prove the requests in order while permits are available, leaving the rest for the next
+        // iterations. Each task holds its permit until its request is proven, so the number of
+        // requests being proven at once stays bounded while the node keeps bidding.
+        self.proving_order.sort(&mut requests);
         for request in requests {
-            // Check if this request is already known to be unexecutable.
-            let request_id = request.request_id.clone();
-            let unexecutable_registry = self.unexecutable_requests.lock().await;
-            if unexecutable_registry.contains(&request_id) {
-                info!(
-                    request_id = %hex::encode(&request_id),
-                    "{SERIAL_PROVER_TAG} Skipping request marked as UNEXECUTABLE"
-                );
+            let Ok(permit) = self.permits.clone().try_acquire_owned() else {
+                break;
+            };
+
+            let prover = self.clone();
+            let ctx = ctx.clone();
+            tasks.spawn(request.request_id.clone(), async move {
+                let result = prover.prove_request(ctx.as_ref(), request).await;
+                drop(permit);
+                result
+            });
+        }
+
+        Ok(())
+    }
 
-                // Release lock early.
-                drop(unexecutable_registry);
+    async fn drain(&self, ctx: &Arc<C>) -> Result<()> {
+        const SERIAL_PROVER_TAG: &str = "\x1b[33m[SerialProver]\x1b[0m";
 
-                // Notify the network about the failure.
-                report_request_status(ctx, request_id.clone(), &request_id, "skipped UNEXECUTABLE")
-                    .await;
+        // Wait for the requests being proven to be fulfilled before the node exits.
+        let mut tasks = self.tasks.lock().await;
+        if !tasks.set.is_empty() {
+            info!(count = %tasks.set.len(), "{SERIAL_PROVER_TAG} Waiting for requests being proven.");
+        }
+        while let Some(result) = tasks.set.join_next_with_id().await {
+            tasks.finish(ctx.metrics(), result).await;
+        }
 
-                continue;
+        Ok(())
+    }
+}
+
+/// The tasks proving requests in the background, and the requests they prove.
+#[derive(Default)]
+struct ProvingTasks {
+    /// The tasks, which return the result of proving their request.
+    set: JoinSet<Result<()>>,
+    /// The ID of the request proven by each task.
+    requests: HashMap<task::Id, Vec<u8>>,
+}
+
+impl ProvingTasks {
+    /// Whether the request is being proven by one of the tasks.
+    fn contains(&self, request_id: &[u8]) -> bool {
+        self.requests.values().any(|id| id == request_id)
+    }
+
+    /// Spawn a task proving the request.
+    fn spawn<F>(&mut self, request_id: Vec<u8>, future: F)
+    where
+        F: Future<Output = Result<()>> + Send + 'static,
+    {
+        let handle = self.set.spawn(future);
+        self.requests.insert(handle.id(), request_id);
+    }
+
+    /// Handle the result of a finished task.
+    ///
+    /// Failures are logged, and panics are also counted in the [`NodeMetrics`] like those of the
+    /// other node tasks. Either way, the request can be picked again if it is still assigned.
+    async fn finish(
+        &mut self,
+        metrics: &NodeMetrics,
+        result: Result<(task::Id, Result<()>), JoinError>,
+    ) {
+        const SERIAL_PROVER_TAG: &str = "\x1b[33m[SerialProver]\x1b[0m";
+
+        let (id, result) = match result {
+            Ok((id, result)) => (id, result),
+            Err(e) => {
+                let id = e.id();
+                if e.is_panic() {
+                    let message = ErrorCapture::extract_panic_message(&e.into_panic());
+                    metrics.record_panic().await;
+                    (id, Err(anyhow::anyhow!("prover panicked: {message}")))
+                } else {
+                    (id, Err(anyhow::anyhow!("proving task was cancelled")))
+                }
             }
+        };
+        let request_id = self.requests.remove(&id).unwrap_or_default();
+        if let Err(e) = result {
+            error!(
+                request_id = %hex::encode(&request_id),
+                "{SERIAL_PROVER_TAG} Failed to prove request: {:?}",
+                e
+            );
+        }
+    }
+}
 
-            // No longer need the registry lock.
-            drop(unexecutable_registry);
+impl SerialProver {
+    /// Helper function to report a request status to the network and log the result.
+    /// This handles both success and failure of the reporting itself.
+    ///
+    /// In dry-run mode, the status is only logged, and the request isn't proven again.
+    async fn report_request_status<C: NodeContext>(
+        &self,
+        ctx: &C,
+        request_id: Vec<u8>,
+        display_request_id: &[u8],
+        status_type: &str,
+    ) {
+        const SERIAL_PROVER_TAG: &str = "\x1b[33m[SerialProver]\x1b[0m";
 
-            // Log the request details.
-            let request_id_hex = hex::encode(&request.request_id);
+        if self.dry_run {
             info!(
-                request_id = %request_id_hex,
-                vk_hash = %hex::encode(request.vk_hash),
-                version = %request.version,
-                mode = %request.mode,
-                strategy = %request.strategy,
-                requester = %hex::encode(request.requester),
-                tx_hash = %hex::encode(request.tx_hash),
-                program_uri = %request.program_public_uri,
-                stdin_uri = %request.stdin_public_uri,
-                cycle_limit = %request.cycle_limit,
-                created_at = %request.created_at,
-                created_at_utc = %DateTime::from_timestamp(i64::try_from(request.created_at).unwrap_or_default(), 0).unwrap_or_default(),
-                deadline = %request.deadline,
-                deadline_utc = %DateTime::from_timestamp(i64::try_from(request.deadline).unwrap_or_default(), 0).unwrap_or_default(),
-                "{SERIAL_PROVER_TAG} Proving request..."
+                request_id = %hex::encode(display_request_id),
+                "{SERIAL_PROVER_TAG} Dry run, skipping report of {} status to network",
+                status_type
             );
+            self.dry_run_handled.lock().await.insert(request_id);
+            return;
+        }
 
-            // Download the program.
-            let program_artifact_id = extract_artifact_name(&request.program_public_uri)?;
-            let program_artifact = Artifact {
-                id: program_artifact_id.clone(),
-                label: "program".to_string(),
-                expiry: None,
-            };
-            let program: Vec<u8> =
-                program_artifact.download_program_from_uri(&request.program_public_uri, "").await?;
-            info!(program_size = %program.len(), artifact_id = %hex::encode(program_artifact_id), "{SERIAL_PROVER_TAG} Downloaded program.");
-
-            // Download the stdin.
-            let stdin_artifact_id = extract_artifact_name(&request.stdin_public_uri)?;
-            let stdin_artifact = Artifact {
-                id: stdin_artifact_id.clone(),
-                label: "stdin".to_string(),
-                expiry: None,
-            };
-            let stdin: SP1Stdin =
-                stdin_artifact.download_stdin_from_uri(&request.stdin_public_uri, "").await?;
-            info!(stdin_size = %stdin.buffer.iter().map(std::vec::Vec::len).sum::<usize>(), artifact_id = %hex::encode(stdin_artifact_id), "{SERIAL_PROVER_TAG} Downloaded stdin.");
-
-            // Generate the proving keys and the proof in a separate task.
-            let prover = self.prover.clone();
-            let mode = ProofMode::try_from(request.mode).unwrap_or(ProofMode::Core);
-            let mode = match mode {
-                ProofMode::Core => SP1ProofMode::Core,
-                ProofMode::Compressed => SP1ProofMode::Compressed,
-                ProofMode::Plonk => SP1ProofMode::Plonk,
-                ProofMode::Groth16 => SP1ProofMode::Groth16,
-                ProofMode::UnspecifiedProofMode => unreachable!(),
-            };
+        if let Err(fail_err) = fail_request(ctx, request_id).await {
+            error!(
+                request_id = %hex::encode(display_request_id),
+                "{SERIAL_PROVER_TAG} Failed to notify network about {} status: {:?}",
+                status_type,
+                fail_err
+            );
+        } else {
+            info!(
+                request_id = %hex::encode(display_request_id),
+                "{SERIAL_PROVER_TAG} Successfully reported {} status to network",
+                status_type
+            );
+        }
+    }
 
-            // Store the join handle and extract its abort handle.
-            let proving_handle = tokio::spawn(async move {
-                let start = Instant::now();
-                info!("{SERIAL_PROVER_TAG} Setting up proving key...");
+    /// Prove a single assigned request and submit the proof to the network.
+    #[allow(clippy::too_many_lines)]
+    async fn prove_request<C: NodeContext>(&self, ctx: &C, request: ProofRequest) -> Result<()> {
+        const SERIAL_PROVER_TAG: &str = "\x1b[33m[SerialProver]\x1b[0m";
 
-                let pk = prover.setup(program.into()).await?;
-                info!(duration = %start.elapsed().as_secs_f64(), "{SERIAL_PROVER_TAG} Set up proving key.");
+        // Count the assignment as a won bid if we bid on it.
+        ctx.metrics().record_assignment(&request.request_id).await;
 
-                let start = Instant::now();
-                info!("{SERIAL_PROVER_TAG} Executing program...");
-                let (_, report) = prover.execute(pk.elf().clone(), stdin.clone()).await?;
-                let cycles = report.total_instruction_count();
-                info!(duration = %start.elapsed().as_secs_f64(), cycles = %cycles, "{SERIAL_PROVER_TAG} Executed program.");
+        // Check if this request is already known to be unexecutable.
+        let request_id = request.request_id.clone();
+        let unexecutable_registry = self.unexecutable_requests.lock().await;
+        if unexecutable_registry.contains(&request_id) {
+            info!(
+                request_id = %hex::encode(&request_id),
+                "{SERIAL_PROVER_TAG} Skipping request marked as UNEXECUTABLE"
+            );
 
-                let start = Instant::now();
-                info!("{SERIAL_PROVER_TAG} Generating proof...");
-                let proof = prover.prove(&pk, stdin).mode(mode).await?;
-                let proving_time = start.elapsed();
-                info!(duration = %proving_time.as_secs_f64(), cycles = %cycles, "{SERIAL_PROVER_TAG} Proof generation complete.");
-                Ok::<_, anyhow::Error>((proof, cycles, proving_time))
-            });
-            let proving_abort_handle = proving_handle.abort_handle();
-
-            // Create a check task for this specific request.
-            let request_id = request.request_id.clone();
-            let unexecutable_registry = self.unexecutable_requests.clone();
-
-            // Spawn a task to periodically check if the request became UNEXECUTABLE.
-            let monitoring_task = tokio::spawn(async move {
-                // Check every 2 seconds if the request is now in our unexecutable registry.
-                let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
-                loop {
-                    interval.tick().await;
-
-                    // Check if we already know this request is unexecutable.
-                    let is_unexecutable = {
-                        let registry = unexecutable_registry.lock().await;
-                        registry.contains(&request_id)
-                    };
+            // Release lock early.
+            drop(unexecutable_registry);
 
-                    if is_unexecutable {
-                        info!(
-                            request_id = %hex::encode(&request_id),
-                            "{SERIAL_PROVER_TAG} Request now marked as UNEXECUTABLE, aborting proof generation"
-                        );
+            // Notify the network about the failure.
+            self.report_request_status(
+                ctx,
+                request_id.clone(),
+                &request_id,
+                "skipped UNEXECUTABLE",
+            )
+            .await;
 
-                        // Abort the proving task.
-                        proving_abort_handle.abort();
+            return Ok(());
+        }
 
-                        info!("{SERIAL_PROVER_TAG} Aborted proving task.");
+        // No longer need the registry lock.
+        drop(unexecutable_registry);
 
-                        break;
-                    }
+        // Log the request details.
+        let request_id_hex = hex::encode(&request.request_id);
+        info!(
+            request_id = %request_id_hex,
+            vk_hash = %hex::encode(request.vk_hash),
+            version = %request.version,
+            mode = %request.mode,
+            strategy = %request.strategy,
+            requester = %hex::encode(request.requester),
+            tx_hash = %hex::encode(request.tx_hash),
+            program_uri = %request.program_public_uri,
+            stdin_uri = %request.stdin_public_uri,
+            cycle_limit = %request.cycle_limit,
+            created_at = %request.created_at,
+            created_at_utc = %DateTime::from_timestamp(i64::try_from(request.created_at).unwrap_or_default(), 0).unwrap_or_default(),
+            deadline = %request.deadline,
+            deadline_utc = %DateTime::from_timestamp(i64::try_from(request.deadline).unwrap_or_default(), 0).unwrap_or_default(),
+            "{SERIAL_PROVER_TAG} Proving request..."
+        );
+
+        // Resolve the proof mode of the request before downloading anything.
+        let mode = ProofMode::try_from(request.mode).unwrap_or_default();
+        let Some(mode) = sp1_proof_mode(mode) else {
+            error!(request_id = %request_id_hex, mode = %request.mode, "{SERIAL_PROVER_TAG} Request has no valid proof mode.");
+            self.report_request_status(
+                ctx,
+                request.request_id.clone(),
+                &request.request_id,
+                "invalid proof mode",
+            )
+            .await;
+            return Ok(());
+        };
+
+        // Download the program.
+        let program_artifact_id = extract_artifact_name(&request.program_public_uri)?;
+        let program_artifact = Artifact {
+            id: program_artifact_id.clone(),
+            label: "program".to_string(),
+            expiry: None,
+        };
+        let program = self
+            .download_artifact(
+                &program_artifact,
+                &request.program_public_uri,
+                ArtifactType::Program,
+            )
+            .await?;
+        let program: Vec<u8> =
+            bincode::deserialize(&program).context("Failed to deserialize program from URI")?;
+        info!(program_size = %program.len(), artifact_id = %hex::encode(program_artifact_id), "{SERIAL_PROVER_TAG} Downloaded program.");
+
+        // Download the stdin.
+        let stdin_artifact_id = extract_artifact_name(&request.stdin_public_uri)?;
+        let stdin_artifact =
+            Artifact { id: stdin_artifact_id.clone(), label: "stdin".to_string(), expiry: None };
+        let stdin = self
+            .download_artifact(&stdin_artifact, &request.stdin_public_uri, ArtifactType::Stdin)
+            .await?;
+        let stdin: SP1Stdin =
+            bincode::deserialize(&stdin).context("Failed to deserialize stdin from URI")?;
+        info!(stdin_size = %stdin.buffer.iter().map(std::vec::Vec::len).sum::<usize>(), artifact_id = %hex::encode(stdin_artifact_id), "{SERIAL_PROVER_TAG} Downloaded stdin.");
+
+        // Generate the proving keys and the proof in a separate task.
+        let prover = self.prover.clone();
+
+        // Store the join handle and extract its abort handle.
+        let verify_proofs = self.verify_proofs;
+        let vk_hash = request.vk_hash.clone();
+        let proving_handle = tokio::spawn(async move {
+            let start = Instant::now();
+            info!("{SERIAL_PROVER_TAG} Setting up proving key...");
+
+            let pk = prover.setup(program.into()).await?;
+            info!(duration = %start.elapsed().as_secs_f64(), "{SERIAL_PROVER_TAG} Set up proving key.");
+
+            let start = Instant::now();
+            info!("{SERIAL_PROVER_TAG} Executing program...");
+            let (_, report) = prover.execute(pk.elf().clone(), stdin.clone()).await?;
+            let cycles = report.total_instruction_count();
+            info!(duration = %start.elapsed().as_secs_f64(), cycles = %cycles, "{SERIAL_PROVER_TAG} Executed program.");
+
+            let start = Instant::now();
+            info!("{SERIAL_PROVER_TAG} Generating proof...");
+            let proof = prover.prove(&pk, stdin).mode(mode).await?;
+            let proving_time = start.elapsed();
+            info!(duration = %proving_time.as_secs_f64(), cycles = %cycles, "{SERIAL_PROVER_TAG} Proof generation complete.");
+
+            if verify_proofs {
+                let start = Instant::now();
+                info!("{SERIAL_PROVER_TAG} Verifying proof...");
+                let vk = pk.verifying_key();
+                if vk.bytes32_raw().as_slice() != vk_hash.as_slice() {
+                    anyhow::bail!(
+                        "program vk hash 0x{} does not match the requested vk hash 0x{}",
+                        hex::encode(vk.bytes32_raw()),
+                        hex::encode(&vk_hash)
+                    );
                 }
-            });
+                prover.verify(&proof, vk, None).context("proof failed local verification")?;
+                info!(duration = %start.elapsed().as_secs_f64(), "{SERIAL_PROVER_TAG} Verified proof.");
+            }
+            Ok::<_, anyhow::Error>((proof, cycles, proving_time))
+        });
+        let proving_abort_handle = proving_handle.abort_handle();
+
+        // Create a check task for this specific request.
+        let request_id = request.request_id.clone();
+        let unexecutable_registry = self.unexecutable_requests.clone();
 
-            // Wait for the proving task to complete or be aborted.
-            let result = proving_handle.await;
+        // Spawn a task to periodically check if the request became UNEXECUTABLE.
+        let monitoring_task = tokio::spawn(async move {
+            // Check every 2 seconds if the request is now in our unexecutable registry.
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
+            loop {
+                interval.tick().await;
 
-            // Cancel the monitoring task since proving is done.
-            monitoring_task.abort();
+                // Check if we already know this request is unexecutable.
+                let is_unexecutable = {
+                    let registry = unexecutable_registry.lock().await;
+                    registry.contains(&request_id)
+                };
 
-            match result {
-                Ok(Ok((proof, cycles, proving_time))) => {
-                    // Update the metrics.
-                    let metrics = ctx.metrics();
-                    *metrics.total_cycles.lock().await += cycles;
-                    *metrics.total_proving_time.lock().await += proving_time;
-                    *metrics.fulfilled.lock().await += 1;
+                if is_unexecutable {
+                    info!(
+                        request_id = %hex::encode(&request_id),
+                        "{SERIAL_PROVER_TAG} Request now marked as UNEXECUTABLE, aborting proof generation"
+                    );
 
-                    // Now serialize the actual proof value.
-                    let proof_bytes =
-                        bincode::serialize(&proof).context("failed to serialize proof")?;
+                    // Abort the proving task.
+                    proving_abort_handle.abort();
 
-                    // Fulfill the proof.
-                    let address = ctx.signer().address().to_vec();
-                    if let Err(e) = ctx
-                        .network()
-                        .clone()
-                        .with_retry(
-                            || async {
-                                // Get the nonce.
-                                let nonce = ctx
-                                    .network()
-                                    .clone()
-                                    .get_nonce(GetNonceRequest { address: address.clone() })
-                                    .await?
-                                    .into_inner()
-                                    .nonce;
-                                info!(nonce = %nonce, "{SERIAL_PROVER_TAG} Fetched account nonce.");
-
-                                // Create and submit the fulfill request.
-                                let body = FulfillProofRequestBody {
-                                    nonce,
-                                    request_id: request.request_id.clone(),
-                                    proof: proof_bytes.clone(),
-                                    reserved_metadata: None,
-                                    domain: SPN_MAINNET_V1_DOMAIN.to_vec(),
-                                    variant: TransactionVariant::FulfillVariant as i32,
-                                };
-                                let fulfill_request = FulfillProofRequest {
-                                    format: MessageFormat::Binary.into(),
-                                    signature: body.sign(&ctx.signer()).into(),
-                                    body: Some(body),
-                                };
-                                ctx.network().clone().fulfill_proof(fulfill_request).await?;
-                                info!(
-                                    request_id = %hex::encode(&request.request_id),
-                                    proof_size = %proof_bytes.len(),
-                                    "{SERIAL_PROVER_TAG} Proof fulfillment submitted."
-                                );
-                                Ok(())
-                            },
-                            "Fulfill",
-                        )
-                        .await
-                    {
-                        error!("{SERIAL_PROVER_TAG} Failed to fulfill proof: {:?}", e);
-                    }
+                    info!("{SERIAL_PROVER_TAG} Aborted proving task.");
+
+                    break;
                 }
-                Ok(Err(e)) => {
-                    error!("{SERIAL_PROVER_TAG} Proof generation failed: {:?}", e);
-
-                    // Report failure to the network.
-                    report_request_status(
-                        ctx,
-                        request.request_id.clone(),
-                        &request.request_id,
-                        "proof failure",
-                    )
-                    .await;
+            }
+        });
+
+        // Wait for the proving task to complete or be aborted.
+        let result = proving_handle.await;
+
+        // Cancel the monitoring task since proving is done.
+        monitoring_task.abort();
+
+        match result {
+            Ok(Ok((proof, cycles, proving_time))) => {
+                // Update the metrics.
+                ctx.metrics().record_proving_duration(proving_time).await;
+
+                // Now serialize the actual proof value.
+                let proof_bytes =
+                    bincode::serialize(&proof).context("failed to serialize proof")?;
+
+                // In dry-run mode, log the fulfillment instead of submitting it.
+                if self.dry_run {
+                    info!(
+                        request_id = %hex::encode(&request.request_id),
+                        proof_size = %proof_bytes.len(),
+                        signer = %ctx.signer().address(),
+                        "{SERIAL_PROVER_TAG} Dry run, skipping proof fulfillment."
+                    );
+                    self.dry_run_handled.lock().await.insert(request.request_id.clone());
+                    return Ok(());
                 }
-                Err(e) if e.is_panic() => {
-                    let panic_payload = e.into_panic();
-                    let panic_msg = match panic_payload.downcast_ref::<&str>() {
-                        Some(s) => (*s).to_string(),
-                        None => match panic_payload.downcast_ref::<String>() {
-                            Some(s) => s.clone(),
-                            None => "Unknown panic".to_string(),
-                        },
-                    };
 
-                    error!("{SERIAL_PROVER_TAG} Proving panicked: {}", panic_msg);
+                // Count the proof as fulfilled, since it is about to be submitted.
+                ctx.metrics().record_fulfillment(cycles, proving_time).await;
 
-                    // Attempt to mark the request as failed on the network.
-                    report_request_status(
-                        ctx,
-                        request.request_id.clone(),
-                        &request.request_id,
-                        "panic failure",
+                // Fulfill the proof.
+                let address = ctx.signer().address().to_vec();
+                if let Err(e) = ctx
+                    .network()
+                    .clone()
+                    .with_retry(
+                        || async {
+                            // Get the nonce.
+                            let nonce = ctx
+                                .network()
+                                .clone()
+                                .get_nonce(GetNonceRequest { address: address.clone() })
+                                .await?
+                                .into_inner()
+                                .nonce;
+                            info!(nonce = %nonce, "{SERIAL_PROVER_TAG} Fetched account nonce.");
+
+                            // Create and submit the fulfill request.
+                            let body = FulfillProofRequestBody {
+                                nonce,
+                                request_id: request.request_id.clone(),
+                                proof: proof_bytes.clone(),
+                                reserved_metadata: None,
+                                domain: SPN_MAINNET_V1_DOMAIN.to_vec(),
+                                variant: TransactionVariant::FulfillVariant as i32,
+                            };
+                            let signature = body
+                                .sign(&ctx.signer())
+                                .context("failed to sign fulfill request")?;
+                            let fulfill_request = FulfillProofRequest {
+                                format: MessageFormat::Binary.into(),
+                                signature: signature.into(),
+                                body: Some(body),
+                            };
+                            ctx.network().clone().fulfill_proof(fulfill_request).await?;
+                            info!(
+                                request_id = %hex::encode(&request.request_id),
+                                proof_size = %proof_bytes.len(),
+                                "{SERIAL_PROVER_TAG} Proof fulfillment submitted."
+                            );
+                            if let Some(cost) = estimated_request_cost(&request) {
+                                ctx.metrics().record_estimated_earnings(cost).await;
+                            }
+                            Ok(())
+                        },
+                        "Fulfill",
                     )
-                    .await;
+                    .await
+                {
+                    error!("{SERIAL_PROVER_TAG} Failed to fulfill proof: {:?}", e);
                 }
-                Err(e) => {
-                    // Check if this was a cancellation.
-                    let is_cancelled = e.is_cancelled();
-
-                    if is_cancelled {
-                        warn!(
-                            request_id = %hex::encode(&request.request_id),
-                            "{SERIAL_PROVER_TAG} Proving was aborted because request is UNEXECUTABLE"
-                        );
-                    } else {
-                        error!("{SERIAL_PROVER_TAG} Proving was aborted because: {:?}", e);
-                    }
+            }
+            Ok(Err(e)) => {
+                error!("{SERIAL_PROVER_TAG} Proof generation failed: {:?}", e);
+                ctx.metrics().record_failed_proof().await;
+
+                // Report failure to the network.
+                self.report_request_status(
+                    ctx,
+                    request.request_id.clone(),
+                    &request.request_id,
+                    "proof failure",
+                )
+                .await;
+            }
+            Err(e) if e.is_panic() => {
+                let panic_payload = e.into_panic();
+                let panic_msg = match panic_payload.downcast_ref::<&str>() {
+                    Some(s) => (*s).to_string(),
+                    None => match panic_payload.downcast_ref::<String>() {
+                        Some(s) => s.clone(),
+                        None => "Unknown panic".to_string(),
+                    },
+                };
 
-                    // Always notify network about task failure.
-                    let status_type = if is_cancelled { "cancellation" } else { "task failure" };
-                    report_request_status(
-                        ctx,
-                        request.request_id.clone(),
-                        &request.request_id,
-                        status_type,
-                    )
-                    .await;
+                error!("{SERIAL_PROVER_TAG} Proving panicked: {}", panic_msg);
+                ctx.metrics().record_panicked_proof().await;
+
+                // Attempt to mark the request as failed on the network.
+                self.report_request_status(
+                    ctx,
+                    request.request_id.clone(),
+                    &request.request_id,
+                    "panic failure",
+                )
+                .await;
+            }
+            Err(e) => {
+                // Check if this was a cancellation.
+                let is_cancelled = e.is_cancelled();
+
+                if is_cancelled {
+                    warn!(
+                        request_id = %hex::encode(&request.request_id),
+                        "{SERIAL_PROVER_TAG} Proving was aborted because request is UNEXECUTABLE"
+                    );
+                } else {
+                    error!("{SERIAL_PROVER_TAG} Proving was aborted because: {:?}", e);
+                    ctx.metrics().record_failed_proof().await;
                 }
+
+                // Always notify network about task failure.
+                let status_type = if is_cancelled { "cancellation" } else { "task failure" };
+                self.report_request_status(
+                    ctx,
+                    request.request_id.clone(),
+                    &request.request_id,
+                    status_type,
+                )
+                .await;
             }
         }
 
@@ -803,28 +1358,99 @@ impl<C: NodeContext> NodeProver<C> for SerialProver {
     }
 }
 
-/// Helper function to report a request status to the network and log the result.
-/// This handles both success and failure of the reporting itself.
-async fn report_request_status<C: NodeContext>(
-    ctx: &C,
-    request_id: Vec<u8>,
-    display_request_id: &[u8],
-    status_type: &str,
-) {
-    const SERIAL_PROVER_TAG: &str = "\x1b[33m[SerialProver]\x1b[0m";
+/// The SP1 proof mode that produces proofs of the given mode, or `None` if it is unspecified.
+fn sp1_proof_mode(mode: ProofMode) -> Option<SP1ProofMode> {
+    match mode {
+        ProofMode::Core => Some(SP1ProofMode::Core),
+        ProofMode::Compressed => Some(SP1ProofMode::Compressed),
+        ProofMode::Plonk => Some(SP1ProofMode::Plonk),
+        ProofMode::Groth16 => Some(SP1ProofMode::Groth16),
+        ProofMode::UnspecifiedProofMode => None,
+    }
+}
 
-    if let Err(fail_err) = fail_request(ctx, request_id).await {
-        error!(
-            request_id = %hex::encode(display_request_id),
-            "{SERIAL_PROVER_TAG} Failed to notify network about {} status: {:?}",
-            status_type,
-            fail_err
-        );
-    } else {
-        info!(
-            request_id = %hex::encode(display_request_id),
-            "{SERIAL_PROVER_TAG} Successfully reported {} status to network",
-            status_type
-        );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spn_rpc::NetworkChannel;
+    use tonic::transport::Endpoint;
+
+    #[test]
+    fn test_estimated_request_cost() {
+        // Assigned requests don't include the PGUs they used yet, so the gas limit is used.
+        let request = ProofRequest {
+            gas_price: Some(3),
+            gas_limit: 1_000,
+            gas_used: None,
+            base_fee: Some("500".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(estimated_request_cost(&request), Some(U256::from(3_500)));
+
+        // Once the request is fulfilled, the PGUs it used are charged instead.
+        let request = ProofRequest { gas_used: Some(100), ..request };
+        assert_eq!(estimated_request_cost(&request), Some(U256::from(800)));
+
+        // Requests without a price have no cost.
+        let request = ProofRequest { gas_price: None, ..request };
+        assert_eq!(estimated_request_cost(&request), None);
+    }
+
+    #[test]
+    fn test_proving_order_profit() {
+        let request = |id: u8, gas_price: u64, gas_limit: u64, deadline: u64| ProofRequest {
+            request_id: vec![id],
+            gas_price: Some(gas_price),
+            gas_limit,
+            gas_used: None,
+            deadline,
+            ..Default::default()
+        };
+        let mut requests = vec![
+            request(1, 1, 1_000, 10),
+            request(2, 5, 1_000, 30),
+            request(3, 2, 5_000, 20),
+            request(4, 1, 1_000, 5),
+        ];
+
+        // The requests that pay the most are proven first, even without the PGUs they used.
+        ProvingOrder::Profit.sort(&mut requests);
+        let ids: Vec<u8> = requests.iter().map(|request| request.request_id[0]).collect();
+        assert_eq!(ids, [3, 2, 4, 1]);
+
+        ProvingOrder::Deadline.sort(&mut requests);
+        let ids: Vec<u8> = requests.iter().map(|request| request.request_id[0]).collect();
+        assert_eq!(ids, [4, 1, 3, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_sends_no_bid() {
+        // The network is unreachable, so submitting a bid fails.
+        let network = NetworkChannel::new(&Endpoint::from_static("http://127.0.0.1:1")).client();
+        let ctx = SerialContext::new(network, PrivateKeySigner::random());
+        let request = ProofRequest { request_id: vec![1], deadline: 10, ..Default::default() };
+        let bidder = SerialBidder::new(U256::from(1), 1.0, Address::ZERO);
+        assert!(bidder.submit_bid(&ctx, &request, 0, U256::from(1)).await.is_err());
+
+        // In dry-run mode, the bid is only logged.
+        let bidder = bidder.with_dry_run(true);
+        bidder.submit_bid(&ctx, &request, 0, U256::from(1)).await.unwrap();
+        assert_eq!(*ctx.metrics.bids_placed.lock().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_proving_tasks_finish() {
+        let metrics = NodeMetrics::new();
+        let mut tasks = ProvingTasks::default();
+        tasks.spawn(vec![1], async { Ok(()) });
+        tasks.spawn(vec![2], async { panic!("failed to prove") });
+        assert!(tasks.contains(&[1]) && tasks.contains(&[2]));
+
+        // Both requests can be picked again once their tasks finish, and the panic is counted.
+        while let Some(result) = tasks.set.join_next_with_id().await {
+            tasks.finish(&metrics, result).await;
+        }
+        assert!(!tasks.contains(&[1]) && !tasks.contains(&[2]));
+        assert_eq!(*metrics.panics.lock().await, 1);
     }
 }
@@ -0,0 +1,45 @@
+use alloy_primitives::U256;
+
+use spn_network_types::ProofRequest;
+
+/// The context for deciding whether and how much to bid on a request.
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// The details of the request.
+    pub request: ProofRequest,
+    /// The time remaining until the request's deadline, in seconds.
+    pub remaining_time: u64,
+    /// The estimated time required to prove the request, in seconds.
+    pub required_time: u64,
+}
+
+/// A strategy for pricing bids on requests.
+///
+/// The strategy gets consulted by the [`SerialBidder`](crate::SerialBidder) for every request it
+/// considers bidding on, after the request has been checked to be provable before its deadline.
+pub trait BiddingStrategy: Send + Sync + 'static {
+    /// Returns the $PROVE price per billion proving gas units (PGUs) to bid on the request, or
+    /// `None` to skip the request.
+    fn bid(&self, request: &RequestContext) -> Option<U256>;
+}
+
+/// A [`BiddingStrategy`] that always bids the same amount.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedBidStrategy {
+    /// The $PROVE price per billion proving gas units (PGUs) to bid.
+    pub bid: U256,
+}
+
+impl FixedBidStrategy {
+    /// Create a new [`FixedBidStrategy`].
+    #[must_use]
+    pub fn new(bid: U256) -> Self {
+        Self { bid }
+    }
+}
+
+impl BiddingStrategy for FixedBidStrategy {
+    fn bid(&self, _request: &RequestContext) -> Option<U256> {
+        Some(self.bid)
+    }
+}
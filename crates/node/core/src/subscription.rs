@@ -0,0 +1,111 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use spn_network_types::{FulfillmentStatus, GetFilteredProofRequestsRequest, ProofRequest};
+use spn_rpc::fetch_owner;
+use spn_utils::time_now;
+use tonic::{codec::Streaming, Code};
+use tracing::{info, warn};
+
+use crate::{NodeContext, SP1_NETWORK_VERSION};
+
+/// The default delay before re-subscribing to the request stream after it was interrupted.
+pub const DEFAULT_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(30);
+
+/// Streams the unassigned requests from the network so that they can be bid on as soon as they
+/// appear, instead of on the next poll.
+///
+/// While the stream is down, the node falls back to polling and re-subscribes after a delay. If
+/// the network doesn't support streaming at all, the subscription is disabled for good.
+#[derive(Debug)]
+pub(crate) struct RequestSubscription {
+    stream: Option<Streaming<ProofRequest>>,
+    next_attempt: Option<Instant>,
+    resubscribe_delay: Duration,
+    supported: bool,
+}
+
+impl RequestSubscription {
+    /// Create a new [`RequestSubscription`] that re-subscribes after the given delay once the
+    /// stream is interrupted.
+    pub(crate) fn new(resubscribe_delay: Duration) -> Self {
+        Self { stream: None, next_attempt: None, resubscribe_delay, supported: true }
+    }
+
+    /// Whether the requests are currently streamed, in which case the bidder doesn't need to
+    /// poll for them.
+    pub(crate) fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Subscribe to the request stream if it isn't connected and a new attempt is due.
+    pub(crate) async fn connect<C: NodeContext>(&mut self, ctx: &C) {
+        if self.stream.is_some() ||
+            !self.supported ||
+            self.next_attempt.is_some_and(|next_attempt| Instant::now() < next_attempt)
+        {
+            return;
+        }
+
+        match subscribe(ctx).await {
+            Ok(stream) => {
+                info!("Subscribed to the request stream, bidding on new requests as they appear.");
+                self.stream = Some(stream);
+            }
+            Err(e) => {
+                let unimplemented = e
+                    .downcast_ref::<tonic::Status>()
+                    .is_some_and(|status| status.code() == Code::Unimplemented);
+                if unimplemented {
+                    warn!(
+                        "The network doesn't support streaming requests, falling back to polling."
+                    );
+                    self.supported = false;
+                } else {
+                    warn!(
+                        "Failed to subscribe to the request stream, falling back to polling: {e:?}"
+                    );
+                }
+                self.next_attempt = Some(Instant::now() + self.resubscribe_delay);
+            }
+        }
+    }
+
+    /// Wait for the next request on the stream.
+    ///
+    /// Returns `None` if the stream was interrupted, after which the node polls until it
+    /// re-subscribes. While disconnected, this never resolves.
+    pub(crate) async fn next(&mut self) -> Option<ProofRequest> {
+        let Some(stream) = self.stream.as_mut() else {
+            return std::future::pending().await;
+        };
+
+        match stream.message().await {
+            Ok(Some(request)) => return Some(request),
+            Ok(None) => warn!("The request stream was closed, falling back to polling."),
+            Err(e) => warn!("The request stream failed, falling back to polling: {e:?}"),
+        }
+        self.stream = None;
+        self.next_attempt = Some(Instant::now() + self.resubscribe_delay);
+        None
+    }
+}
+
+/// Subscribe to the unassigned requests that the node's owner hasn't bid on yet.
+async fn subscribe<C: NodeContext>(ctx: &C) -> Result<Streaming<ProofRequest>> {
+    let signer = ctx.signer().address().to_vec();
+    let owner = fetch_owner(ctx.network(), &signer).await?;
+    let stream = ctx
+        .network()
+        .clone()
+        .subscribe_proof_requests(GetFilteredProofRequestsRequest {
+            version: Some(SP1_NETWORK_VERSION.to_string()),
+            fulfillment_status: Some(FulfillmentStatus::Requested.into()),
+            minimum_deadline: Some(time_now()),
+            not_bid_by: Some(owner),
+            ..Default::default()
+        })
+        .await?
+        .into_inner();
+    Ok(stream)
+}
@@ -0,0 +1,86 @@
+use std::sync::OnceLock;
+
+use spn_network_types::ProofRequest;
+
+/// The default GPU memory needed to prove a request regardless of its size, in MiB.
+pub const DEFAULT_VRAM_BASE_MIB: u64 = 8 * 1024;
+
+/// The default GPU memory kept free for the driver and other processes, in MiB.
+pub const DEFAULT_VRAM_HEADROOM_MIB: u64 = 1024;
+
+/// Estimates the GPU memory needed to prove a request, so that the bidder can skip requests that
+/// the GPU can't fit instead of winning them and failing late with an out-of-memory error.
+///
+/// The estimate is a fixed base plus a number of bytes per cycle of the request's cycle limit,
+/// which can be calibrated from the memory use observed while proving. It is compared against the
+/// total memory of the GPU minus a headroom rather than its free memory, which drops while the
+/// node is proving other requests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VramPreflight {
+    /// The GPU memory needed regardless of the size of the request, in MiB.
+    pub base_mib: u64,
+    /// The GPU memory needed per cycle of the request, in bytes.
+    pub bytes_per_cycle: f64,
+    /// The GPU memory kept free for the driver and other processes, in MiB.
+    pub headroom_mib: u64,
+}
+
+impl VramPreflight {
+    /// Create a new [`VramPreflight`] that estimates the given number of bytes per cycle.
+    #[must_use]
+    pub fn new(bytes_per_cycle: f64) -> Self {
+        Self {
+            base_mib: DEFAULT_VRAM_BASE_MIB,
+            bytes_per_cycle,
+            headroom_mib: DEFAULT_VRAM_HEADROOM_MIB,
+        }
+    }
+
+    /// Set the GPU memory needed regardless of the size of the request, in MiB.
+    ///
+    /// Defaults to [`DEFAULT_VRAM_BASE_MIB`].
+    #[must_use]
+    pub fn with_base_mib(mut self, base_mib: u64) -> Self {
+        self.base_mib = base_mib;
+        self
+    }
+
+    /// Set the GPU memory kept free for the driver and other processes, in MiB.
+    ///
+    /// Defaults to [`DEFAULT_VRAM_HEADROOM_MIB`].
+    #[must_use]
+    pub fn with_headroom_mib(mut self, headroom_mib: u64) -> Self {
+        self.headroom_mib = headroom_mib;
+        self
+    }
+
+    /// The estimated GPU memory needed to prove the request, in MiB.
+    ///
+    /// Requests without a cycle limit are estimated from their gas limit instead.
+    #[must_use]
+    pub fn required_mib(&self, request: &ProofRequest) -> u64 {
+        const MIB: f64 = 1024.0 * 1024.0;
+
+        let cycles = if request.cycle_limit > 0 { request.cycle_limit } else { request.gas_limit };
+        let cycles_mib = (cycles as f64 * self.bytes_per_cycle / MIB).ceil() as u64;
+        self.base_mib.saturating_add(cycles_mib)
+    }
+
+    /// The GPU memory that a request can use, which is the total memory of the largest GPU minus
+    /// the headroom, in MiB, or `None` if no GPU is detected.
+    #[must_use]
+    pub fn available_mib(&self) -> Option<u64> {
+        Self::total_mib().map(|total| total.saturating_sub(self.headroom_mib))
+    }
+
+    /// The total memory of the GPU with the most memory, in MiB, or `None` if no GPU is detected.
+    ///
+    /// The GPUs are detected at most once per process, since their total memory doesn't change
+    /// and detecting them can block on `nvidia-smi`.
+    #[must_use]
+    pub fn total_mib() -> Option<u64> {
+        static TOTAL_MIB: OnceLock<Option<u64>> = OnceLock::new();
+        *TOTAL_MIB
+            .get_or_init(|| spn_utils::detect_gpus().iter().map(|gpu| gpu.memory_total_mib).max())
+    }
+}
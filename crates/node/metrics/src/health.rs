@@ -0,0 +1,131 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Router};
+use eyre::WrapErr;
+use std::{fmt, future::Future, net::SocketAddr, pin::Pin, sync::Arc};
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+/// A check of whether the service is ready to do its work, returning the reason if it isn't.
+pub type ReadinessCheck =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync>;
+
+/// [`HealthServer`] responsible for serving the liveness and readiness endpoints probed by
+/// orchestrators such as Kubernetes or systemd.
+///
+/// `/healthz` succeeds as long as the process is up, while `/readyz` runs the readiness check on
+/// every probe and fails with `503 Service Unavailable` and the reason if it doesn't pass.
+#[derive(Clone)]
+pub struct HealthServer {
+    listen_addr: SocketAddr,
+    readiness_check: ReadinessCheck,
+}
+
+impl fmt::Debug for HealthServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthServer").field("listen_addr", &self.listen_addr).finish()
+    }
+}
+
+impl HealthServer {
+    /// Create a new [`HealthServer`] listening on the given address, which is ready whenever the
+    /// given check passes.
+    pub fn new<F, Fut>(listen_addr: SocketAddr, readiness_check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let readiness_check: ReadinessCheck = Arc::new(move || Box::pin(readiness_check()));
+        Self { listen_addr, readiness_check }
+    }
+
+    /// Serves the health endpoints until the shutdown signal is received.
+    ///
+    /// Like [`MetricServer::serve`](crate::server::MetricServer::serve), the server also shuts
+    /// down if the sender of the shutdown signal is dropped.
+    pub async fn serve(self, mut shutdown_signal: broadcast::Receiver<()>) -> eyre::Result<()> {
+        let listen_addr = self.listen_addr;
+        let app = Router::new()
+            .route("/healthz", get(Self::health_handler))
+            .route("/readyz", get(Self::readiness_handler))
+            .with_state(self);
+
+        let listener = tokio::net::TcpListener::bind(listen_addr)
+            .await
+            .wrap_err_with(|| format!("could not bind health server to {listen_addr}"))?;
+        info!("health server listening on {}", listener.local_addr()?);
+
+        if let Err(err) = axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                let _ = shutdown_signal.recv().await;
+                info!("shutdown signal received for health server");
+            })
+            .await
+        {
+            error!(%err, "health server error");
+        }
+
+        Ok(())
+    }
+
+    /// Handler for the liveness endpoint.
+    async fn health_handler() -> impl IntoResponse {
+        (StatusCode::OK, "ok")
+    }
+
+    /// Handler for the readiness endpoint.
+    async fn readiness_handler(State(server): State<Self>) -> impl IntoResponse {
+        match (server.readiness_check)().await {
+            Ok(()) => (StatusCode::OK, "ready".to_string()),
+            Err(reason) => {
+                warn!(%reason, "readiness check failed");
+                (StatusCode::SERVICE_UNAVAILABLE, reason)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+    use std::{
+        net::TcpListener,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    #[tokio::test]
+    async fn test_health_endpoints() {
+        let listen_addr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let ready = Arc::new(AtomicBool::new(false));
+        let check = ready.clone();
+        let server = HealthServer::new(listen_addr, move || {
+            let ready = check.load(Ordering::SeqCst);
+            async move {
+                if ready {
+                    Ok(())
+                } else {
+                    Err("not funded".to_string())
+                }
+            }
+        });
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let server_handle = tokio::spawn(server.serve(shutdown_rx));
+
+        // Give the server a moment to start
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let client = Client::new();
+        let get = |path: &str| client.get(format!("http://{listen_addr}{path}")).send();
+
+        // The node is alive but not ready until the check passes.
+        assert_eq!(get("/healthz").await.unwrap().status(), StatusCode::OK.as_u16());
+        let response = get("/readyz").await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE.as_u16());
+        assert_eq!(response.text().await.unwrap(), "not funded");
+
+        ready.store(true, Ordering::SeqCst);
+        assert_eq!(get("/readyz").await.unwrap().status(), StatusCode::OK.as_u16());
+
+        shutdown_tx.send(()).unwrap();
+        server_handle.await.unwrap().unwrap();
+    }
+}
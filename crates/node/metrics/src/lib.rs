@@ -1,5 +1,9 @@
+/// The liveness and readiness endpoints probed by orchestrators.
+pub mod health;
 /// The metrics hooks for prometheus.
 pub mod hooks;
+/// The push gateway exporter for short-lived processes.
+pub mod push;
 pub mod recorder;
 /// The metric server serving the metrics.
 pub mod server;
@@ -0,0 +1,144 @@
+use crate::{hooks::Hooks, recorder::get_or_init_prometheus, version::VersionInfo};
+use eyre::WrapErr;
+use metrics_process::Collector;
+use std::time::Duration;
+use tokio::{sync::broadcast, time::MissedTickBehavior};
+use tracing::{debug, info, warn};
+
+/// The default interval at which metrics are pushed to the push gateway.
+pub const DEFAULT_PUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Configuration for the [`MetricPusher`].
+#[derive(Debug, Clone)]
+pub struct PushGatewayConfig {
+    gateway_url: String,
+    interval: Duration,
+    version_info: VersionInfo,
+    hooks: Hooks,
+    service_name: String,
+}
+
+impl PushGatewayConfig {
+    /// Create a new [`PushGatewayConfig`] that pushes to the push gateway at the given URL, e.g.
+    /// `http://localhost:9091`.
+    ///
+    /// The metrics are grouped under a job named after the service.
+    pub fn new(gateway_url: String, version_info: VersionInfo, service_name: String) -> Self {
+        let hooks = Hooks::new();
+        Self { gateway_url, interval: DEFAULT_PUSH_INTERVAL, version_info, hooks, service_name }
+    }
+
+    /// Set the interval at which metrics are pushed.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The URL that the metrics of this service are pushed to.
+    fn job_url(&self) -> String {
+        format!("{}/metrics/job/{}", self.gateway_url.trim_end_matches('/'), self.service_name)
+    }
+}
+
+/// [`MetricPusher`] responsible for pushing metrics to a Prometheus push gateway.
+///
+/// Unlike the [`MetricServer`](crate::server::MetricServer), which waits to be scraped, this
+/// captures the metrics of short-lived processes, such as a calibration run, that may exit before
+/// a scrape happens. The metrics are rendered by the same Prometheus recorder, so the two can be
+/// used together.
+#[derive(Debug, Clone)]
+pub struct MetricPusher {
+    config: PushGatewayConfig,
+    client: reqwest::Client,
+}
+
+impl MetricPusher {
+    /// Create a new [`MetricPusher`] with the given configuration.
+    pub fn new(config: PushGatewayConfig) -> Self {
+        Self { config, client: reqwest::Client::new() }
+    }
+
+    /// Pushes the metrics on an interval until the shutdown signal is received, and once more
+    /// before returning so that the final values aren't lost.
+    ///
+    /// Failed pushes on the interval are logged and retried on the next tick, while a failure of
+    /// the final push is returned.
+    pub async fn run(self, mut shutdown_signal: broadcast::Receiver<()>) -> eyre::Result<()> {
+        // Initialize the prometheus recorder and describe the metrics.
+        get_or_init_prometheus(&self.config.service_name);
+        Collector::default().describe();
+        self.config.version_info.register_version_metrics();
+
+        let mut interval = tokio::time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        info!("pushing metrics to {} every {:?}", self.config.job_url(), self.config.interval);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(err) = self.push().await {
+                        warn!(%err, "failed to push metrics");
+                    }
+                }
+                // A closed channel means the sender was dropped, which also signals shutdown.
+                _ = shutdown_signal.recv() => break,
+            }
+        }
+
+        info!("received shutdown signal, pushing final metrics...");
+        self.push().await.wrap_err("could not push final metrics")
+    }
+
+    /// Pushes the current metrics to the push gateway, replacing the ones previously pushed by
+    /// this service.
+    pub async fn push(&self) -> eyre::Result<()> {
+        // Execute all hooks
+        self.config.hooks.iter().for_each(|hook| hook());
+
+        // Render the metrics with the prometheus recorder
+        let body = get_or_init_prometheus(&self.config.service_name).render();
+        self.client.put(self.config.job_url()).body(body).send().await?.error_for_status()?;
+        debug!("pushed metrics to {}", self.config.job_url());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, routing::put, Router};
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_push_gateway() {
+        let version_info = VersionInfo::test();
+
+        // Start a push gateway that records the pushed payloads.
+        let pushes = Arc::new(Mutex::new(Vec::new()));
+        let app = Router::new()
+            .route(
+                "/metrics/job/test",
+                put(|State(pushes): State<Arc<Mutex<Vec<String>>>>, body: String| async move {
+                    pushes.lock().unwrap().push(body);
+                }),
+            )
+            .with_state(pushes.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let gateway_url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        // Push on an interval, then once more on shutdown.
+        let config = PushGatewayConfig::new(gateway_url, version_info, "test".to_string())
+            .with_interval(Duration::from_millis(50));
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let pusher = tokio::spawn(MetricPusher::new(config).run(shutdown_rx));
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        shutdown_tx.send(()).unwrap();
+        pusher.await.unwrap().unwrap();
+
+        let pushes = pushes.lock().unwrap();
+        assert!(pushes.len() >= 2, "expected pushes on the interval and on shutdown");
+        assert!(pushes.last().unwrap().contains("process_cpu_seconds_total"));
+    }
+}
@@ -113,13 +113,17 @@ impl MetricServer {
         self.config.version_info.register_version_metrics();
         describe_io_stats();
 
-        // Listen for the external shutdown signal
+        // Listen for the external shutdown signal. If the sender is dropped without sending, the
+        // owner has gone away, so shut down as well rather than keep the socket open.
         tokio::spawn(async move {
-            if shutdown_signal.recv().await.is_ok() {
-                info!("received external shutdown signal, initiating graceful shutdown...");
-                if internal_shutdown_tx.send(()).is_err() {
-                    warn!("failed to send shutdown signal to metrics server");
+            match shutdown_signal.recv().await {
+                Ok(()) => {
+                    info!("received external shutdown signal, initiating graceful shutdown...");
                 }
+                Err(_) => info!("shutdown signal sender dropped, initiating graceful shutdown..."),
+            }
+            if internal_shutdown_tx.send(()).is_err() {
+                warn!("failed to send shutdown signal to metrics server");
             }
         });
 
@@ -142,19 +146,23 @@ impl MetricServer {
             .route("/metrics", get(Self::metrics_handler))
             .with_state(self.clone());
 
+        // Bind before spawning, so that an address that is already in use, e.g. by another prover
+        // on the same host, is reported to the caller.
         let listen_addr = self.config.listen_addr;
-        info!("metrics server listening on {}", listen_addr);
+        let listener = tokio::net::TcpListener::bind(listen_addr)
+            .await
+            .wrap_err_with(|| format!("could not bind metrics server to {listen_addr}"))?;
+        info!("metrics server listening on {}", listener.local_addr()?);
 
         // Spawn a task to accept connections.
         Ok(spawn(async move {
             // Use axum's built-in server functionality with simplified shutdown
-            if let Err(err) =
-                axum::serve(tokio::net::TcpListener::bind(listen_addr).await.unwrap(), app)
-                    .with_graceful_shutdown(async {
-                        let _ = shutdown_rx.await;
-                        info!("shutdown signal received for metrics server");
-                    })
-                    .await
+            if let Err(err) = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                    info!("shutdown signal received for metrics server");
+                })
+                .await
             {
                 error!(%err, "metrics server error");
             }
@@ -207,14 +215,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_metrics_endpoint() {
-        let version_info = VersionInfo {
-            version: "test".to_string(),
-            build_timestamp: "test".to_string(),
-            cargo_features: "test".to_string(),
-            git_sha: "test".to_string(),
-            target_triple: "test".to_string(),
-            build_profile: "test".to_string(),
-        };
+        let version_info = VersionInfo::test();
 
         let listen_addr = get_random_available_addr();
         let config = MetricServerConfig::new(listen_addr, version_info, "test".to_string());
@@ -241,4 +242,27 @@ mod tests {
         // Cleanup: abort the server task
         server_handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_metrics_server_shutdown() {
+        let version_info = VersionInfo::test();
+
+        let listen_addr = get_random_available_addr();
+        let config = MetricServerConfig::new(listen_addr, version_info, "test".to_string());
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let server = MetricServer::new(config.clone().with_ready_signal(ready_tx));
+        let server_handle = tokio::spawn(async move { server.serve(shutdown_rx).await });
+        ready_rx.await.unwrap();
+
+        // A second server on the same address fails to start instead of panicking.
+        let (_other_shutdown_tx, other_shutdown_rx) = broadcast::channel(1);
+        let err = MetricServer::new(config).serve(other_shutdown_rx).await.unwrap_err();
+        assert!(format!("{err:#}").contains("could not bind metrics server"), "{err:#}");
+
+        // The server stops and releases its address once the shutdown signal is sent.
+        shutdown_tx.send(()).unwrap();
+        server_handle.await.unwrap().unwrap();
+        TcpListener::bind(listen_addr).unwrap();
+    }
 }
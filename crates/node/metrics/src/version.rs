@@ -35,3 +35,19 @@ impl VersionInfo {
         _gauge.set(1.0);
     }
 }
+
+#[cfg(test)]
+impl VersionInfo {
+    /// Version information with every field set to `test`, for the tests of the exporters.
+    pub(crate) fn test() -> Self {
+        let test = || "test".to_string();
+        Self {
+            version: test(),
+            build_timestamp: test(),
+            cargo_features: test(),
+            git_sha: test(),
+            target_triple: test(),
+            build_profile: test(),
+        }
+    }
+}
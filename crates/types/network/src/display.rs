@@ -0,0 +1,116 @@
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{ExecutionStatus, FulfillmentStrategy, ProofMode, TransactionVariant};
+
+/// An error returned when parsing one of the network enums from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid {kind}: {value}")]
+pub struct ParseEnumError {
+    /// The name of the enum that was being parsed.
+    pub kind: &'static str,
+    /// The string that failed to parse.
+    pub value: String,
+}
+
+/// Implements [`fmt::Display`] and [`FromStr`] for a protobuf enum using human-readable names.
+///
+/// Parsing is case-insensitive and also accepts the protobuf names, e.g. both `groth16` and
+/// `GROTH16` parse to [`ProofMode::Groth16`].
+macro_rules! impl_display {
+    ($ty:ident, $kind:literal, { $($variant:ident => $name:literal),+ $(,)? }) => {
+        impl $ty {
+            /// Returns the human-readable name of the variant.
+            #[must_use]
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)+
+                }
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = ParseEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.to_ascii_lowercase().as_str() {
+                    $($name => Ok(Self::$variant),)+
+                    _ => Self::from_str_name(s)
+                        .ok_or_else(|| ParseEnumError { kind: $kind, value: s.to_string() }),
+                }
+            }
+        }
+    };
+}
+
+impl_display!(ProofMode, "proof mode", {
+    UnspecifiedProofMode => "unspecified",
+    Core => "core",
+    Compressed => "compressed",
+    Plonk => "plonk",
+    Groth16 => "groth16",
+});
+
+impl_display!(FulfillmentStrategy, "fulfillment strategy", {
+    UnspecifiedFulfillmentStrategy => "unspecified",
+    Hosted => "hosted",
+    Reserved => "reserved",
+    Auction => "auction",
+});
+
+impl_display!(ExecutionStatus, "execution status", {
+    UnspecifiedExecutionStatus => "unspecified",
+    Unexecuted => "unexecuted",
+    Executed => "executed",
+    Unexecutable => "unexecutable",
+    ValidationFailed => "validation_failed",
+});
+
+impl_display!(TransactionVariant, "transaction variant", {
+    RequestVariant => "request",
+    BidVariant => "bid",
+    SettleVariant => "settle",
+    ExecuteVariant => "execute",
+    FulfillVariant => "fulfill",
+    DelegateVariant => "delegate",
+    TransferVariant => "transfer",
+    WithdrawVariant => "withdraw",
+    AddDelegateVariant => "add_delegate",
+    RemoveDelegateVariant => "remove_delegate",
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_round_trip() {
+        assert_eq!(ProofMode::Groth16.to_string(), "groth16");
+        assert_eq!(FulfillmentStrategy::Auction.to_string(), "auction");
+        assert_eq!(ExecutionStatus::ValidationFailed.to_string(), "validation_failed");
+        assert_eq!(TransactionVariant::BidVariant.to_string(), "bid");
+
+        for mode in [ProofMode::Core, ProofMode::Compressed, ProofMode::Plonk, ProofMode::Groth16] {
+            assert_eq!(mode.to_string().parse::<ProofMode>(), Ok(mode));
+        }
+
+        // Parsing is case-insensitive and accepts the protobuf names.
+        assert_eq!("Groth16".parse::<ProofMode>(), Ok(ProofMode::Groth16));
+        assert_eq!("HOSTED".parse::<FulfillmentStrategy>(), Ok(FulfillmentStrategy::Hosted));
+        assert_eq!(
+            "TRANSFER_VARIANT".parse::<TransactionVariant>(),
+            Ok(TransactionVariant::TransferVariant)
+        );
+        assert_eq!(
+            "stark".parse::<ProofMode>(),
+            Err(ParseEnumError { kind: "proof mode", value: "stark".to_string() })
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use prost::Message;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A protobuf message that can be converted to and from JSON.
+///
+/// Binary protobuf stays the wire format, while JSON is a convenience for tools that print or
+/// edit messages, like a CLI that shows a [`crate::RequestProofRequest`]. The JSON follows the
+/// serde derives of the generated types, so bytes are arrays of numbers, enums are their numeric
+/// values, and unset optional fields are `null`.
+pub trait ProtoJson: Message + Serialize + DeserializeOwned + Sized {
+    /// Serializes the message to pretty-printed JSON.
+    fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserializes a message from JSON produced by [`ProtoJson::to_json`].
+    fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+impl<T: Message + Serialize + DeserializeOwned> ProtoJson for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RequestProofRequest, RequestProofRequestBody};
+
+    #[test]
+    fn test_json_round_trip() {
+        let request = RequestProofRequest {
+            format: 1,
+            signature: vec![1, 2, 3],
+            body: Some(RequestProofRequestBody {
+                stdin_uri: "s3://bucket/stdins/artifact".to_string(),
+                whitelist: vec![vec![2; 20]],
+                base_fee: "100".to_string(),
+                ..Default::default()
+            }),
+        };
+
+        let json = request.to_json().unwrap();
+        assert!(json.contains("\"stdin_uri\": \"s3://bucket/stdins/artifact\""));
+        assert!(json.contains("\"base_fee\": \"100\""));
+        assert_eq!(RequestProofRequest::from_json(&json).unwrap(), request);
+
+        // The binary encoding is unaffected.
+        let decoded = RequestProofRequest::decode(request.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, request);
+    }
+}
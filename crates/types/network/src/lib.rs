@@ -9,22 +9,85 @@ pub use network::*;
 mod types;
 pub use types::*;
 
-use alloy_primitives::Keccak256;
+mod display;
+pub use display::*;
+
+mod json;
+pub use json::*;
+
+mod validation;
+pub use validation::*;
+
 #[cfg(feature = "network")]
-use alloy_primitives::Signature;
+use alloy_primitives::{keccak256, Signature, SignatureError};
+use alloy_primitives::{Address, Keccak256, B256};
 #[cfg(feature = "network")]
 use alloy_signer::SignerSync;
 use prost::Message;
 
 #[cfg(feature = "network")]
 pub trait Signable: Message {
-    fn sign<S: SignerSync>(&self, signer: &S) -> Signature;
+    fn sign<S: SignerSync>(&self, signer: &S) -> alloy_signer::Result<Signature>;
 }
 
 #[cfg(feature = "network")]
 impl<T: Message> Signable for T {
-    fn sign<S: SignerSync>(&self, signer: &S) -> Signature {
-        signer.sign_message_sync(&self.encode_to_vec()).unwrap()
+    fn sign<S: SignerSync>(&self, signer: &S) -> alloy_signer::Result<Signature> {
+        signer.sign_message_sync(&self.encode_to_vec())
+    }
+}
+
+/// The EIP-712 type of the struct that [`SignableTyped`] messages are signed as.
+#[cfg(feature = "network")]
+const SPN_MESSAGE_TYPE: &str = "SpnMessage(bytes body)";
+
+/// A message that can be signed as EIP-712 typed data.
+///
+/// The message is signed as an `SpnMessage(bytes body)` struct holding its protobuf encoding,
+/// bound to a domain separator like `spn_utils::SPN_MAINNET_V1_DOMAIN`. Unlike [`Signable`], this
+/// lets wallets show which domain they are signing for instead of asking for a blind personal
+/// sign, and the signature can't be replayed on another domain.
+#[cfg(feature = "network")]
+pub trait SignableTyped: Message {
+    fn eip712_signing_hash(&self, domain: B256) -> B256;
+
+    fn sign_typed<S: SignerSync>(
+        &self,
+        signer: &S,
+        domain: B256,
+    ) -> alloy_signer::Result<Signature>;
+
+    /// Recovers the address that signed the message with [`SignableTyped::sign_typed`] for the
+    /// given domain.
+    ///
+    /// A signature for another message or domain recovers to an unrelated address, so callers
+    /// must compare the result with the expected signer.
+    fn recover_typed(&self, signature: &Signature, domain: B256)
+        -> Result<Address, SignatureError>;
+}
+
+#[cfg(feature = "network")]
+impl<T: Message> SignableTyped for T {
+    fn eip712_signing_hash(&self, domain: B256) -> B256 {
+        let type_hash = keccak256(SPN_MESSAGE_TYPE);
+        let struct_hash = keccak256([type_hash, keccak256(self.encode_to_vec())].concat());
+        keccak256([&[0x19, 0x01], domain.as_slice(), struct_hash.as_slice()].concat())
+    }
+
+    fn sign_typed<S: SignerSync>(
+        &self,
+        signer: &S,
+        domain: B256,
+    ) -> alloy_signer::Result<Signature> {
+        signer.sign_hash_sync(&self.eip712_signing_hash(domain))
+    }
+
+    fn recover_typed(
+        &self,
+        signature: &Signature,
+        domain: B256,
+    ) -> Result<Address, SignatureError> {
+        signature.recover_address_from_prehash(&self.eip712_signing_hash(domain))
     }
 }
 
@@ -43,3 +106,40 @@ impl<T: Message> HashableWithSender for T {
         Ok(hasher.finalize().into())
     }
 }
+
+/// Computes the id of a proof request from its body and the address of its requester.
+///
+/// This is the keccak256 hash of the requester address followed by the encoded body, which is the
+/// id that the network and the vApp use to refer to the request.
+pub fn compute_request_id(
+    body: &RequestProofRequestBody,
+    requester: &Address,
+) -> Result<B256, prost::EncodeError> {
+    body.hash_with_signer(requester.as_slice()).map(B256::from)
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use alloy_signer_local::PrivateKeySigner;
+
+    use super::*;
+
+    #[test]
+    fn test_sign_typed_round_trip() {
+        let signer = PrivateKeySigner::random();
+        let message = GetNonceRequest { address: signer.address().to_vec() };
+        let domain = B256::repeat_byte(1);
+
+        // The signature recovers to the signer for the domain it was made for.
+        let signature = message.sign_typed(&signer, domain).unwrap();
+        assert_eq!(message.recover_typed(&signature, domain).unwrap(), signer.address());
+
+        // It doesn't for another domain or message.
+        assert_ne!(
+            message.recover_typed(&signature, B256::repeat_byte(2)).unwrap(),
+            signer.address()
+        );
+        let other = GetNonceRequest { address: Address::ZERO.to_vec() };
+        assert_ne!(other.recover_typed(&signature, domain).unwrap(), signer.address());
+    }
+}
@@ -2074,6 +2074,9 @@ pub struct Delegation {
     /// The date the delegation was created.
     #[prost(uint64, tag = "3")]
     pub created_at: u64,
+    /// The additional signers of the prover, authorized alongside the delegate.
+    #[prost(bytes = "vec", repeated, tag = "4")]
+    pub additional_delegates: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2173,6 +2176,8 @@ pub enum TransactionVariant {
     DelegateVariant = 5,
     TransferVariant = 6,
     WithdrawVariant = 7,
+    AddDelegateVariant = 8,
+    RemoveDelegateVariant = 9,
 }
 impl TransactionVariant {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2189,6 +2194,8 @@ impl TransactionVariant {
             Self::DelegateVariant => "DELEGATE_VARIANT",
             Self::TransferVariant => "TRANSFER_VARIANT",
             Self::WithdrawVariant => "WITHDRAW_VARIANT",
+            Self::AddDelegateVariant => "ADD_DELEGATE_VARIANT",
+            Self::RemoveDelegateVariant => "REMOVE_DELEGATE_VARIANT",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2202,6 +2209,8 @@ impl TransactionVariant {
             "DELEGATE_VARIANT" => Some(Self::DelegateVariant),
             "TRANSFER_VARIANT" => Some(Self::TransferVariant),
             "WITHDRAW_VARIANT" => Some(Self::WithdrawVariant),
+            "ADD_DELEGATE_VARIANT" => Some(Self::AddDelegateVariant),
+            "REMOVE_DELEGATE_VARIANT" => Some(Self::RemoveDelegateVariant),
             _ => None,
         }
     }
@@ -0,0 +1,214 @@
+use alloy_primitives::U256;
+use thiserror::Error;
+
+use crate::{FulfillmentStrategy, ProofMode, RequestProofRequestBody, TransactionVariant};
+
+/// An error returned when a request body doesn't satisfy the invariants checked by the network.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    /// A field that must be set is empty or zero.
+    #[error("{field} must be set")]
+    Missing {
+        /// The name of the field.
+        field: &'static str,
+    },
+    /// A bytes field doesn't have the expected length.
+    #[error("{field} must be {expected} bytes, got {actual}")]
+    InvalidLength {
+        /// The name of the field.
+        field: &'static str,
+        /// The expected length in bytes.
+        expected: usize,
+        /// The actual length in bytes.
+        actual: usize,
+    },
+    /// An amount field isn't a valid decimal U256.
+    #[error("{field} is not a valid amount: {value:?}")]
+    InvalidAmount {
+        /// The name of the field.
+        field: &'static str,
+        /// The value of the field.
+        value: String,
+    },
+    /// An enum field has an unknown or unspecified value.
+    #[error("{field} has an invalid value: {value}")]
+    InvalidEnum {
+        /// The name of the field.
+        field: &'static str,
+        /// The value of the field.
+        value: i32,
+    },
+    /// The gas limit is lower than the cycle limit, so the request can never be fulfilled.
+    #[error("gas_limit ({gas_limit}) must not be lower than cycle_limit ({cycle_limit})")]
+    GasLimitBelowCycleLimit {
+        /// The gas limit of the request.
+        gas_limit: u64,
+        /// The cycle limit of the request.
+        cycle_limit: u64,
+    },
+}
+
+/// The length of an address in bytes.
+const ADDRESS_LENGTH: usize = 20;
+
+/// The length of a hash in bytes.
+const HASH_LENGTH: usize = 32;
+
+impl RequestProofRequestBody {
+    /// Checks the invariants of the request body that the network would otherwise only reject
+    /// after it is signed and sent.
+    ///
+    /// This checks that the verifying key hash, domain, and addresses have the right lengths, that
+    /// the deadline, cycle limit, and stdin are set, that the gas limit is at least the cycle
+    /// limit, that the fees are valid amounts, and that the enums are known. Clients should call it
+    /// before signing the request.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_length("vk_hash", &self.vk_hash, HASH_LENGTH)?;
+        check_length("domain", &self.domain, HASH_LENGTH)?;
+        if let Some(public_values_hash) = &self.public_values_hash {
+            check_length("public_values_hash", public_values_hash, HASH_LENGTH)?;
+        }
+        for (field, address) in [
+            ("auctioneer", &self.auctioneer),
+            ("executor", &self.executor),
+            ("verifier", &self.verifier),
+            ("treasury", &self.treasury),
+        ] {
+            check_length(field, address, ADDRESS_LENGTH)?;
+        }
+        for prover in &self.whitelist {
+            check_length("whitelist", prover, ADDRESS_LENGTH)?;
+        }
+
+        for (field, is_set) in [
+            ("version", !self.version.is_empty()),
+            ("stdin_uri", !self.stdin_uri.is_empty()),
+            ("deadline", self.deadline != 0),
+            ("cycle_limit", self.cycle_limit != 0),
+        ] {
+            if !is_set {
+                return Err(ValidationError::Missing { field });
+            }
+        }
+        if self.gas_limit < self.cycle_limit {
+            return Err(ValidationError::GasLimitBelowCycleLimit {
+                gas_limit: self.gas_limit,
+                cycle_limit: self.cycle_limit,
+            });
+        }
+
+        check_amount("base_fee", &self.base_fee)?;
+        check_amount("max_price_per_pgu", &self.max_price_per_pgu)?;
+
+        if !matches!(ProofMode::try_from(self.mode), Ok(mode) if mode != ProofMode::UnspecifiedProofMode)
+        {
+            return Err(ValidationError::InvalidEnum { field: "mode", value: self.mode });
+        }
+        if !matches!(
+            FulfillmentStrategy::try_from(self.strategy),
+            Ok(strategy) if strategy != FulfillmentStrategy::UnspecifiedFulfillmentStrategy
+        ) {
+            return Err(ValidationError::InvalidEnum { field: "strategy", value: self.strategy });
+        }
+        if TransactionVariant::try_from(self.variant) != Ok(TransactionVariant::RequestVariant) {
+            return Err(ValidationError::InvalidEnum { field: "variant", value: self.variant });
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that a bytes field has the expected length.
+fn check_length(field: &'static str, bytes: &[u8], expected: usize) -> Result<(), ValidationError> {
+    if bytes.len() != expected {
+        return Err(ValidationError::InvalidLength { field, expected, actual: bytes.len() });
+    }
+    Ok(())
+}
+
+/// Checks that an amount field is a valid decimal U256.
+fn check_amount(field: &'static str, value: &str) -> Result<(), ValidationError> {
+    value
+        .parse::<U256>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::InvalidAmount { field, value: value.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_body() -> RequestProofRequestBody {
+        RequestProofRequestBody {
+            nonce: 1,
+            vk_hash: vec![1; 32],
+            version: "sp1-v6.0.0".to_string(),
+            mode: ProofMode::Compressed as i32,
+            strategy: FulfillmentStrategy::Auction as i32,
+            stdin_uri: "s3://bucket/stdins/artifact".to_string(),
+            deadline: 1_700_000_000,
+            cycle_limit: 1_000,
+            gas_limit: 10_000,
+            min_auction_period: 0,
+            whitelist: vec![vec![2; 20]],
+            domain: vec![3; 32],
+            auctioneer: vec![4; 20],
+            executor: vec![5; 20],
+            verifier: vec![6; 20],
+            public_values_hash: None,
+            base_fee: "0".to_string(),
+            max_price_per_pgu: "100000".to_string(),
+            variant: TransactionVariant::RequestVariant as i32,
+            treasury: vec![7; 20],
+            stdin_private: false,
+        }
+    }
+
+    #[test]
+    fn test_validate_request_body() {
+        assert_eq!(request_body().validate(), Ok(()));
+
+        // A zero gas limit isn't unlimited, since no proof could use fewer PGUs than that.
+        let body = RequestProofRequestBody { gas_limit: 0, ..request_body() };
+        assert_eq!(
+            body.validate(),
+            Err(ValidationError::GasLimitBelowCycleLimit { gas_limit: 0, cycle_limit: 1_000 })
+        );
+
+        let body = RequestProofRequestBody { vk_hash: vec![], ..request_body() };
+        assert_eq!(
+            body.validate(),
+            Err(ValidationError::InvalidLength { field: "vk_hash", expected: 32, actual: 0 })
+        );
+
+        let body = RequestProofRequestBody { deadline: 0, ..request_body() };
+        assert_eq!(body.validate(), Err(ValidationError::Missing { field: "deadline" }));
+
+        let body = RequestProofRequestBody { gas_limit: 999, ..request_body() };
+        assert_eq!(
+            body.validate(),
+            Err(ValidationError::GasLimitBelowCycleLimit { gas_limit: 999, cycle_limit: 1_000 })
+        );
+
+        let body = RequestProofRequestBody { base_fee: "-1".to_string(), ..request_body() };
+        assert_eq!(
+            body.validate(),
+            Err(ValidationError::InvalidAmount { field: "base_fee", value: "-1".to_string() })
+        );
+
+        let body = RequestProofRequestBody { treasury: vec![7; 19], ..request_body() };
+        assert!(matches!(
+            body.validate(),
+            Err(ValidationError::InvalidLength { field: "treasury", .. })
+        ));
+
+        let body = RequestProofRequestBody {
+            variant: TransactionVariant::BidVariant as i32,
+            ..request_body()
+        };
+        assert!(matches!(
+            body.validate(),
+            Err(ValidationError::InvalidEnum { field: "variant", .. })
+        ));
+    }
+}
@@ -0,0 +1,30 @@
+//! Benchmarks computing the root of a [`MerkleStorage`] from scratch.
+//!
+//! Run with `cargo bench -p spn-vapp-core --bench merkle`, and again with `--features rayon` to
+//! compare the serial and parallel hashing of the layers.
+
+use alloy_primitives::{keccak256, Address, U256};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use spn_vapp_core::merkle::MerkleStorage;
+
+/// The numbers of leaves in the benchmarked trees.
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+fn bench_root(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merkle_root");
+    group.sample_size(10);
+    for size in SIZES {
+        let leaves = (0..size).map(|i| {
+            let word = keccak256(i.to_be_bytes());
+            (Address::from_word(word), U256::from(i))
+        });
+        let tree = MerkleStorage::<Address, U256>::from_leaves(leaves);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &tree, |b, tree| {
+            b.iter(|| tree.root());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_root);
+criterion_main!(benches);
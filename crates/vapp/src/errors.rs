@@ -193,4 +193,16 @@ pub enum VAppPanic {
 
     #[error("Arithmetic overflow")]
     ArithmeticOverflow,
+
+    #[error("Arithmetic underflow")]
+    ArithmeticUnderflow,
+
+    #[error("Division by zero")]
+    DivisionByZero,
+
+    #[error("Balance overflow: cannot add {amount} to {balance}")]
+    BalanceOverflow { balance: U256, amount: U256 },
+
+    #[error("Balance underflow: cannot deduct {amount} from {balance}")]
+    BalanceUnderflow { balance: U256, amount: U256 },
 }
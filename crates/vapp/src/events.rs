@@ -0,0 +1,156 @@
+//! Events.
+//!
+//! This module contains the types for the events that describe the state changes made by the
+//! vApp, so that they can be indexed without re-deriving them from the transactions.
+
+use alloy_primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::RequestId;
+
+/// `VApp` Events describe the state changes made by an applied
+/// [`crate::transactions::VAppTransaction`], in the order they were made.
+///
+/// Unlike [`crate::receipts::VAppReceipt`]s, which are only produced for the transactions that
+/// require follow up transactions on the settlement contract, every applied transaction emits
+/// events. Reverted transactions don't emit any, as they only retire their transaction ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VAppEvent {
+    /// The balance of an account was increased.
+    Credited {
+        /// The account that was credited.
+        account: Address,
+        /// The amount that was credited.
+        amount: U256,
+        /// Why the account was credited.
+        reason: BalanceChangeReason,
+    },
+    /// The balance of an account was decreased.
+    Debited {
+        /// The account that was debited.
+        account: Address,
+        /// The amount that was debited.
+        amount: U256,
+        /// Why the account was debited.
+        reason: BalanceChangeReason,
+    },
+    /// A prover was created, with its owner as its signer.
+    ProverCreated {
+        /// The address of the prover.
+        prover: Address,
+        /// The owner of the prover.
+        owner: Address,
+        /// The fee of the prover's stakers, in basis points.
+        staker_fee_bips: U256,
+    },
+    /// The owner of a prover delegated its signer.
+    SignerDelegated {
+        /// The address of the prover.
+        prover: Address,
+        /// The new signer of the prover.
+        signer: Address,
+    },
+    /// The owner of a prover authorized an additional signer.
+    SignerAdded {
+        /// The address of the prover.
+        prover: Address,
+        /// The added signer of the prover.
+        signer: Address,
+    },
+    /// The owner of a prover revoked one of its additional signers.
+    SignerRemoved {
+        /// The address of the prover.
+        prover: Address,
+        /// The removed signer of the prover.
+        signer: Address,
+    },
+    /// A request was fulfilled and its cost was paid to the prover.
+    RequestFulfilled {
+        /// The ID of the request.
+        request_id: RequestId,
+        /// The account that made the request.
+        requester: Address,
+        /// The prover that fulfilled the request.
+        prover: Address,
+        /// The PGUs used by the request.
+        pgus: U256,
+        /// The cost paid by the requester.
+        cost: U256,
+    },
+    /// A request couldn't be executed and its requester was punished.
+    RequestPunished {
+        /// The ID of the request.
+        request_id: RequestId,
+        /// The account that made the request.
+        requester: Address,
+        /// The punishment paid by the requester to the treasury.
+        punishment: U256,
+    },
+}
+
+/// The reason for a [`VAppEvent::Credited`] or [`VAppEvent::Debited`] balance change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BalanceChangeReason {
+    /// A deposit from the settlement contract.
+    Deposit,
+    /// A transfer between accounts.
+    Transfer,
+    /// A withdrawal to the settlement contract.
+    Withdraw,
+    /// The fee paid to the auctioneer for including a transaction.
+    AuctioneerFee,
+    /// The cost of a request paid by its requester.
+    RequesterFee,
+    /// The share of the cost of a request paid to the treasury.
+    ProtocolFee,
+    /// The share of the cost of a request paid to the stakers of the prover.
+    StakerReward,
+    /// The share of the cost of a request paid to the owner of the prover.
+    OwnerReward,
+    /// The punishment of a requester for an unexecutable request.
+    Punishment,
+}
+
+/// The fees collected by the vApp, broken down by category.
+///
+/// The breakdown is accumulated from the [`VAppEvent`]s of applied transactions, so that operators
+/// can reconcile protocol revenue with the balance of the treasury.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    /// The share of the cost of requests paid to the treasury.
+    pub protocol_fees: U256,
+    /// The punishments of requesters for unexecutable requests, paid to the treasury.
+    pub punishments: U256,
+    /// The fees paid to auctioneers for including transactions.
+    pub auctioneer_fees: U256,
+    /// The share of the cost of requests paid to the stakers of provers.
+    pub staker_rewards: U256,
+    /// The share of the cost of requests paid to the owners of provers.
+    pub owner_rewards: U256,
+}
+
+impl FeeBreakdown {
+    /// Adds the fees credited by the given events to the breakdown.
+    pub fn record(&mut self, events: &[VAppEvent]) {
+        for event in events {
+            let VAppEvent::Credited { amount, reason, .. } = event else {
+                continue;
+            };
+            let total = match reason {
+                BalanceChangeReason::ProtocolFee => &mut self.protocol_fees,
+                BalanceChangeReason::Punishment => &mut self.punishments,
+                BalanceChangeReason::AuctioneerFee => &mut self.auctioneer_fees,
+                BalanceChangeReason::StakerReward => &mut self.staker_rewards,
+                BalanceChangeReason::OwnerReward => &mut self.owner_rewards,
+                _ => continue,
+            };
+            *total = total.saturating_add(*amount);
+        }
+    }
+
+    /// The revenue of the protocol, i.e. the fees and punishments paid to the treasury.
+    #[must_use]
+    pub fn protocol_revenue(&self) -> U256 {
+        self.protocol_fees.saturating_add(self.punishments)
+    }
+}
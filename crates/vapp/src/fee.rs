@@ -9,6 +9,27 @@ use alloy_primitives::U256;
 /// The protocol fee in bips.
 pub const PROTOCOL_FEE_BIPS: U256 = U256::ZERO;
 
+/// The number of bips in 100%.
+pub const BIPS_DENOMINATOR: U256 = U256::from_limbs([10_000, 0, 0, 0]);
+
+/// Calculates the share of an amount given in bips, i.e. `amount * bips / 10_000`.
+///
+/// The result is rounded down, so a share never exceeds its exact value and the remainder of a
+/// split stays with whoever receives the rest of the amount. Fails with
+/// [`VAppPanic::ArithmeticOverflow`] if `amount * bips` exceeds [`U256::MAX`].
+pub fn apply_bips(amount: U256, bips: U256) -> Result<U256, VAppPanic> {
+    u256::div(u256::mul(amount, bips)?, BIPS_DENOMINATOR)
+}
+
+/// Calculates the cost of a request to its requester, i.e. `price_per_pgu * pgus + base_fee`.
+///
+/// This is what a requester is charged when a request is cleared, with the price of the winning
+/// bid and the PGUs used by the proof. Fails with [`VAppPanic::ArithmeticOverflow`] if the cost
+/// exceeds [`U256::MAX`].
+pub fn request_cost(price_per_pgu: U256, pgus: u64, base_fee: U256) -> Result<U256, VAppPanic> {
+    u256::add(u256::mul(price_per_pgu, U256::from(pgus))?, base_fee)
+}
+
 /// Calculates the fee split for a given reward.
 ///
 /// Returns (`protocol_reward`, `staker_reward`, `owner_reward`).
@@ -17,8 +38,7 @@ pub fn fee(
     protocol_fee_bips: U256,
     staker_fee_bips: U256,
 ) -> Result<(U256, U256, U256), VAppPanic> {
-    // Basis points denominator (100% = 10_000 bips).
-    let denominator = U256::from(10_000u64);
+    let denominator = BIPS_DENOMINATOR;
 
     // Ensure individual bips are within the valid 0–10_000 range.
     if protocol_fee_bips > denominator {
@@ -51,9 +71,48 @@ pub fn fee(
         });
     }
 
-    let protocol_reward = u256::div(u256::mul(amount, protocol_fee_bips)?, denominator)?;
-    let staker_reward = u256::div(u256::mul(amount, adjusted_staker_fee_bips)?, denominator)?;
+    let protocol_reward = apply_bips(amount, protocol_fee_bips)?;
+    let staker_reward = apply_bips(amount, adjusted_staker_fee_bips)?;
     let owner_reward = u256::sub(u256::sub(amount, protocol_reward)?, staker_reward)?;
 
     Ok((protocol_reward, staker_reward, owner_reward))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_bips() {
+        // 1000 bips is 10%, and 10_000 bips is the whole amount.
+        assert_eq!(apply_bips(U256::from(12_345), U256::from(1_000)), Ok(U256::from(1_234)));
+        assert_eq!(apply_bips(U256::from(12_345), BIPS_DENOMINATOR), Ok(U256::from(12_345)));
+        assert_eq!(apply_bips(U256::from(12_345), U256::ZERO), Ok(U256::ZERO));
+
+        // Shares are rounded down.
+        assert_eq!(apply_bips(U256::from(9_999), U256::from(1)), Ok(U256::ZERO));
+        assert_eq!(apply_bips(U256::from(19_999), U256::from(1)), Ok(U256::from(1)));
+
+        // Amounts too large to multiply by the bips fail instead of wrapping around.
+        assert_eq!(apply_bips(U256::MAX, U256::from(1)), Ok(U256::MAX / BIPS_DENOMINATOR));
+        assert_eq!(apply_bips(U256::MAX, U256::from(2)), Err(VAppPanic::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn test_request_cost() {
+        assert_eq!(request_cost(U256::from(3), 1_000, U256::from(500)), Ok(U256::from(3_500)));
+        assert_eq!(request_cost(U256::MAX, 2, U256::ZERO), Err(VAppPanic::ArithmeticOverflow));
+        assert_eq!(request_cost(U256::MAX, 1, U256::from(1)), Err(VAppPanic::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn test_fee_rounding() {
+        // The remainder of the rounded down fees goes to the owner.
+        let (protocol, staker, owner) =
+            fee(U256::from(10_001), U256::from(1), U256::from(1_000)).unwrap();
+        assert_eq!(
+            (protocol, staker, owner),
+            (U256::from(1), U256::from(1_000), U256::from(9_000))
+        );
+    }
+}
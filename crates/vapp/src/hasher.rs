@@ -0,0 +1,130 @@
+//! Hashers.
+//!
+//! This module contains alternative implementations of [`MerkleTreeHasher`] that are cheaper to
+//! compute inside the zkVM than the default [`alloy_primitives::Keccak256`]. The L1 contract
+//! expects Keccak roots, so these are only suitable for trees whose roots are not checked on-chain.
+//!
+//! The cycles each hasher takes inside the zkVM can be compared with the
+//! `vapp-hasher-bench-script` binary in `programs/vapp/hasher-bench`.
+
+#[cfg(any(feature = "blake3", feature = "poseidon"))]
+use alloy_primitives::B256;
+
+#[cfg(any(feature = "blake3", feature = "poseidon"))]
+use crate::{merkle::MerkleTreeHasher, storage::StorageValue};
+
+/// A [`MerkleTreeHasher`] that uses BLAKE3 over the ABI encoding of the values.
+#[cfg(feature = "blake3")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Blake3;
+
+#[cfg(feature = "blake3")]
+impl MerkleTreeHasher for Blake3 {
+    fn hash<V: StorageValue>(value: &V) -> B256 {
+        B256::from(*blake3::hash(&value.abi_encode()).as_bytes())
+    }
+
+    fn hash_pair<V: StorageValue>(left: &V, right: &V) -> B256 {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&left.abi_encode());
+        hasher.update(&right.abi_encode());
+        B256::from(*hasher.finalize().as_bytes())
+    }
+}
+
+/// A [`MerkleTreeHasher`] that uses the circom-compatible Poseidon hash over the BN254 scalar
+/// field.
+///
+/// Values are ABI-encoded and split into 31-byte chunks so that each chunk fits in a field
+/// element, then absorbed one at a time starting from the encoded length. Pairs are hashed by
+/// reducing each 32-byte child into a field element.
+#[cfg(feature = "poseidon")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Poseidon;
+
+#[cfg(feature = "poseidon")]
+impl MerkleTreeHasher for Poseidon {
+    fn hash<V: StorageValue>(value: &V) -> B256 {
+        let encoded = value.abi_encode();
+        let mut state = ark_bn254::Fr::from(encoded.len() as u64);
+        for chunk in encoded.chunks(31) {
+            state = poseidon(&[state, to_field(chunk)]);
+        }
+        to_b256(state)
+    }
+
+    fn hash_pair<V: StorageValue>(left: &V, right: &V) -> B256 {
+        to_b256(poseidon(&[to_field(&left.abi_encode()), to_field(&right.abi_encode())]))
+    }
+}
+
+/// Hashes the field elements with the circom-compatible Poseidon parameters for their count.
+#[cfg(feature = "poseidon")]
+fn poseidon(inputs: &[ark_bn254::Fr]) -> ark_bn254::Fr {
+    use light_poseidon::PoseidonHasher;
+
+    // Both only fail if the number of inputs is unsupported, which is never the case for two.
+    light_poseidon::Poseidon::<ark_bn254::Fr>::new_circom(inputs.len())
+        .and_then(|mut poseidon| poseidon.hash(inputs))
+        .expect("poseidon supports two inputs")
+}
+
+/// Converts big-endian bytes into a field element, reducing them modulo the field order.
+#[cfg(feature = "poseidon")]
+fn to_field(bytes: &[u8]) -> ark_bn254::Fr {
+    use ark_ff::PrimeField;
+
+    ark_bn254::Fr::from_be_bytes_mod_order(bytes)
+}
+
+/// Converts a field element into its 32-byte big-endian representation.
+#[cfg(feature = "poseidon")]
+fn to_b256(element: ark_bn254::Fr) -> B256 {
+    use ark_ff::{BigInteger, PrimeField};
+
+    B256::left_padding_from(&element.into_bigint().to_bytes_be())
+}
+
+#[cfg(all(test, any(feature = "blake3", feature = "poseidon")))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+
+    use super::*;
+    use crate::{
+        merkle::MerkleStorage,
+        storage::{Storage, StorageKey},
+    };
+
+    /// Checks that a tree built with the hasher produces proofs that verify against its root.
+    fn check_hasher<H: MerkleTreeHasher>() {
+        let mut tree = MerkleStorage::<Address, U256, H>::new();
+        let empty_root = tree.root();
+        let keys: Vec<Address> = (1..=8u8).map(Address::with_last_byte).collect();
+        for (i, key) in keys.iter().enumerate() {
+            tree.insert(*key, U256::from(i)).unwrap();
+        }
+        let root = tree.root();
+        assert_ne!(root, empty_root);
+
+        for key in &keys {
+            let proof = tree.proof(key).unwrap();
+            assert_eq!(proof.proof.len(), Address::bits());
+            assert!(MerkleStorage::<Address, U256, H>::verify_proof(root, &proof).is_ok());
+        }
+
+        // Distinct values hash differently.
+        assert_ne!(H::hash(&uint!(1_U256)), H::hash(&uint!(2_U256)));
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_tree_verifies() {
+        check_hasher::<Blake3>();
+    }
+
+    #[cfg(feature = "poseidon")]
+    #[test]
+    fn poseidon_tree_verifies() {
+        check_hasher::<Poseidon>();
+    }
+}
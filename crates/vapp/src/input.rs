@@ -2,29 +2,45 @@
 //!
 //! This module contains types used as inputs into the programs that run inside the SP1 RISC-V zkVM.
 
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{Address, Keccak256, B256};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    merkle::MerkleProof, sol::Account, sparse::SparseStorage, state::VAppState, storage::RequestId,
+    merkle::{MerkleProof, MerkleTreeHasher},
+    sol::Account,
+    sparse::SparseStorage,
+    state::VAppState,
+    storage::{RequestId, SignerKey},
     transactions::VAppTransaction,
 };
 
 /// The inputs necessary to prove the state-transition-function of the vApp.
+///
+/// The hasher `H` of the state trees defaults to [`Keccak256`], which is what the L1 contract
+/// expects.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VAppStfInput {
+#[serde(bound(serialize = "", deserialize = ""))]
+pub struct VAppStfInput<H: MerkleTreeHasher = Keccak256> {
     /// The current state root.
     pub root: B256,
     /// The accounts root.
     pub accounts_root: B256,
     /// The requests root.
     pub requests_root: B256,
+    /// The additional signers root, or `None` if no prover has additional signers.
+    pub signers_root: Option<B256>,
     /// The current state.
-    pub state: VAppState<SparseStorage<Address, Account>, SparseStorage<RequestId, bool>>,
+    pub state: VAppState<
+        SparseStorage<Address, Account>,
+        SparseStorage<RequestId, bool>,
+        SparseStorage<SignerKey, bool>,
+    >,
     /// The merkle proofs for account verification.
-    pub account_proofs: Vec<MerkleProof<Address, Account>>,
+    pub account_proofs: Vec<MerkleProof<Address, Account, H>>,
     /// The merkle proofs for request verification.
-    pub request_proofs: Vec<MerkleProof<RequestId, bool>>,
+    pub request_proofs: Vec<MerkleProof<RequestId, bool, H>>,
+    /// The merkle proofs for additional signer verification.
+    pub signer_proofs: Vec<MerkleProof<SignerKey, bool, H>>,
     /// The transactions to process.
     pub txs: Vec<(i64, VAppTransaction)>,
     /// The prover's timestamp.
@@ -23,7 +23,9 @@
 #![warn(missing_docs)]
 
 pub mod errors;
+pub mod events;
 pub mod fee;
+pub mod hasher;
 pub mod input;
 pub mod merkle;
 pub mod receipts;
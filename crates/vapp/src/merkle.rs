@@ -5,11 +5,15 @@
 
 use std::{
     collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
     marker::PhantomData,
+    path::Path,
 };
 
 use alloy_primitives::{keccak256, Keccak256, B256, U256};
-use serde::{Deserialize, Serialize};
+use alloy_sol_types::{SolType, SolValue};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
@@ -64,6 +68,29 @@ pub enum MerkleStorageError {
 
     #[error("Invalid merkle proof length")]
     InvalidMerkleProofLength,
+
+    #[error("Invalid merkle proof encoding")]
+    InvalidMerkleProofEncoding,
+
+    #[error("Key is present in the tree")]
+    KeyPresent,
+}
+
+/// Errors that can occur when saving or loading a [`MerkleStorage`] checkpoint.
+#[derive(Debug, Error)]
+#[allow(missing_docs)]
+pub enum MerkleCheckpointError {
+    #[error("Failed to access checkpoint: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialize checkpoint: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Checkpoint has invalid zero hashes")]
+    InvalidZeroHashes,
+
+    #[error("Checkpoint root mismatch: expected {expected}, got {actual}")]
+    RootMismatch { expected: B256, actual: B256 },
 }
 
 /// A merkle proof for a key-value pair in the [`MerkleStorage`].
@@ -87,6 +114,137 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleProof<K, V, H> {
     }
 }
 
+impl<K, V, H> MerkleProof<K, V, H>
+where
+    K: StorageKey + SolValue + From<<K::SolType as SolType>::RustType>,
+    V: StorageValue + From<<V::SolType as SolType>::RustType>,
+    H: MerkleTreeHasher,
+{
+    /// Encode the proof into a compact byte format.
+    ///
+    /// The layout is the ABI encodings of the key and the value, each prefixed with its length as
+    /// a big-endian `u32` (an empty leaf has no value and a length of `u32::MAX`), followed by a
+    /// bitmap with one bit per layer and the siblings whose bit is set. Siblings that are the root
+    /// of an empty subtree are omitted, which makes proofs into sparse trees much smaller.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let zero_hashes = MerkleStorage::<K, V, H>::compute_zero_hashes();
+        let mut bitmap = vec![0u8; self.proof.len().div_ceil(8)];
+        let mut siblings = Vec::with_capacity(self.proof.len() * 32);
+        for (layer, sibling) in self.proof.iter().enumerate() {
+            if zero_hashes.get(layer) != Some(sibling) {
+                bitmap[layer / 8] |= 1 << (layer % 8);
+                siblings.extend_from_slice(sibling.as_slice());
+            }
+        }
+
+        let key = self.key.abi_encode();
+        let value = self.value.as_ref().map(SolValue::abi_encode);
+        let mut bytes = Vec::with_capacity(
+            8 + key.len() + value.as_ref().map_or(0, Vec::len) + bitmap.len() + siblings.len(),
+        );
+        write_length_prefixed(&mut bytes, Some(&key));
+        write_length_prefixed(&mut bytes, value.as_deref());
+        bytes.extend_from_slice(&bitmap);
+        bytes.extend_from_slice(&siblings);
+        bytes
+    }
+
+    /// Decode a proof produced by [`MerkleProof::to_bytes`], filling in the omitted siblings with
+    /// the roots of empty subtrees.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleStorageError> {
+        let mut reader = bytes;
+        let key = read_length_prefixed(&mut reader)?
+            .ok_or(MerkleStorageError::InvalidMerkleProofEncoding)?;
+        let key = K::abi_decode(key).map_err(|_| MerkleStorageError::InvalidMerkleProofEncoding)?;
+        let value = read_length_prefixed(&mut reader)?
+            .map(V::abi_decode)
+            .transpose()
+            .map_err(|_| MerkleStorageError::InvalidMerkleProofEncoding)?;
+
+        let num_bits = K::bits();
+        let bitmap = take(&mut reader, num_bits.div_ceil(8))
+            .ok_or(MerkleStorageError::InvalidMerkleProofEncoding)?;
+        let mut siblings = reader;
+        let zero_hashes = MerkleStorage::<K, V, H>::compute_zero_hashes();
+        let mut proof = Vec::with_capacity(num_bits);
+        for (layer, zero_hash) in zero_hashes.iter().take(num_bits).enumerate() {
+            if bitmap[layer / 8] & (1 << (layer % 8)) == 0 {
+                proof.push(*zero_hash);
+            } else {
+                let sibling = take(&mut siblings, 32)
+                    .ok_or(MerkleStorageError::InvalidMerkleProofEncoding)?;
+                proof.push(B256::from_slice(sibling));
+            }
+        }
+        if !siblings.is_empty() {
+            return Err(MerkleStorageError::InvalidMerkleProofEncoding);
+        }
+
+        Ok(Self::new(key, value, proof))
+    }
+}
+
+/// The length prefix of a missing value in the [`MerkleProof::to_bytes`] encoding.
+const MISSING_VALUE_LENGTH: u32 = u32::MAX;
+
+/// Write the bytes prefixed with their length, or [`MISSING_VALUE_LENGTH`] if there are none.
+fn write_length_prefixed(buffer: &mut Vec<u8>, bytes: Option<&[u8]>) {
+    match bytes {
+        Some(bytes) => {
+            let length = u32::try_from(bytes.len()).expect("encoded values fit in a u32");
+            buffer.extend_from_slice(&length.to_be_bytes());
+            buffer.extend_from_slice(bytes);
+        }
+        None => buffer.extend_from_slice(&MISSING_VALUE_LENGTH.to_be_bytes()),
+    }
+}
+
+/// Read bytes written by [`write_length_prefixed`], advancing the reader past them.
+fn read_length_prefixed<'a>(reader: &mut &'a [u8]) -> Result<Option<&'a [u8]>, MerkleStorageError> {
+    let length = take(reader, 4).ok_or(MerkleStorageError::InvalidMerkleProofEncoding)?;
+    let length = u32::from_be_bytes(length.try_into().expect("length is four bytes"));
+    if length == MISSING_VALUE_LENGTH {
+        return Ok(None);
+    }
+    take(reader, length as usize).map(Some).ok_or(MerkleStorageError::InvalidMerkleProofEncoding)
+}
+
+/// Split the first `len` bytes off the reader, if it has that many.
+fn take<'a>(reader: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if reader.len() < len {
+        return None;
+    }
+    let (head, tail) = reader.split_at(len);
+    *reader = tail;
+    Some(head)
+}
+
+/// A single merkle proof for several key-value pairs in the [`MerkleStorage`].
+///
+/// Proofs for nearby keys share most of their sibling hashes, so instead of storing a full path per
+/// key, this only stores the siblings that can't be computed from the leaves themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiProof<K: StorageKey, V: StorageValue, H: MerkleTreeHasher = Keccak256> {
+    /// The keys being accessed and their current values (`None` if the leaf is empty), sorted by
+    /// index.
+    pub leaves: Vec<(K, Option<V>)>,
+    /// The sibling hashes, in the order they are consumed when replaying the proof bottom-up,
+    /// layer by layer and from left to right within each layer.
+    pub siblings: Vec<B256>,
+    /// The phantom data for the hasher type.
+    #[serde(skip)]
+    _hasher: PhantomData<H>,
+}
+
+impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MultiProof<K, V, H> {
+    /// Construct a multiproof from its leaves and sibling hashes.
+    #[must_use]
+    pub fn new(leaves: Vec<(K, Option<V>)>, siblings: Vec<B256>) -> Self {
+        Self { leaves, siblings, _hasher: PhantomData }
+    }
+}
+
 /// Trait for types that can be used as the hasher in a [`MerkleTree`].
 pub trait MerkleTreeHasher {
     /// Returns the hash of the value.
@@ -97,9 +255,34 @@ pub trait MerkleTreeHasher {
 }
 
 impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H> {
+    /// Build a tree from its leaves in one pass.
+    ///
+    /// Unlike calling [`Storage::insert`] for each key, this computes the zero hashes once and
+    /// doesn't touch the cache or track the keys as touched, so it is the constructor to use when
+    /// loading a persisted state. Later pairs overwrite earlier ones with the same key.
+    #[must_use]
+    pub fn from_leaves(leaves: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self {
+            leaves: leaves.into_iter().map(|(key, value)| (key.index(), value)).collect(),
+            zero_hashes: Self::compute_zero_hashes(),
+            cache: BTreeMap::new(),
+            touched_keys: BTreeSet::new(),
+            _key: PhantomData,
+            _hasher: PhantomData,
+        }
+    }
+
     /// Compute the merkle root from scratch.
+    ///
+    /// With the `rayon` feature enabled, the nodes of each layer are hashed in parallel.
     #[must_use]
     pub fn root(&self) -> B256 {
+        self.compute_root(cfg!(feature = "rayon"))
+    }
+
+    /// Compute the merkle root from scratch, hashing the nodes of each layer in parallel if
+    /// `parallel` is set.
+    fn compute_root(&self, parallel: bool) -> B256 {
         let num_bits = K::bits();
 
         // If no leaves, return the precomputed empty tree root.
@@ -107,55 +290,76 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
             return self.zero_hashes[num_bits];
         }
 
-        // Build the tree bottom-up.
-        let mut current_layer: BTreeMap<U256, B256> = BTreeMap::new();
-
         // Start with leaves (layer 0) - hash the values.
-        for (&index, value) in &self.leaves {
-            let hash = H::hash(value);
-            current_layer.insert(index, hash);
-        }
+        let mut current_layer = self.leaf_layer(parallel);
 
         // Build each layer up to the root.
         for layer in 1..=num_bits {
-            let mut next_layer: BTreeMap<U256, B256> = BTreeMap::new();
-
-            // Process all nodes that exist in current layer.
-            for (&index, &hash) in &current_layer {
-                let parent_index = index >> 1;
-                let is_left_child = (index & U256::from(1)) == U256::ZERO;
-
-                // Get or create parent entry.
-                let parent_entry = next_layer.entry(parent_index).or_insert_with(|| {
-                    if is_left_child {
-                        H::hash_pair(&hash, &self.zero_hashes[layer - 1])
-                    } else {
-                        H::hash_pair(&self.zero_hashes[layer - 1], &hash)
-                    }
-                });
-
-                // Update parent with both children if we now have the sibling.
-                if is_left_child {
-                    let sibling_index = index | U256::from(1);
-                    if let Some(&sibling_hash) = current_layer.get(&sibling_index) {
-                        *parent_entry = H::hash_pair(&hash, &sibling_hash);
-                    }
-                } else {
-                    let sibling_index = index & !U256::from(1);
-                    if let Some(&sibling_hash) = current_layer.get(&sibling_index) {
-                        *parent_entry = H::hash_pair(&sibling_hash, &hash);
-                    }
-                }
-            }
-
-            current_layer = next_layer;
+            current_layer = self.parent_layer(layer, &current_layer, parallel);
         }
 
         // Return root or empty tree hash if no nodes made it to the top.
-        let num_bits = K::bits();
         current_layer.get(&U256::ZERO).copied().unwrap_or(self.zero_hashes[num_bits])
     }
 
+    /// The root of a tree without any leaves.
+    #[must_use]
+    pub fn empty_root() -> B256 {
+        Self::compute_zero_hashes()[K::bits()]
+    }
+
+    /// Whether the tree has no leaves.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Compute the hashes of the non-empty leaves.
+    ///
+    /// Each leaf is hashed independently, so if `parallel` is set and the `rayon` feature is
+    /// enabled, the leaves are hashed in parallel.
+    #[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+    fn leaf_layer(&self, parallel: bool) -> BTreeMap<U256, B256> {
+        let hash_leaf = |(&index, value): (&U256, &V)| (index, H::hash(value));
+
+        #[cfg(feature = "rayon")]
+        if parallel {
+            use rayon::prelude::*;
+            return self.leaves.par_iter().map(hash_leaf).collect();
+        }
+        self.leaves.iter().map(hash_leaf).collect()
+    }
+
+    /// Compute the non-empty nodes at `layer` from the non-empty nodes of the layer below it.
+    ///
+    /// Each parent only depends on its two children, so if `parallel` is set and the `rayon`
+    /// feature is enabled, the parents are hashed in parallel.
+    #[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+    fn parent_layer(
+        &self,
+        layer: usize,
+        children: &BTreeMap<U256, B256>,
+        parallel: bool,
+    ) -> BTreeMap<U256, B256> {
+        let zero_hash = self.zero_hashes[layer - 1];
+        // The children are sorted, so siblings map to adjacent duplicate parents.
+        let mut parents: Vec<U256> = children.keys().map(|&index| index >> 1).collect();
+        parents.dedup();
+        let hash_parent = |&parent: &U256| {
+            let left_index = parent << 1;
+            let left = children.get(&left_index).unwrap_or(&zero_hash);
+            let right = children.get(&(left_index | U256::from(1))).unwrap_or(&zero_hash);
+            (parent, H::hash_pair(left, right))
+        };
+
+        #[cfg(feature = "rayon")]
+        if parallel {
+            use rayon::prelude::*;
+            return parents.par_iter().map(hash_parent).collect();
+        }
+        parents.iter().map(hash_parent).collect()
+    }
+
     /// Generate a merkle proof for the value at the given key.
     pub fn proof(&mut self, key: &K) -> Result<MerkleProof<K, V, H>, MerkleStorageError> {
         let index = key.index();
@@ -198,6 +402,80 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
         Ok(MerkleProof::new(key.clone(), self.leaves.get(&index).cloned(), proof))
     }
 
+    /// Generate a merkle proof for the value at the given key without mutating the tree.
+    ///
+    /// Unlike [`MerkleStorage::proof`], sibling hashes that aren't already cached are recomputed
+    /// on every call instead of being cached, so this is slower but can be called through a
+    /// shared reference, e.g. by many readers holding a read lock.
+    pub fn proof_ref(&self, key: &K) -> Result<MerkleProof<K, V, H>, MerkleStorageError> {
+        let index = key.index();
+        let num_bits = K::bits();
+
+        if K::bits() < 256 && index >= (U256::from(1) << num_bits) {
+            return Err(MerkleStorageError::IndexOutOfBounds { index, num_bits });
+        }
+
+        let proof =
+            (0..num_bits).map(|layer| self.node_ref(layer, (index >> layer) ^ U256::ONE)).collect();
+
+        Ok(MerkleProof::new(key.clone(), self.leaves.get(&index).cloned(), proof))
+    }
+
+    /// Generate a merkle proof that the given key is absent from the tree.
+    ///
+    /// Empty leaves hash to [`B256::ZERO`], so the returned proof has no value and proves that the
+    /// leaf at the key's index is the zero hash. Returns [`MerkleStorageError::KeyPresent`] if the
+    /// key has a value.
+    pub fn non_membership_proof(
+        &mut self,
+        key: &K,
+    ) -> Result<MerkleProof<K, V, H>, MerkleStorageError> {
+        if self.leaves.contains_key(&key.index()) {
+            return Err(MerkleStorageError::KeyPresent);
+        }
+        self.proof(key)
+    }
+
+    /// Generate a single merkle proof for the values at the given keys.
+    ///
+    /// Duplicate keys are only included once.
+    pub fn multiproof(&mut self, keys: &[K]) -> Result<MultiProof<K, V, H>, MerkleStorageError> {
+        let num_bits = K::bits();
+
+        let mut leaves = Vec::with_capacity(keys.len());
+        for key in keys {
+            let index = key.index();
+            if num_bits < 256 && index >= (U256::from(1) << num_bits) {
+                return Err(MerkleStorageError::IndexOutOfBounds { index, num_bits });
+            }
+            leaves.push((key.clone(), self.leaves.get(&index).cloned()));
+        }
+        leaves.sort_by_key(|(key, _)| key.index());
+        leaves.dedup_by_key(|(key, _)| key.index());
+
+        // Walk up the tree, only emitting the siblings that aren't themselves on a proven path.
+        let mut indices: Vec<U256> = leaves.iter().map(|(key, _)| key.index()).collect();
+        let mut siblings = Vec::new();
+        for layer in 0..num_bits {
+            let mut i = 0;
+            while i < indices.len() {
+                let index = indices[i];
+                if index & U256::ONE == U256::ZERO &&
+                    indices.get(i + 1) == Some(&(index | U256::ONE))
+                {
+                    i += 2;
+                } else {
+                    siblings.push(self.compute_node(layer, index ^ U256::ONE));
+                    i += 1;
+                }
+            }
+            indices = indices.into_iter().map(|index| index >> 1).collect();
+            indices.dedup();
+        }
+
+        Ok(MultiProof::new(leaves, siblings))
+    }
+
     /// Check if a subtree is completely empty (contains no leaves).
     fn is_subtree_empty(&self, layer: usize, index: U256) -> bool {
         // Calculate the range of leaf indices that this subtree covers.
@@ -238,14 +516,70 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
         let hash = H::hash_pair(&left_hash, &right_hash);
 
         // Only cache the top layers to bound memory usage.
-        let cache_threshold = K::bits().saturating_sub(COMPUTE_NODE_CACHE_LAYERS);
-        if layer >= cache_threshold {
+        if layer >= Self::cache_threshold() {
             self.cache.insert((layer, index), hash);
         }
 
         hash
     }
 
+    /// The lowest layer whose nodes are cached by [`MerkleStorage::compute_node`].
+    fn cache_threshold() -> usize {
+        K::bits().saturating_sub(COMPUTE_NODE_CACHE_LAYERS)
+    }
+
+    /// Remove the cached hashes of the ancestors of the leaf at `index`, which are the only nodes
+    /// whose hashes change when the leaf does.
+    fn invalidate_path(&mut self, index: U256) {
+        for layer in Self::cache_threshold().max(1)..=K::bits() {
+            self.cache.remove(&(layer, index >> layer));
+        }
+    }
+
+    /// Remove the cached hashes of the ancestors of all the leaves at `indices`.
+    ///
+    /// The indices are sorted, so the leaves that share an ancestor in a layer are adjacent, and
+    /// each ancestor is only removed once.
+    fn invalidate_paths(&mut self, indices: &BTreeSet<U256>) {
+        if self.cache.is_empty() {
+            return;
+        }
+        for layer in Self::cache_threshold().max(1)..=K::bits() {
+            let mut last = None;
+            for index in indices {
+                let node = *index >> layer;
+                if last != Some(node) {
+                    self.cache.remove(&(layer, node));
+                    last = Some(node);
+                }
+            }
+        }
+    }
+
+    /// Compute the hash of the node at (`layer`, `index`) like [`MerkleStorage::compute_node`], but
+    /// without populating the cache.
+    fn node_ref(&self, layer: usize, index: U256) -> B256 {
+        // Base case: leaf layer.
+        if layer == 0 {
+            return self.leaves.get(&index).map_or(self.zero_hashes[0], |v| H::hash(v));
+        }
+
+        // Return cached value if available.
+        if let Some(&cached) = self.cache.get(&(layer, index)) {
+            return cached;
+        }
+
+        // Empty subtree short-circuit.
+        if self.is_subtree_empty(layer, index) {
+            return self.zero_hashes[layer];
+        }
+
+        // Recurse into left and right children.
+        let left_hash = self.node_ref(layer - 1, index << 1);
+        let right_hash = self.node_ref(layer - 1, (index << 1) | U256::from(1));
+        H::hash_pair(&left_hash, &right_hash)
+    }
+
     /// Get the set of keys that have been touched (read or written).
     #[must_use]
     pub fn get_touched_keys(&self) -> &BTreeSet<K> {
@@ -293,6 +627,21 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
         }
     }
 
+    /// Verify a merkle proof that the key is absent from the tree, i.e. that its leaf is the zero
+    /// hash that empty leaves hash to.
+    ///
+    /// Returns [`MerkleStorageError::KeyPresent`] if the proof carries a value, since it then
+    /// claims membership instead.
+    pub fn verify_non_membership(
+        root: B256,
+        proof: &MerkleProof<K, V, H>,
+    ) -> Result<(), MerkleStorageError> {
+        if proof.value.is_some() {
+            return Err(MerkleStorageError::KeyPresent);
+        }
+        Self::verify_proof_with_hash(root, proof.key.index(), B256::ZERO, &proof.proof)
+    }
+
     /// Verify a merkle proof with a pre-computed leaf hash.
     pub fn verify_proof_with_hash(
         root: B256,
@@ -323,6 +672,71 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
         Ok(())
     }
 
+    /// Verify a multiproof for several key-value pairs.
+    ///
+    /// An empty multiproof proves nothing and always verifies.
+    pub fn verify_multiproof(
+        root: B256,
+        proof: &MultiProof<K, V, H>,
+    ) -> Result<(), MerkleStorageError> {
+        if proof.leaves.is_empty() {
+            return Ok(());
+        }
+
+        let mut nodes: Vec<(U256, B256)> = proof
+            .leaves
+            .iter()
+            .map(|(key, value)| (key.index(), value.as_ref().map_or(B256::ZERO, |v| H::hash(v))))
+            .collect();
+
+        // The siblings are ordered by the leaf indices, so the leaves must be sorted and unique.
+        if nodes.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(MerkleStorageError::InvalidMerkleProof);
+        }
+
+        let mut siblings = proof.siblings.iter();
+        for _ in 0..K::bits() {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let (index, hash) = nodes[i];
+                let parent_hash = if index & U256::ONE == U256::ZERO {
+                    match nodes.get(i + 1) {
+                        // The sibling is on a proven path, so its hash is already known.
+                        Some(&(sibling_index, sibling_hash))
+                            if sibling_index == index | U256::ONE =>
+                        {
+                            i += 1;
+                            H::hash_pair(&hash, &sibling_hash)
+                        }
+                        _ => {
+                            let sibling_hash = siblings
+                                .next()
+                                .ok_or(MerkleStorageError::InvalidMerkleProofLength)?;
+                            H::hash_pair(&hash, sibling_hash)
+                        }
+                    }
+                } else {
+                    let sibling_hash =
+                        siblings.next().ok_or(MerkleStorageError::InvalidMerkleProofLength)?;
+                    H::hash_pair(sibling_hash, &hash)
+                };
+                parents.push((index >> 1, parent_hash));
+                i += 1;
+            }
+            nodes = parents;
+        }
+
+        if siblings.next().is_some() {
+            return Err(MerkleStorageError::InvalidMerkleProofLength);
+        }
+        if nodes != [(U256::ZERO, root)] {
+            return Err(MerkleStorageError::InvalidMerkleProof);
+        }
+
+        Ok(())
+    }
+
     /// Compute zero hashes for all layers.
     fn compute_zero_hashes() -> Vec<B256> {
         let num_bits = K::bits();
@@ -346,14 +760,15 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
     /// # Arguments
     /// * `old_root` - The previous merkle root
     /// * `proofs` - List of merkle proofs for accessed keys
-    /// * `new_values` - List of (key, `new_value`) pairs to update
+    /// * `new_values` - List of (key, `new_value`) pairs to update, where a `new_value` of `None`
+    ///   deletes the leaf
     ///
     /// # Returns
     /// Result containing the new merkle root or an error if proofs are invalid
     pub fn calculate_new_root(
         old_root: B256,
         proofs: &[MerkleProof<K, V, H>],
-        new_values: &[(K, V)],
+        new_values: &[(K, Option<V>)],
     ) -> Result<B256, MerkleStorageError> {
         // Early return if absolutely nothing was accessed or modified.
         if new_values.is_empty() && proofs.is_empty() {
@@ -365,9 +780,9 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
         //    this, also build a fast-lookup table that tells us whether a particular key has an
         //    updated value or not.
         // -----------------------------------------------------------------------------------------
-        let mut updated_value_map: BTreeMap<U256, &V> = BTreeMap::new();
+        let mut updated_value_map: BTreeMap<U256, Option<&V>> = BTreeMap::new();
         for (k, v) in new_values {
-            updated_value_map.insert(k.index(), v);
+            updated_value_map.insert(k.index(), v.as_ref());
         }
 
         for proof in proofs {
@@ -392,18 +807,14 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
         let mut nodes: BTreeMap<(usize, U256), B256> = BTreeMap::new();
 
         // Iterate over every proof and replay its path to the root using the
-        // (possibly updated) value for the leaf.
+        // (possibly updated) value for the leaf. Deleted and empty leaves hash to zero.
         for proof in proofs {
             let leaf_index = proof.key.index();
-            let is_updated = updated_value_map.contains_key(&leaf_index);
-            let mut current_hash = if is_updated {
-                H::hash(updated_value_map[&leaf_index])
-            } else {
-                match &proof.value {
-                    Some(v) => H::hash(v),
-                    None => B256::ZERO,
-                }
+            let value = match updated_value_map.get(&leaf_index) {
+                Some(&updated_value) => updated_value,
+                None => proof.value.as_ref(),
             };
+            let mut current_hash = value.map_or(B256::ZERO, |v| H::hash(v));
             let mut current_index = leaf_index;
 
             // Store/overwrite the leaf node.
@@ -462,9 +873,9 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
             return Ok(old_root);
         }
 
-        // Build a `Vec<(K, V)>` with **updated** key/value pairs. We rely on the proofs to
+        // Build a `Vec<(K, Option<V>)>` with **updated** key/value pairs. We rely on the proofs to
         // provide the canonical key values (`K`) for each updated index.
-        let mut new_values: Vec<(K, V)> = Vec::new();
+        let mut new_values: Vec<(K, Option<V>)> = Vec::new();
 
         for (index, value) in updates.iter_raw() {
             // Find the matching proof so we can recover the key of type `K`.
@@ -472,7 +883,7 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
                 return Err(MerkleStorageError::MissingMerkleProofForUpdatedKey);
             };
 
-            new_values.push((proof.key.clone(), (*value).clone()));
+            new_values.push((proof.key.clone(), Some((*value).clone())));
         }
 
         // Delegate to the original implementation.
@@ -480,6 +891,53 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> MerkleStorage<K, V, H>
     }
 }
 
+impl<K, V, H> MerkleStorage<K, V, H>
+where
+    K: StorageKey,
+    V: StorageValue,
+    H: MerkleTreeHasher,
+    Self: Serialize + DeserializeOwned,
+{
+    /// Save a checkpoint of the tree to the given path.
+    ///
+    /// Only the leaves and zero hashes are saved, as the cache is rebuilt lazily. The checkpoint
+    /// is written to a temporary file first and then renamed, so an existing checkpoint at the
+    /// path is never left half-written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), MerkleCheckpointError> {
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Load a checkpoint saved with [`MerkleStorage::save`] from the given path.
+    ///
+    /// The root of the loaded tree is checked against `expected_root`, which should come from a
+    /// trusted source like the L1 contract, to catch corrupted or tampered checkpoints.
+    pub fn load(
+        path: impl AsRef<Path>,
+        expected_root: B256,
+    ) -> Result<Self, MerkleCheckpointError> {
+        let reader = BufReader::new(File::open(path)?);
+        let storage: Self = serde_json::from_reader(reader)?;
+
+        if storage.zero_hashes != Self::compute_zero_hashes() {
+            return Err(MerkleCheckpointError::InvalidZeroHashes);
+        }
+        let actual = storage.root();
+        if actual != expected_root {
+            return Err(MerkleCheckpointError::RootMismatch { expected: expected_root, actual });
+        }
+
+        Ok(storage)
+    }
+}
+
 impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> Default for MerkleStorage<K, V, H> {
     fn default() -> Self {
         Self::new()
@@ -508,8 +966,25 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> Storage<K, V> for Merk
         self.leaves.insert(index, value);
         // Track that this key has been touched (written).
         self.touched_keys.insert(key);
-        // Clear cache as tree structure has changed.
-        self.cache.clear();
+        // Invalidate the cached nodes on the path to the root, as their hashes have changed.
+        self.invalidate_path(index);
+
+        Ok(())
+    }
+
+    /// Insert many values at once.
+    ///
+    /// The cached nodes are invalidated once at the end, and the ancestors shared by the inserted
+    /// leaves are only visited once.
+    fn insert_many(&mut self, items: impl IntoIterator<Item = (K, V)>) -> Result<(), StorageError> {
+        let mut indices = BTreeSet::new();
+        for (key, value) in items {
+            let index = key.index();
+            self.leaves.insert(index, value);
+            self.touched_keys.insert(key);
+            indices.insert(index);
+        }
+        self.invalidate_paths(&indices);
 
         Ok(())
     }
@@ -519,11 +994,10 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> Storage<K, V> for Merk
         let index = key.index();
         // Track that this key has been touched (entry can be used for read or write).
         self.touched_keys.insert(key);
+        // Invalidate the cached nodes on the path to the root, as their hashes may change.
+        self.invalidate_path(index);
         // Get the entry.
-        let entry = self.leaves.entry(index);
-        // Clear cache as tree structure may change.
-        self.cache.clear();
-        Ok(entry)
+        Ok(self.leaves.entry(index))
     }
 
     /// Get a value at the given key.
@@ -541,11 +1015,15 @@ impl<K: StorageKey, V: StorageValue, H: MerkleTreeHasher> Storage<K, V> for Merk
         let index = key.index();
         // Track that this key has been touched (entry can be used for read or write).
         self.touched_keys.insert(key.clone());
+        // Invalidate the cached nodes on the path to the root, as their hashes may change.
+        self.invalidate_path(index);
         // Get the leaf.
-        let leaf = self.leaves.get_mut(&index);
-        // Clear cache as tree structure may change.
-        self.cache.clear();
-        Ok(leaf)
+        Ok(self.leaves.get_mut(&index))
+    }
+
+    /// Iterate over the non-empty leaves and their indices, in index order.
+    fn iter(&self) -> Box<dyn Iterator<Item = (&U256, &V)> + '_> {
+        Box::new(self.leaves.iter())
     }
 }
 
@@ -641,6 +1119,127 @@ mod tests {
         assert_eq!(tree1.root(), tree2.root());
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_root_matches_serial_root() {
+        // Trees of pseudo-random keys and values, from empty to a thousand leaves.
+        for (seed, size) in [(0u8, 0usize), (1, 1), (2, 2), (3, 17), (4, 1000)] {
+            let leaves = (0..size).map(|i| {
+                let word = keccak256([[seed].as_slice(), &i.to_be_bytes()].concat());
+                (Address::from_word(word), U256::from_be_bytes(keccak256(word).0))
+            });
+            let tree = AddressTree::from_leaves(leaves);
+            assert_eq!(tree.compute_root(true), tree.compute_root(false), "size {size}");
+            assert_eq!(tree.root(), tree.compute_root(false));
+        }
+    }
+
+    #[test]
+    fn from_leaves_matches_inserts() {
+        let leaves: Vec<(Address, U256)> =
+            (0..64u8).map(|i| (Address::from_word(keccak256([i])), U256::from(i))).collect();
+
+        let mut inserted = AddressTree::new();
+        for (key, value) in &leaves {
+            inserted.insert(*key, *value).unwrap();
+        }
+
+        let mut loaded = AddressTree::from_leaves(leaves.clone());
+        assert_eq!(loaded.root(), inserted.root());
+        assert!(loaded.get_touched_keys().is_empty());
+
+        // The loaded tree behaves like any other after construction.
+        let proof = loaded.proof(&leaves[7].0).unwrap();
+        assert!(AddressTree::verify_proof(inserted.root(), &proof).is_ok());
+        assert_eq!(AddressTree::from_leaves([]).root(), AddressTree::new().root());
+    }
+
+    #[test]
+    fn proof_bytes_round_trip() {
+        let mut tree = AddressTree::new();
+        let key = address!("742d35Cc6635C0532925a3b8D39A2E9bcf2E7570");
+        tree.insert(key, uint!(1000_U256)).unwrap();
+        tree.insert(Address::ZERO, uint!(2000_U256)).unwrap();
+        let root = tree.root();
+
+        // Siblings of empty subtrees are omitted, so a sparse proof is much smaller.
+        let proof = tree.proof(&key).unwrap();
+        let bytes = proof.to_bytes();
+        assert!(bytes.len() < 160 * 32 / 4);
+        let decoded = MerkleProof::<Address, U256>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.key, key);
+        assert_eq!(decoded.value, Some(uint!(1000_U256)));
+        assert_eq!(decoded.proof, proof.proof);
+        assert!(AddressTree::verify_proof(root, &decoded).is_ok());
+
+        // Proofs of empty leaves round trip too.
+        let missing = Address::with_last_byte(1);
+        let proof = tree.proof(&missing).unwrap();
+        let decoded = MerkleProof::<Address, U256>::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(decoded.value, None);
+        assert!(AddressTree::verify_proof(root, &decoded).is_ok());
+
+        // Truncated and padded encodings are rejected.
+        assert_eq!(
+            MerkleProof::<Address, U256>::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            MerkleStorageError::InvalidMerkleProofEncoding
+        );
+        let mut padded = bytes.clone();
+        padded.push(0);
+        assert!(MerkleProof::<Address, U256>::from_bytes(&padded).is_err());
+    }
+
+    #[test]
+    fn non_membership_proof_proves_absence() {
+        let mut tree = AddressTree::new();
+        let present = address!("742d35Cc6635C0532925a3b8D39A2E9bcf2E7570");
+        let absent = address!("8ba1f109551bD432803012645aac136c0001bC80");
+        tree.insert(present, uint!(1000_U256)).unwrap();
+        let root = tree.root();
+
+        let proof = tree.non_membership_proof(&absent).unwrap();
+        assert_eq!(proof.value, None);
+        assert!(AddressTree::verify_non_membership(root, &proof).is_ok());
+        assert_eq!(
+            tree.non_membership_proof(&present).unwrap_err(),
+            MerkleStorageError::KeyPresent
+        );
+
+        // A membership proof doesn't prove absence, even with its value stripped.
+        let mut proof = tree.proof(&present).unwrap();
+        assert_eq!(
+            AddressTree::verify_non_membership(root, &proof).unwrap_err(),
+            MerkleStorageError::KeyPresent
+        );
+        proof.value = None;
+        assert_eq!(
+            AddressTree::verify_non_membership(root, &proof).unwrap_err(),
+            MerkleStorageError::InvalidMerkleProof
+        );
+    }
+
+    #[test]
+    fn save_and_load_checkpoint() {
+        let tree = AddressTree::from_leaves(
+            (0..16u8).map(|i| (Address::from_word(keccak256([i])), U256::from(i))),
+        );
+        let root = tree.root();
+
+        // Save and reload the tree.
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        tree.save(&path).unwrap();
+        let loaded = AddressTree::load(&path, root).unwrap();
+        assert_eq!(loaded.root(), root);
+
+        // A checkpoint that doesn't match the expected root is rejected.
+        let result = AddressTree::load(&path, B256::ZERO);
+        assert!(matches!(
+            result,
+            Err(MerkleCheckpointError::RootMismatch { expected: B256::ZERO, actual }) if actual == root
+        ));
+    }
+
     #[test]
     fn address_tree_works_with_ethereum_addresses() {
         let mut tree = AddressTree::new();
@@ -689,6 +1288,50 @@ mod tests {
         assert_eq!(tree.get(&key).unwrap(), Some(&updated_value));
     }
 
+    #[test]
+    fn insert_many_matches_individual_inserts() {
+        let items: Vec<_> = (0..50u64).map(|i| (U256::from(i * 7919), U256::from(i + 1))).collect();
+        let mut expected = U256Tree::new();
+        for (key, value) in &items {
+            expected.insert(*key, *value).unwrap();
+        }
+
+        // Populate the cache before the batch, so that stale nodes would show up in the proofs.
+        let mut tree = U256Tree::new();
+        tree.insert(uint!(1_U256), uint!(1_U256)).unwrap();
+        tree.proof(&uint!(1_U256)).unwrap();
+        tree.insert_many(items.iter().copied().chain([(uint!(1_U256), U256::ZERO)])).unwrap();
+        expected.insert(uint!(1_U256), U256::ZERO).unwrap();
+
+        let root = tree.root();
+        assert_eq!(root, expected.root());
+        for (key, _) in &items {
+            assert!(tree.get_touched_keys().contains(key));
+            assert!(U256Tree::verify_proof(root, &tree.proof(key).unwrap()).is_ok());
+        }
+    }
+
+    #[test]
+    fn contains_key_and_iter_reflect_stored_leaves() {
+        let mut tree = U256Tree::new();
+        let (key1, key2) = (uint!(7_U256), uint!(3_U256));
+
+        assert!(!tree.contains_key(&key1).unwrap());
+        tree.insert(key1, uint!(70_U256)).unwrap();
+        tree.insert(key2, uint!(30_U256)).unwrap();
+        assert!(tree.contains_key(&key1).unwrap());
+
+        // Checking for a key counts as a read, so that it is witnessed.
+        let missing = uint!(99_U256);
+        assert!(!tree.contains_key(&missing).unwrap());
+        assert!(tree.get_touched_keys().contains(&missing));
+
+        // The trait can be used as an object, and iterates in index order.
+        let storage: &dyn Storage<U256, U256> = &tree;
+        let leaves: Vec<_> = storage.iter().map(|(index, value)| (*index, *value)).collect();
+        assert_eq!(leaves, [(key2, uint!(30_U256)), (key1, uint!(70_U256))]);
+    }
+
     #[test]
     fn verify_proof_with_hash_works_correctly() {
         let mut tree = U256Tree::new();
@@ -730,6 +1373,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_only_invalidates_cached_path() {
+        let mut tree = AddressTree::new();
+        let keys: Vec<Address> = (0..32u8).map(|i| Address::from_word(keccak256([i]))).collect();
+        for (i, key) in keys.iter().enumerate() {
+            tree.insert(*key, U256::from(i)).unwrap();
+        }
+
+        // Populate the cache.
+        for key in &keys {
+            tree.proof(key).unwrap();
+        }
+        let cached = tree.cache.len();
+        assert!(cached > 0);
+
+        // Updating a leaf only evicts its ancestors, and proofs stay valid against the new root.
+        tree.insert(keys[3], uint!(1000_U256)).unwrap();
+        assert!(tree.cache.len() >= cached - COMPUTE_NODE_CACHE_LAYERS);
+        *tree.get_mut(&keys[5]).unwrap().unwrap() = uint!(2000_U256);
+        let root = tree.root();
+        for key in &keys {
+            let proof = tree.proof(key).unwrap();
+            assert!(AddressTree::verify_proof(root, &proof).is_ok());
+        }
+    }
+
+    #[test]
+    fn proof_ref_matches_proof() {
+        let mut tree = AddressTree::new();
+        let keys: Vec<Address> = (0..16u8).map(|i| Address::from_word(keccak256([i]))).collect();
+        for (i, key) in keys.iter().enumerate() {
+            tree.insert(*key, U256::from(i)).unwrap();
+        }
+        let root = tree.root();
+
+        // Proofs from a shared reference match the caching proofs and leave the cache untouched.
+        let shared = &tree;
+        let proofs: Vec<_> = keys.iter().map(|key| shared.proof_ref(key).unwrap()).collect();
+        assert!(tree.cache.is_empty());
+        for (key, proof) in keys.iter().zip(&proofs) {
+            assert!(AddressTree::verify_proof(root, proof).is_ok());
+            assert_eq!(proof.proof, tree.proof(key).unwrap().proof);
+        }
+
+        // Absent keys work too, reusing the nodes cached by `proof`.
+        let absent = Address::with_last_byte(1);
+        let proof = tree.proof_ref(&absent).unwrap();
+        assert_eq!(proof.value, None);
+        assert!(AddressTree::verify_proof(root, &proof).is_ok());
+    }
+
+    #[test]
+    fn multiproof_verifies_many_keys() {
+        let mut tree = AddressTree::new();
+        let keys: Vec<Address> = (0..16u8).map(|i| Address::with_last_byte(i * 3)).collect();
+        for (i, key) in keys.iter().enumerate() {
+            tree.insert(*key, U256::from(i)).unwrap();
+        }
+        let root = tree.root();
+
+        // Include an absent key and a duplicate.
+        let mut proven = keys[..8].to_vec();
+        proven.push(Address::with_last_byte(100));
+        proven.push(keys[0]);
+        let multiproof = tree.multiproof(&proven).unwrap();
+        assert_eq!(multiproof.leaves.len(), 9);
+        assert!(AddressTree::verify_multiproof(root, &multiproof).is_ok());
+
+        // Shared siblings are only included once.
+        assert!(multiproof.siblings.len() < 9 * 160);
+
+        // A wrong value fails to verify.
+        let mut wrong_value = multiproof.clone();
+        wrong_value.leaves[1].1 = Some(uint!(999_U256));
+        assert_eq!(
+            AddressTree::verify_multiproof(root, &wrong_value),
+            Err(MerkleStorageError::InvalidMerkleProof)
+        );
+
+        // A missing or extra sibling fails to verify.
+        let mut missing_sibling = multiproof.clone();
+        missing_sibling.siblings.pop();
+        assert_eq!(
+            AddressTree::verify_multiproof(root, &missing_sibling),
+            Err(MerkleStorageError::InvalidMerkleProofLength)
+        );
+        let mut extra_sibling = multiproof;
+        extra_sibling.siblings.push(B256::ZERO);
+        assert_eq!(
+            AddressTree::verify_multiproof(root, &extra_sibling),
+            Err(MerkleStorageError::InvalidMerkleProofLength)
+        );
+    }
+
     #[test]
     fn zero_key_and_value_work_correctly() {
         let mut tree = U256Tree::new();
@@ -782,7 +1519,7 @@ mod tests {
 
         // Define new value: update key1.
         let new_value1 = uint!(150_U256);
-        let new_values = vec![(key1, new_value1)];
+        let new_values = vec![(key1, Some(new_value1))];
 
         // Calculate new root using the function.
         let calculated_root = U256Tree::calculate_new_root(old_root, &proofs, &new_values)
@@ -820,7 +1557,7 @@ mod tests {
 
         // Define new values: update key1, keep key2 same.
         let new_value1 = uint!(150_U256);
-        let new_values = vec![(key1, new_value1)];
+        let new_values = vec![(key1, Some(new_value1))];
 
         // Calculate new root using the function.
         let calculated_root = U256Tree::calculate_new_root(old_root, &proofs, &new_values)
@@ -865,7 +1602,7 @@ mod tests {
         // Define new values: update key1 and key3.
         let new_value1 = uint!(150_U256);
         let new_value3 = uint!(350_U256);
-        let new_values = vec![(key1, new_value1), (key3, new_value3)];
+        let new_values = vec![(key1, Some(new_value1)), (key3, Some(new_value3))];
 
         // Calculate new root using the function.
         let calculated_root = U256Tree::calculate_new_root(old_root, &proofs, &new_values)
@@ -922,7 +1659,7 @@ mod tests {
             vec![MerkleProof::new(key_existing, Some(value_existing), proof_existing.proof)];
 
         // Attempt to update the missing key – this should fail.
-        let new_values = vec![(key_missing, new_value_missing)];
+        let new_values = vec![(key_missing, Some(new_value_missing))];
 
         let result = U256Tree::calculate_new_root(old_root, &proofs, &new_values);
         assert!(result.is_err());
@@ -951,7 +1688,7 @@ mod tests {
         let proof = tree.proof(&key).unwrap();
         let proofs = vec![MerkleProof::new(key, Some(value), proof.proof)];
         // Update to the *same* value.
-        let new_values = vec![(key, value)];
+        let new_values = vec![(key, Some(value))];
 
         let new_root =
             U256Tree::calculate_new_root(old_root, &proofs, &new_values).expect("Should succeed");
@@ -970,7 +1707,11 @@ mod tests {
         let proofs = vec![MerkleProof::new(key, Some(original_value), proof.proof)];
 
         // Provide duplicate updates – the last one should be applied.
-        let new_values = vec![(key, uint!(10_U256)), (key, uint!(20_U256)), (key, uint!(30_U256))];
+        let new_values = vec![
+            (key, Some(uint!(10_U256))),
+            (key, Some(uint!(20_U256))),
+            (key, Some(uint!(30_U256))),
+        ];
 
         let calculated_root =
             U256Tree::calculate_new_root(old_root, &proofs, &new_values).expect("Should succeed");
@@ -983,6 +1724,33 @@ mod tests {
         assert_eq!(calculated_root, expected_root);
     }
 
+    #[test]
+    fn calculate_new_root_deletes_leaves() {
+        let mut tree = U256Tree::new();
+        let key1 = uint!(1_U256);
+        let key2 = uint!(2_U256);
+        let value1 = uint!(100_U256);
+        let value2 = uint!(200_U256);
+        tree.insert(key1, value1).unwrap();
+        tree.insert(key2, value2).unwrap();
+        let old_root = tree.root();
+
+        let proofs = vec![tree.proof(&key1).unwrap(), tree.proof(&key2).unwrap()];
+
+        // Deleting key1 leaves a tree with only key2.
+        let calculated_root = U256Tree::calculate_new_root(old_root, &proofs, &[(key1, None)])
+            .expect("Should succeed");
+        let mut expected_tree = U256Tree::new();
+        expected_tree.insert(key2, value2).unwrap();
+        assert_eq!(calculated_root, expected_tree.root());
+
+        // Deleting every leaf yields the empty root.
+        let calculated_root =
+            U256Tree::calculate_new_root(old_root, &proofs, &[(key1, None), (key2, None)])
+                .expect("Should succeed");
+        assert_eq!(calculated_root, U256Tree::new().root());
+    }
+
     #[test]
     fn key_tracking_works_correctly() {
         let mut tree = U256Tree::new();
@@ -1048,7 +1816,7 @@ mod tests {
             if i % 5 == 0 {
                 // Update selected keys to a new deterministic value.
                 let new_value = U256::from(i * 19 + 11);
-                new_values.push((key, new_value));
+                new_values.push((key, Some(new_value)));
             }
         }
 
@@ -1057,7 +1825,7 @@ mod tests {
 
         // Apply the same updates to the original tree and compare roots.
         for (key, new_value) in new_values {
-            tree.insert(key, new_value).unwrap();
+            tree.insert(key, new_value.unwrap()).unwrap();
         }
         let expected_root = tree.root();
 
@@ -10,7 +10,12 @@ use crate::sol::{CreateProver, Deposit, Receipt, TransactionStatus, TransactionV
 /// `VApp` Receipts represent the succesful execution of a [`crate::transactions::VAppTransaction`].
 ///
 /// These receipts are used to invoke follow up transactions on the settlement contract.
+///
+/// Receipts serialize to JSON with a `type` tag next to the fields of the receipt, e.g.
+/// `{ "type": "deposit", "onchain_tx_id": 1, "status": "Completed", "action": { ... } }`, so that
+/// they can be exposed as-is by an API or explorer.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum VAppReceipt {
     /// A receipt for a [`crate::transactions::VAppTransaction::Deposit`] transaction.
     Deposit(OnchainReceipt<Deposit>),
@@ -65,4 +70,68 @@ impl VAppReceipt {
             },
         }
     }
+
+    /// Returns a one-line human-readable summary of the receipt, e.g.
+    /// `deposit of 100 to 0x… (onchain tx 1)`.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        match self {
+            VAppReceipt::Deposit(receipt) => format!(
+                "deposit of {} to {} (onchain tx {})",
+                receipt.action.amount, receipt.action.account, receipt.onchain_tx_id
+            ),
+            VAppReceipt::CreateProver(receipt) => format!(
+                "create prover {} owned by {} with a staker fee of {} bips (onchain tx {})",
+                receipt.action.prover,
+                receipt.action.owner,
+                receipt.action.stakerFeeBips,
+                receipt.onchain_tx_id
+            ),
+            VAppReceipt::Withdraw(receipt) => {
+                format!("withdraw of {} from {}", receipt.action.amount, receipt.action.account)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_receipt_json() {
+        let account = address!("0x1111111111111111111111111111111111111111");
+        let receipt = VAppReceipt::Deposit(OnchainReceipt {
+            onchain_tx_id: 7,
+            status: TransactionStatus::Completed,
+            action: Deposit { account, amount: U256::from(100) },
+        });
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["type"], "deposit");
+        assert_eq!(json["onchain_tx_id"], 7);
+        assert_eq!(json["action"]["account"], account.to_string());
+        assert_eq!(serde_json::from_value::<VAppReceipt>(json).unwrap(), receipt);
+
+        let receipt = VAppReceipt::Withdraw(OffchainReceipt {
+            status: TransactionStatus::Completed,
+            action: Withdraw { account, amount: U256::from(5) },
+        });
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["type"], "withdraw");
+        assert_eq!(serde_json::from_value::<VAppReceipt>(json).unwrap(), receipt);
+    }
+
+    #[test]
+    fn test_receipt_summary() {
+        let account = address!("0x1111111111111111111111111111111111111111");
+        let receipt = VAppReceipt::Deposit(OnchainReceipt {
+            onchain_tx_id: 7,
+            status: TransactionStatus::Completed,
+            action: Deposit { account, amount: U256::from(100) },
+        });
+        assert_eq!(receipt.summary(), format!("deposit of 100 to {account} (onchain tx 7)"));
+    }
 }
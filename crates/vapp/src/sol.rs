@@ -126,13 +126,13 @@ impl Account {
 
     /// Adds an amount to the balance of the account.
     pub fn add_balance(&mut self, amount: U256) -> Result<(), VAppPanic> {
-        self.balance = u256::add(self.balance, amount)?;
+        self.balance = u256::add_balance(self.balance, amount)?;
         Ok(())
     }
 
     /// Removes an amount from the balance of the account.
     pub fn deduct_balance(&mut self, amount: U256) -> Result<(), VAppPanic> {
-        self.balance = u256::sub(self.balance, amount)?;
+        self.balance = u256::sub_balance(self.balance, amount)?;
         Ok(())
     }
 
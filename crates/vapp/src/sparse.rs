@@ -3,10 +3,7 @@
 //! This module contains implementations of the [`SparseStorage`] data structure, which is used to
 //! store and retrieve data inside the vApp while keeping only the used leaves  
 
-use std::{
-    collections::{btree_map::Entry, BTreeMap, BTreeSet},
-    marker::PhantomData,
-};
+use std::collections::{btree_map::Entry, BTreeMap};
 
 use alloy_primitives::{B256, U256};
 use serde::{Deserialize, Serialize};
@@ -21,11 +18,13 @@ use crate::{
 ///
 /// Similar to `MerkleStore`, this uses U256 indices internally and converts keys using the
 /// [`crate::storage::StorageKey::index()`] method for efficient storage.
+///
+/// The witnessed keys are serialized together with their indices so that [`SparseStorage::diff`]
+/// can still return them after a round trip.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SparseStorage<K: StorageKey, V: StorageValue> {
     inner: BTreeMap<U256, V>,
-    witnessed_keys: BTreeSet<U256>,
-    _key: PhantomData<K>,
+    witnessed_keys: BTreeMap<U256, K>,
 }
 
 /// Errors that can occur during sparse storage operations.
@@ -56,12 +55,12 @@ pub enum SparseStorageError {
 
 impl<K: StorageKey, V: StorageValue> Storage<K, V> for SparseStorage<K, V> {
     fn new() -> Self {
-        Self { inner: BTreeMap::new(), witnessed_keys: BTreeSet::new(), _key: PhantomData }
+        Self { inner: BTreeMap::new(), witnessed_keys: BTreeMap::new() }
     }
 
     fn insert(&mut self, key: K, value: V) -> Result<(), StorageError> {
         let index = key.index();
-        if !self.witnessed_keys.contains(&index) {
+        if !self.witnessed_keys.contains_key(&index) {
             return Err(StorageError::KeyNotAllowed);
         }
 
@@ -69,9 +68,20 @@ impl<K: StorageKey, V: StorageValue> Storage<K, V> for SparseStorage<K, V> {
         Ok(())
     }
 
+    fn insert_many(&mut self, items: impl IntoIterator<Item = (K, V)>) -> Result<(), StorageError> {
+        let items: Vec<(U256, V)> =
+            items.into_iter().map(|(key, value)| (key.index(), value)).collect();
+        if items.iter().any(|(index, _)| !self.witnessed_keys.contains_key(index)) {
+            return Err(StorageError::KeyNotAllowed);
+        }
+
+        self.inner.extend(items);
+        Ok(())
+    }
+
     fn entry(&mut self, key: K) -> Result<Entry<'_, U256, V>, StorageError> {
         let index = key.index();
-        if !self.witnessed_keys.contains(&index) {
+        if !self.witnessed_keys.contains_key(&index) {
             return Err(StorageError::KeyNotAllowed);
         }
 
@@ -80,7 +90,7 @@ impl<K: StorageKey, V: StorageValue> Storage<K, V> for SparseStorage<K, V> {
 
     fn get(&mut self, key: &K) -> Result<Option<&V>, StorageError> {
         let index = key.index();
-        if !self.witnessed_keys.contains(&index) {
+        if !self.witnessed_keys.contains_key(&index) {
             return Err(StorageError::KeyNotAllowed);
         }
 
@@ -89,12 +99,16 @@ impl<K: StorageKey, V: StorageValue> Storage<K, V> for SparseStorage<K, V> {
 
     fn get_mut(&mut self, key: &K) -> Result<Option<&mut V>, StorageError> {
         let index = key.index();
-        if !self.witnessed_keys.contains(&index) {
+        if !self.witnessed_keys.contains_key(&index) {
             return Err(StorageError::KeyNotAllowed);
         }
 
         Ok(self.inner.get_mut(&index))
     }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&U256, &V)> + '_> {
+        Box::new(self.iter_raw())
+    }
 }
 
 impl<K: StorageKey, V: StorageValue> SparseStorage<K, V> {
@@ -106,11 +120,34 @@ impl<K: StorageKey, V: StorageValue> SparseStorage<K, V> {
 
     /// Iterate over the raw key-value pairs (returns U256 indices).
     pub fn iter_raw(&self) -> impl Iterator<Item = (&U256, &V)> {
-        self.inner.iter().filter(|(key, _)| self.witnessed_keys.contains(key))
+        self.inner.iter().filter(|(key, _)| self.witnessed_keys.contains_key(key))
+    }
+
+    /// Merge the values and witnessed keys of another storage into this one.
+    ///
+    /// This combines the updates of storages recovered against the same root. If both storages
+    /// hold a value at the same index, the value from `other` wins, as if its updates were applied
+    /// after the ones of this storage.
+    pub fn merge(&mut self, other: &SparseStorage<K, V>) {
+        self.witnessed_keys
+            .extend(other.witnessed_keys.iter().map(|(index, key)| (*index, key.clone())));
+        self.inner.extend(other.iter_raw().map(|(index, value)| (*index, value.clone())));
     }
 }
 
 impl<K: StorageKey, V: StorageValue + PartialEq> SparseStorage<K, V> {
+    /// Returns the values of this storage that differ from those of a baseline, in index order.
+    ///
+    /// A value differs if the baseline holds no value or a different value at its index. Values
+    /// that are only held by the baseline aren't included.
+    #[must_use]
+    pub fn diff(&self, baseline: &SparseStorage<K, V>) -> Vec<(K, V)> {
+        self.iter_raw()
+            .filter(|(index, value)| baseline.inner.get(index) != Some(*value))
+            .map(|(index, value)| (self.witnessed_keys[index].clone(), value.clone()))
+            .collect()
+    }
+
     /// Recovers the state of the sparse store using merkle proofs against a given root.
     ///
     /// This function checks that all values currently stored in the sparse store
@@ -135,10 +172,10 @@ impl<K: StorageKey, V: StorageValue + PartialEq> SparseStorage<K, V> {
             // Add the key to the set of witnessed keys.
             //
             // We enforce that only witnessed keys can be used with the [`Storage`] trait.
-            if self.witnessed_keys.contains(&proof.key.index()) {
+            if self.witnessed_keys.contains_key(&proof.key.index()) {
                 return Err(SparseStorageError::DuplicateProof { index: proof.key.index() });
             }
-            self.witnessed_keys.insert(proof.key.index());
+            self.witnessed_keys.insert(proof.key.index(), proof.key.clone());
 
             // Verify the proof against the root.
             if MerkleStorage::<K, V, H>::verify_proof(root, proof).is_err() {
@@ -164,7 +201,7 @@ impl<K: StorageKey, V: StorageValue> Default for SparseStorage<K, V> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::merkle::MerkleStorage;
+    use crate::{merkle::MerkleStorage, storage::SignerKey};
     use alloy_primitives::{uint, Address, Keccak256, U256};
 
     type U256Tree = MerkleStorage<U256, U256>;
@@ -390,4 +427,140 @@ mod tests {
         // Verification should now succeed because non-inclusion proofs are accepted.
         assert!(sparse_store.recover::<Keccak256>(root, &proofs).is_ok());
     }
+
+    #[test]
+    fn contains_key_and_iter_only_see_witnessed_keys() {
+        let mut merkle_tree = U256Tree::new();
+        let (stored, empty, unwitnessed) = (uint!(1_U256), uint!(2_U256), uint!(3_U256));
+        merkle_tree.insert(stored, uint!(10_U256)).unwrap();
+        merkle_tree.insert(unwitnessed, uint!(30_U256)).unwrap();
+
+        let root = merkle_tree.root();
+        let proofs = vec![merkle_tree.proof(&stored).unwrap(), merkle_tree.proof(&empty).unwrap()];
+        let mut sparse_store = U256SparseStore::new();
+        sparse_store.recover::<Keccak256>(root, &proofs).unwrap();
+
+        assert!(sparse_store.contains_key(&stored).unwrap());
+        assert!(!sparse_store.contains_key(&empty).unwrap());
+        assert_eq!(sparse_store.contains_key(&unwitnessed), Err(StorageError::KeyNotAllowed));
+
+        let leaves: Vec<_> = sparse_store.iter().collect();
+        assert_eq!(leaves, [(&stored, &uint!(10_U256))]);
+    }
+
+    #[test]
+    fn insert_many_rejects_unwitnessed_keys() {
+        let mut merkle_tree = U256Tree::new();
+        let (key1, key2) = (uint!(1_U256), uint!(2_U256));
+        let root = merkle_tree.root();
+        let proofs = vec![merkle_tree.proof(&key1).unwrap(), merkle_tree.proof(&key2).unwrap()];
+        let mut sparse_store = U256SparseStore::new();
+        sparse_store.recover::<Keccak256>(root, &proofs).unwrap();
+
+        // A single unwitnessed key rejects the whole batch.
+        let result =
+            sparse_store.insert_many([(key1, uint!(10_U256)), (uint!(3_U256), U256::ZERO)]);
+        assert_eq!(result, Err(StorageError::KeyNotAllowed));
+        assert!(sparse_store.is_empty());
+
+        sparse_store.insert_many([(key1, uint!(10_U256)), (key2, uint!(20_U256))]).unwrap();
+        assert_eq!(sparse_store.get(&key2).unwrap(), Some(&uint!(20_U256)));
+    }
+
+    #[test]
+    fn merge_and_diff() {
+        let mut merkle_tree = U256Tree::new();
+        let (key1, key2, key3) = (uint!(1_U256), uint!(2_U256), uint!(3_U256));
+        merkle_tree.insert(key1, uint!(10_U256)).unwrap();
+        merkle_tree.insert(key2, uint!(20_U256)).unwrap();
+        let root = merkle_tree.root();
+        let mut proof = |key| merkle_tree.proof(&key).unwrap();
+
+        let mut baseline = U256SparseStore::new();
+        baseline.recover::<Keccak256>(root, &[proof(key1), proof(key2), proof(key3)]).unwrap();
+
+        // Two update sets computed independently against the same root.
+        let mut left = U256SparseStore::new();
+        left.recover::<Keccak256>(root, &[proof(key1), proof(key2)]).unwrap();
+        left.insert(key1, uint!(11_U256)).unwrap();
+        let mut right = U256SparseStore::new();
+        right.recover::<Keccak256>(root, &[proof(key2), proof(key3)]).unwrap();
+        right.insert(key2, uint!(22_U256)).unwrap();
+        right.insert(key3, uint!(33_U256)).unwrap();
+
+        // The values of `right` win, and its witnessed keys become writable.
+        left.merge(&right);
+        assert_eq!(left.get(&key2).unwrap(), Some(&uint!(22_U256)));
+        left.insert(key3, uint!(34_U256)).unwrap();
+
+        assert_eq!(
+            left.diff(&baseline),
+            [(key1, uint!(11_U256)), (key2, uint!(22_U256)), (key3, uint!(34_U256))]
+        );
+        assert!(baseline.diff(&baseline).is_empty());
+    }
+
+    #[test]
+    fn merge_and_diff_conflicting_writes() {
+        let mut merkle_tree = U256Tree::new();
+        let key = uint!(1_U256);
+        merkle_tree.insert(key, uint!(10_U256)).unwrap();
+        let root = merkle_tree.root();
+        let proofs = vec![merkle_tree.proof(&key).unwrap()];
+
+        let mut baseline = U256SparseStore::new();
+        baseline.recover::<Keccak256>(root, &proofs).unwrap();
+
+        // Both update sets write the same key.
+        let mut left = baseline.clone();
+        left.insert(key, uint!(11_U256)).unwrap();
+        let mut right = baseline.clone();
+        right.insert(key, uint!(12_U256)).unwrap();
+
+        // Whichever side is merged in wins.
+        let mut merged = left.clone();
+        merged.merge(&right);
+        assert_eq!(merged.get(&key).unwrap(), Some(&uint!(12_U256)));
+        assert_eq!(merged.diff(&baseline), [(key, uint!(12_U256))]);
+        assert_eq!(merged.diff(&right), []);
+        assert_eq!(merged.diff(&left), [(key, uint!(12_U256))]);
+
+        let mut merged = right.clone();
+        merged.merge(&left);
+        assert_eq!(merged.get(&key).unwrap(), Some(&uint!(11_U256)));
+        assert_eq!(merged.diff(&baseline), [(key, uint!(11_U256))]);
+    }
+
+    #[test]
+    fn serde_round_trip_keeps_witnessed_keys() {
+        let mut merkle_tree = MerkleStorage::<SignerKey, bool>::new();
+        let key = (Address::from([1u8; 20]), Address::from([2u8; 20]));
+        let root = merkle_tree.root();
+        let proofs = vec![merkle_tree.proof(&key).unwrap()];
+
+        let mut baseline = SparseStorage::<SignerKey, bool>::new();
+        baseline.recover::<Keccak256>(root, &proofs).unwrap();
+        let mut updated = baseline.clone();
+        updated.insert(key, true).unwrap();
+
+        let json = serde_json::to_string(&updated).unwrap();
+        let updated: SparseStorage<SignerKey, bool> = serde_json::from_str(&json).unwrap();
+        assert_eq!(updated.diff(&baseline), [(key, true)]);
+    }
+
+    #[test]
+    fn diff_returns_original_keys() {
+        let mut merkle_tree = MerkleStorage::<SignerKey, bool>::new();
+        let key = (Address::from([1u8; 20]), Address::from([2u8; 20]));
+        let root = merkle_tree.root();
+        let proofs = vec![merkle_tree.proof(&key).unwrap()];
+
+        let mut baseline = SparseStorage::<SignerKey, bool>::new();
+        baseline.recover::<Keccak256>(root, &proofs).unwrap();
+        let mut updated = baseline.clone();
+        updated.insert(key, true).unwrap();
+
+        // The key is returned as is, not as its hashed index.
+        assert_eq!(updated.diff(&baseline), [(key, true)]);
+    }
 }
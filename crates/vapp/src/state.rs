@@ -4,20 +4,24 @@
 
 use alloy_primitives::{Address, B256, U256};
 use eyre::Result;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
 use tracing::{debug, info};
 
-use spn_network_types::{ExecutionStatus, HashableWithSender, ProofMode, TransactionVariant};
+use spn_network_types::{
+    ExecutionStatus, HashableWithSender, ProofMode, RequestProofRequestBody, TransactionVariant,
+};
 
 use crate::{
     errors::{VAppError, VAppPanic, VAppRevert},
-    fee::{fee, PROTOCOL_FEE_BIPS},
+    events::{BalanceChangeReason, VAppEvent},
+    fee::{fee, request_cost, PROTOCOL_FEE_BIPS},
     merkle::{MerkleStorage, MerkleTreeHasher},
     receipts::{OffchainReceipt, OnchainReceipt, VAppReceipt},
     signing::{eth_sign_verify, verify_signed_message},
     sol::{Account, TransactionStatus, VAppStateContainer, Withdraw},
     sparse::SparseStorage,
-    storage::{RequestId, Storage},
+    storage::{RequestId, SignerKey, Storage},
     transactions::{OnchainTransaction, VAppTransaction},
     u256,
     utils::{address, bytes_to_words_be, tx_variant},
@@ -38,7 +42,11 @@ enum ExecuteOutcome {
 ///
 /// This state is used to keep track of the accounts, requests, and other data in the vApp.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VAppState<A: Storage<Address, Account>, R: Storage<RequestId, bool>> {
+pub struct VAppState<
+    A: Storage<Address, Account>,
+    R: Storage<RequestId, bool>,
+    S: Storage<SignerKey, bool>,
+> {
     /// The domain separator, used to avoid replay attacks.
     ///
     /// Encoded as a bytes32 hash of the [`alloy_sol_types::Eip712Domain`] domain.
@@ -53,11 +61,11 @@ pub struct VAppState<A: Storage<Address, Account>, R: Storage<RequestId, bool>>
     pub onchain_tx_id: u64,
     /// The current L1 block number.
     ///
-    /// Keeps track of the last seen block number from a [`VAppEvent`].
+    /// Keeps track of the last seen block number from an [`OnchainTransaction`].
     pub onchain_block: u64,
     /// The current L1 log index.
     ///
-    /// Keeps track of the last seen log index from a [`VAppEvent`].
+    /// Keeps track of the last seen log index from an [`OnchainTransaction`].
     pub onchain_log_index: u64,
     /// The accounts in the system for both requesters and provers.
     ///
@@ -67,11 +75,82 @@ pub struct VAppState<A: Storage<Address, Account>, R: Storage<RequestId, bool>>
     ///
     /// Keeps track of which request IDs have been processed to avoid replay attacks.
     pub transactions: R,
+    /// The additional signers of the provers, on top of their delegated signer.
+    ///
+    /// Keyed by `(prover, signer)` rather than stored in the [`Account`], so that the encoding of
+    /// the accounts stays the same.
+    #[serde(default)]
+    pub signers: S,
 }
 
-impl VAppState<MerkleStorage<Address, Account>, MerkleStorage<RequestId, bool>> {
+/// A snapshot of a [`VAppState`] taken with [`VAppState::checkpoint`].
+///
+/// Restoring it with [`VAppState::restore`] rolls back every change made to the state since the
+/// snapshot was taken, including the counters and the keys touched in the state trees.
+#[derive(Debug, Clone)]
+pub struct StateCheckpoint<
+    A: Storage<Address, Account>,
+    R: Storage<RequestId, bool>,
+    S: Storage<SignerKey, bool>,
+> {
+    /// The state at the time of the checkpoint.
+    state: VAppState<A, R, S>,
+}
+
+/// The outcomes of the transactions executed by [`VAppState::execute_batch`], in order.
+pub type BatchOutcome = Vec<Result<Option<VAppReceipt>, VAppRevert>>;
+
+/// The projected outcome of a [`VAppTransaction`], computed by [`VAppState::simulate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult {
+    /// The receipt the transaction would produce, or the reason it would revert.
+    pub outcome: Result<Option<VAppReceipt>, VAppRevert>,
+    /// The balance changes the transaction would make, sorted by account.
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+/// Errors that can occur when importing a [`VAppState`] with [`VAppState::import_json`].
+#[derive(Debug, Error)]
+#[allow(missing_docs)]
+pub enum StateImportError {
+    #[error("Failed to deserialize state: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("State root mismatch: exported {expected}, computed {actual}")]
+    RootMismatch { expected: B256, actual: B256 },
+}
+
+/// A [`VAppState`] exported with [`VAppState::export_json`], along with its root.
+#[derive(Serialize, Deserialize)]
+struct StateExport<S> {
+    root: B256,
+    state: S,
+}
+
+/// A projected change to the balance of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+    /// The account whose balance would change.
+    pub account: Address,
+    /// The balance before the transaction.
+    pub before: U256,
+    /// The balance after the transaction.
+    pub after: U256,
+}
+
+impl<T: MerkleTreeHasher>
+    VAppState<
+        MerkleStorage<Address, Account, T>,
+        MerkleStorage<RequestId, bool, T>,
+        MerkleStorage<SignerKey, bool, T>,
+    >
+{
     /// Computes the state root.
-    pub fn root<H: MerkleTreeHasher>(&mut self) -> B256 {
+    ///
+    /// The state trees are hashed with their own hasher `T`, while the state container is hashed
+    /// with `H`.
+    #[must_use]
+    pub fn root<H: MerkleTreeHasher>(&self) -> B256 {
         let state = VAppStateContainer {
             domain: self.domain,
             txId: self.tx_id,
@@ -81,14 +160,126 @@ impl VAppState<MerkleStorage<Address, Account>, MerkleStorage<RequestId, bool>>
             accountsRoot: self.accounts.root(),
             transactionsRoot: self.transactions.root(),
         };
-        H::hash(&state)
+        let signers_root = (!self.signers.is_empty()).then(|| self.signers.root());
+        state_root::<H>(&state, signers_root)
+    }
+
+    /// Simulates a [`VAppTransaction`] without mutating the state.
+    ///
+    /// The transaction is executed against a copy of the state, so it goes through exactly the
+    /// same validation and fee computation as [`VAppState::execute`]. Reverts, like an
+    /// insufficient balance, are returned in [`SimulationResult::outcome`].
+    pub fn simulate<V: VAppVerifier>(
+        &self,
+        event: &VAppTransaction,
+    ) -> Result<SimulationResult, VAppPanic>
+    where
+        T: Clone,
+    {
+        let mut simulated = self.clone();
+        simulated.accounts.clear_key_tracking();
+        let outcome = match simulated.execute::<V>(event) {
+            Ok(receipt) => Ok(receipt),
+            Err(VAppError::Revert(revert)) => Err(revert),
+            Err(VAppError::Panic(panic)) => return Err(panic),
+        };
+
+        let balance_of = |accounts: &MerkleStorage<Address, Account, T>, account| {
+            accounts.get_untracked(account).map_or(U256::ZERO, Account::get_balance)
+        };
+        let balance_changes = simulated
+            .accounts
+            .get_touched_keys()
+            .iter()
+            .map(|account| BalanceChange {
+                account: *account,
+                before: balance_of(&self.accounts, account),
+                after: balance_of(&simulated.accounts, account),
+            })
+            .filter(|change| change.before != change.after)
+            .collect();
+
+        Ok(SimulationResult { outcome, balance_changes })
+    }
+
+    /// Returns the largest amount that the account can withdraw while paying the given fee.
+    ///
+    /// This is the balance of the account minus the fee, or zero if the fee exceeds the balance,
+    /// so that a withdraw for the returned amount doesn't revert with
+    /// [`VAppRevert::InsufficientWithdrawBalance`]. It assumes the account withdraws for itself;
+    /// when the owner of a prover withdraws on its behalf, the owner pays the fee instead.
+    #[must_use]
+    pub fn max_withdrawable(&self, account: &Address, fee: U256) -> U256 {
+        let balance = self.accounts.get_untracked(account).map_or(U256::ZERO, Account::get_balance);
+        balance.saturating_sub(fee)
+    }
+
+    /// Returns the balance of a treasury, which collects the protocol fees and punishments of the
+    /// requests that name it.
+    ///
+    /// This is the balance of the treasury's account, so it also counts deposits and transfers to
+    /// the treasury and drops when the treasury withdraws. It only equals the protocol revenue of
+    /// a treasury that receives nothing else. The state doesn't keep per-category totals, since
+    /// they aren't part of the state root; accumulate them from the events returned by
+    /// [`VAppState::execute_with_events`] with a [`FeeBreakdown`](crate::events::FeeBreakdown)
+    /// instead.
+    #[must_use]
+    pub fn treasury_balance(&self, treasury: &Address) -> U256 {
+        self.accounts.get_untracked(treasury).map_or(U256::ZERO, Account::get_balance)
+    }
+
+    /// Exports the state as JSON for debugging, e.g. to replay a state-root mismatch locally.
+    ///
+    /// The export includes the accounts, the processed transactions, the counters, and the root
+    /// computed with `H`, so that [`VAppState::import_json`] can check that the state is intact.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state can't be serialized, which doesn't happen for the state trees.
+    #[must_use]
+    pub fn export_json<H: MerkleTreeHasher>(&self) -> String
+    where
+        Self: Serialize,
+    {
+        let export = StateExport { root: self.root::<H>(), state: self };
+        serde_json::to_string_pretty(&export).expect("failed to serialize state")
+    }
+
+    /// Imports a state exported with [`VAppState::export_json`].
+    ///
+    /// The root of the imported state is recomputed with `H` and checked against the exported
+    /// one, to catch exports that were truncated or edited by hand. Since the exported root comes
+    /// from the same file, this doesn't prove that the state is the one committed onchain.
+    pub fn import_json<H: MerkleTreeHasher>(json: &str) -> Result<Self, StateImportError>
+    where
+        Self: DeserializeOwned,
+    {
+        let export: StateExport<Self> = serde_json::from_str(json)?;
+        let actual = export.state.root::<H>();
+        if actual != export.root {
+            return Err(StateImportError::RootMismatch { expected: export.root, actual });
+        }
+        Ok(export.state)
     }
 }
 
-impl VAppState<SparseStorage<Address, Account>, SparseStorage<RequestId, bool>> {
+impl
+    VAppState<
+        SparseStorage<Address, Account>,
+        SparseStorage<RequestId, bool>,
+        SparseStorage<SignerKey, bool>,
+    >
+{
     /// Computes the state root.
+    ///
+    /// The signers root is `None` if no prover has additional signers.
     #[must_use]
-    pub fn root<H: MerkleTreeHasher>(&self, account_root: B256, transactions_root: B256) -> B256 {
+    pub fn root<H: MerkleTreeHasher>(
+        &self,
+        account_root: B256,
+        transactions_root: B256,
+        signers_root: Option<B256>,
+    ) -> B256 {
         let state = VAppStateContainer {
             domain: self.domain,
             txId: self.tx_id,
@@ -98,11 +289,73 @@ impl VAppState<SparseStorage<Address, Account>, SparseStorage<RequestId, bool>>
             accountsRoot: account_root,
             transactionsRoot: transactions_root,
         };
-        H::hash(&state)
+        state_root::<H>(&state, signers_root)
+    }
+}
+
+/// Computes the state root from the state container and the root of the additional signers.
+///
+/// The signers root is only committed to once a prover has additional signers, so that states
+/// without any keep the root they had before additional signers were introduced.
+fn state_root<H: MerkleTreeHasher>(state: &VAppStateContainer, signers_root: Option<B256>) -> B256 {
+    let root = H::hash(state);
+    match signers_root {
+        Some(signers_root) => H::hash_pair(&root, &signers_root),
+        None => root,
     }
 }
 
-impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R> {
+impl<
+        A: Storage<Address, Account> + Clone,
+        R: Storage<RequestId, bool> + Clone,
+        S: Storage<SignerKey, bool> + Clone,
+    > VAppState<A, R, S>
+{
+    /// Takes a snapshot of the state that it can later be rolled back to with
+    /// [`VAppState::restore`].
+    ///
+    /// This clones the state trees, so its cost grows with the size of the state.
+    #[must_use]
+    pub fn checkpoint(&self) -> StateCheckpoint<A, R, S> {
+        StateCheckpoint { state: self.clone() }
+    }
+
+    /// Rolls the state back to the given checkpoint.
+    pub fn restore(&mut self, checkpoint: StateCheckpoint<A, R, S>) {
+        *self = checkpoint.state;
+    }
+
+    /// Executes a batch of [`VAppTransaction`]s atomically.
+    ///
+    /// Reverts are valid state transitions, so they are returned alongside the receipts and
+    /// execution continues. If any transaction panics, the state is rolled back to before the
+    /// batch and the panic is returned with the index of the transaction that caused it.
+    ///
+    /// The checkpoint clones the state, so its cost grows with the size of the state. In the STF
+    /// program, the state only holds the witnessed leaves, which keeps the clone small.
+    pub fn execute_batch<V: VAppVerifier>(
+        &mut self,
+        events: &[VAppTransaction],
+    ) -> Result<BatchOutcome, (usize, VAppPanic)> {
+        let checkpoint = self.checkpoint();
+        let mut outcomes = Vec::with_capacity(events.len());
+        for (index, event) in events.iter().enumerate() {
+            match self.execute::<V>(event) {
+                Ok(receipt) => outcomes.push(Ok(receipt)),
+                Err(VAppError::Revert(revert)) => outcomes.push(Err(revert)),
+                Err(VAppError::Panic(panic)) => {
+                    self.restore(checkpoint);
+                    return Err((index, panic));
+                }
+            }
+        }
+        Ok(outcomes)
+    }
+}
+
+impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>, S: Storage<SignerKey, bool>>
+    VAppState<A, R, S>
+{
     /// Creates a new [`VAppState`].
     #[must_use]
     pub fn new(domain: B256) -> Self {
@@ -114,9 +367,23 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
             onchain_log_index: 0,
             accounts: A::new(),
             transactions: R::new(),
+            signers: S::new(),
         }
     }
 
+    /// Returns the largest punishment that a request can be charged if it is unexecutable.
+    ///
+    /// This is the most the requester could have paid for the request, i.e. its max price per PGU
+    /// times its gas limit plus its base fee. A clear whose punishment exceeds it panics with
+    /// [`VAppPanic::PunishmentExceedsMaxCost`], so executors should cap punishments at this value.
+    #[allow(clippy::unused_self)]
+    pub fn max_punishment(&self, request: &RequestProofRequestBody) -> Result<U256, VAppPanic> {
+        let base_fee = request.base_fee.parse::<U256>().map_err(VAppPanic::U256ParseError)?;
+        let max_price_per_pgu =
+            request.max_price_per_pgu.parse::<U256>().map_err(VAppPanic::U256ParseError)?;
+        request_cost(max_price_per_pgu, request.gas_limit, base_fee)
+    }
+
     /// Validates a [`OnchainTransaction`].
     ///
     /// Checks for basic invariants such as the EIP-712 domain being initialized and that the
@@ -161,10 +428,44 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
         &mut self,
         event: &VAppTransaction,
     ) -> Result<Option<VAppReceipt>, VAppError> {
-        match self.execute_inner::<V>(event) {
+        self.execute_with_verifier(event, &V::default())
+    }
+
+    /// Executes a [`VAppTransaction`] like [`VAppState::execute`], verifying proofs with the
+    /// given verifier instead of a default one.
+    ///
+    /// This allows verifiers that are configured at runtime, like a
+    /// [`crate::verifier::SelectiveVerifier`].
+    pub fn execute_with_verifier<V: VAppVerifier>(
+        &mut self,
+        event: &VAppTransaction,
+        verifier: &V,
+    ) -> Result<Option<VAppReceipt>, VAppError> {
+        self.apply(event, verifier).map(|(receipt, _)| receipt)
+    }
+
+    /// Executes a [`VAppTransaction`] like [`VAppState::execute`], also returning the
+    /// [`VAppEvent`]s that describe the state changes it made.
+    ///
+    /// Reverted transactions don't emit any events.
+    pub fn execute_with_events<V: VAppVerifier>(
+        &mut self,
+        event: &VAppTransaction,
+    ) -> Result<(Option<VAppReceipt>, Vec<VAppEvent>), VAppError> {
+        self.apply(event, &V::default())
+    }
+
+    /// Applies a [`VAppTransaction`], advancing the cursor unless it panics.
+    fn apply<V: VAppVerifier>(
+        &mut self,
+        event: &VAppTransaction,
+        verifier: &V,
+    ) -> Result<(Option<VAppReceipt>, Vec<VAppEvent>), VAppError> {
+        let mut events = Vec::new();
+        match self.execute_inner(event, verifier, &mut events) {
             Ok(ExecuteOutcome::Applied(receipt)) => {
                 self.tx_id += 1;
-                Ok(receipt)
+                Ok((receipt, events))
             }
             Ok(ExecuteOutcome::Reverted(revert)) => {
                 self.tx_id += 1;
@@ -179,6 +480,8 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
     fn execute_inner<V: VAppVerifier>(
         &mut self,
         event: &VAppTransaction,
+        verifier: &V,
+        events: &mut Vec<VAppEvent>,
     ) -> Result<ExecuteOutcome, VAppPanic> {
         match event {
             VAppTransaction::Deposit(deposit) => {
@@ -204,6 +507,11 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                     .entry(deposit.action.account)?
                     .or_default()
                     .add_balance(deposit.action.amount)?;
+                events.push(VAppEvent::Credited {
+                    account: deposit.action.account,
+                    amount: deposit.action.amount,
+                    reason: BalanceChangeReason::Deposit,
+                });
 
                 // Return the deposit action.
                 return Ok(ExecuteOutcome::Applied(Some(VAppReceipt::Deposit(OnchainReceipt {
@@ -237,6 +545,11 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                     .set_owner(prover.action.owner)
                     .set_signer(prover.action.owner)
                     .set_staker_fee_bips(prover.action.stakerFeeBips);
+                events.push(VAppEvent::ProverCreated {
+                    prover: prover.action.prover,
+                    owner: prover.action.owner,
+                    staker_fee_bips: prover.action.stakerFeeBips,
+                });
 
                 // Return the set delegated signer action.
                 return Ok(ExecuteOutcome::Applied(Some(VAppReceipt::CreateProver(
@@ -259,7 +572,12 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 // Verify the variant.
                 debug!("verify variant");
                 let variant = tx_variant(body.variant)?;
-                if variant != TransactionVariant::DelegateVariant {
+                if !matches!(
+                    variant,
+                    TransactionVariant::DelegateVariant
+                        | TransactionVariant::AddDelegateVariant
+                        | TransactionVariant::RemoveDelegateVariant
+                ) {
                     return Err(VAppPanic::InvalidTransactionVariant);
                 }
 
@@ -286,7 +604,7 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 let delegate_id = body
                     .hash_with_signer(prover_owner.as_slice())
                     .map_err(|_| VAppPanic::HashingBodyFailed)?;
-                if *self.transactions.entry(delegate_id)?.or_default() {
+                if self.transactions.get(&delegate_id)?.copied().unwrap_or_default() {
                     return Err(VAppPanic::TransactionAlreadyProcessed {
                         id: hex::encode(delegate_id),
                     });
@@ -343,22 +661,53 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 // Deduct the delegation fee from the prover owner.
                 debug!("deduct delegation fee from prover owner");
                 self.accounts.entry(prover_owner)?.or_default().deduct_balance(auctioneer_fee)?;
+                events.push(VAppEvent::Debited {
+                    account: prover_owner,
+                    amount: auctioneer_fee,
+                    reason: BalanceChangeReason::AuctioneerFee,
+                });
 
                 // Transfer the fee to the auctioneer.
                 debug!("transfer delegation fee to auctioneer");
                 self.accounts.entry(auctioneer)?.or_default().add_balance(auctioneer_fee)?;
+                events.push(VAppEvent::Credited {
+                    account: auctioneer,
+                    amount: auctioneer_fee,
+                    reason: BalanceChangeReason::AuctioneerFee,
+                });
 
                 // Extract the delegate address.
                 debug!("extract delegate address");
                 let delegate = Address::try_from(body.delegate.as_slice())
                     .map_err(|_| VAppPanic::AddressDeserializationFailed)?;
 
-                // Set the delegate as a signer for the prover's account.
-                debug!("set delegate as signer");
+                // Update the signers of the prover.
+                //
+                // A delegation replaces the delegated signer of the prover's account, while the add
+                // and remove variants manage its additional signers, so that several machines can
+                // sign for the same prover without sharing a key.
+                debug!("update prover signers");
                 let Some(prover_account) = self.accounts.get_mut(&prover)? else {
                     return Err(VAppPanic::ProverDoesNotExist { prover });
                 };
-                prover_account.set_signer(delegate);
+                match variant {
+                    TransactionVariant::AddDelegateVariant => {
+                        self.signers.insert((prover, delegate), true)?;
+                        events.push(VAppEvent::SignerAdded { prover, signer: delegate });
+                    }
+                    TransactionVariant::RemoveDelegateVariant => {
+                        // Only write the key if the signer was added, so that removing an unknown
+                        // signer doesn't create a leaf.
+                        if self.signers.get(&(prover, delegate))?.copied().unwrap_or_default() {
+                            self.signers.insert((prover, delegate), false)?;
+                        }
+                        events.push(VAppEvent::SignerRemoved { prover, signer: delegate });
+                    }
+                    _ => {
+                        prover_account.set_signer(delegate);
+                        events.push(VAppEvent::SignerDelegated { prover, signer: delegate });
+                    }
+                }
 
                 // No action returned since delegation is off-chain.
                 return Ok(ExecuteOutcome::Applied(None));
@@ -399,7 +748,7 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 let transfer_id = body
                     .hash_with_signer(from.as_slice())
                     .map_err(|_| VAppPanic::HashingBodyFailed)?;
-                if *self.transactions.entry(transfer_id)?.or_default() {
+                if self.transactions.get(&transfer_id)?.copied().unwrap_or_default() {
                     return Err(VAppPanic::TransactionAlreadyProcessed {
                         id: hex::encode(transfer_id),
                     });
@@ -445,14 +794,34 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 // Transfer the amount from the transferer to the recipient.
                 info!("├── Account({}): - {} $PROVE", from, amount);
                 self.accounts.entry(from)?.or_default().deduct_balance(amount)?;
+                events.push(VAppEvent::Debited {
+                    account: from,
+                    amount,
+                    reason: BalanceChangeReason::Transfer,
+                });
                 info!("├── Account({}): + {} $PROVE", to, amount);
                 self.accounts.entry(to)?.or_default().add_balance(amount)?;
+                events.push(VAppEvent::Credited {
+                    account: to,
+                    amount,
+                    reason: BalanceChangeReason::Transfer,
+                });
 
                 // Deduct and transfer the auctioneer fee.
                 info!("├── Account({}): - {} $PROVE (fee)", from, auctioneer_fee);
                 self.accounts.entry(from)?.or_default().deduct_balance(auctioneer_fee)?;
+                events.push(VAppEvent::Debited {
+                    account: from,
+                    amount: auctioneer_fee,
+                    reason: BalanceChangeReason::AuctioneerFee,
+                });
                 info!("└── Auctioneer({}): + {} $PROVE (fee)", auctioneer, auctioneer_fee);
                 self.accounts.entry(auctioneer)?.or_default().add_balance(auctioneer_fee)?;
+                events.push(VAppEvent::Credited {
+                    account: auctioneer,
+                    amount: auctioneer_fee,
+                    reason: BalanceChangeReason::AuctioneerFee,
+                });
 
                 return Ok(ExecuteOutcome::Applied(None));
             }
@@ -492,7 +861,7 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 let withdraw_id = body
                     .hash_with_signer(from.as_slice())
                     .map_err(|_| VAppPanic::HashingBodyFailed)?;
-                if *self.transactions.entry(withdraw_id)?.or_default() {
+                if self.transactions.get(&withdraw_id)?.copied().unwrap_or_default() {
                     return Err(VAppPanic::TransactionAlreadyProcessed {
                         id: hex::encode(withdraw_id),
                     });
@@ -554,9 +923,19 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                     // Deduct the amount from the withdrawing account.
                     info!("├── Account({}): - {} $PROVE", account, amount);
                     self.accounts.entry(account)?.or_default().deduct_balance(amount)?;
+                    events.push(VAppEvent::Debited {
+                        account,
+                        amount,
+                        reason: BalanceChangeReason::Withdraw,
+                    });
                     // Deduct the fee from the withdrawing account.
                     info!("├── Account({}): - {} $PROVE (fee)", account, auctioneer_fee);
                     self.accounts.entry(account)?.or_default().deduct_balance(auctioneer_fee)?;
+                    events.push(VAppEvent::Debited {
+                        account,
+                        amount: auctioneer_fee,
+                        reason: BalanceChangeReason::AuctioneerFee,
+                    });
                 } else {
                     // Someone else withdrawing for a prover.
                     debug!("validate balances for prover withdraw (prover pays amount, signer pays fee)");
@@ -588,14 +967,29 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                     // Deduct the amount from the prover.
                     info!("├── Account({}): - {} $PROVE", account, amount);
                     self.accounts.entry(account)?.or_default().deduct_balance(amount)?;
+                    events.push(VAppEvent::Debited {
+                        account,
+                        amount,
+                        reason: BalanceChangeReason::Withdraw,
+                    });
                     // Deduct the fee from the signer.
                     info!("├── Account({}): - {} $PROVE (fee)", from, auctioneer_fee);
                     self.accounts.entry(from)?.or_default().deduct_balance(auctioneer_fee)?;
+                    events.push(VAppEvent::Debited {
+                        account: from,
+                        amount: auctioneer_fee,
+                        reason: BalanceChangeReason::AuctioneerFee,
+                    });
                 }
 
                 // Credit the fee to the auctioneer.
                 info!("└── Auctioneer({}): + {} $PROVE (fee)", auctioneer, auctioneer_fee);
                 self.accounts.entry(auctioneer)?.or_default().add_balance(auctioneer_fee)?;
+                events.push(VAppEvent::Credited {
+                    account: auctioneer,
+                    amount: auctioneer_fee,
+                    reason: BalanceChangeReason::AuctioneerFee,
+                });
 
                 // Return the withdraw action.
                 return Ok(ExecuteOutcome::Applied(Some(VAppReceipt::Withdraw(OffchainReceipt {
@@ -676,7 +1070,7 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 // Check that the request ID has not been fulfilled yet.
                 //
                 // This check ensures that a request can't be used multiple times to pay a prover.
-                if *self.transactions.entry(request_id)?.or_default() {
+                if self.transactions.get(&request_id)?.copied().unwrap_or_default() {
                     return Err(VAppPanic::TransactionAlreadyProcessed {
                         id: hex::encode(request_id),
                     });
@@ -693,10 +1087,17 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                     .get(&prover_address)?
                     .ok_or(VAppPanic::ProverDoesNotExist { prover: prover_address })?;
                 let prover_owner = prover_account.get_owner();
-                if prover_account.get_signer() != bid_signer {
+                let delegated_signer = prover_account.get_signer();
+                if delegated_signer != bid_signer
+                    && !self
+                        .signers
+                        .get(&(prover_address, bid_signer))?
+                        .copied()
+                        .unwrap_or_default()
+                {
                     return Err(VAppPanic::ProverDelegatedSignerMismatch {
                         prover: prover_address,
-                        delegated_signer: prover_account.delegatedSigner,
+                        delegated_signer,
                     });
                 }
 
@@ -749,8 +1150,7 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                         .map_err(VAppPanic::U256ParseError)?;
 
                     // Check that the punishment is less than the max price.
-                    let gas_limit = U256::from(request.gas_limit);
-                    let max_price = u256::add(u256::mul(max_price_per_pgu, gas_limit)?, base_fee)?;
+                    let max_price = self.max_punishment(request)?;
                     if punishment > max_price {
                         return Err(VAppPanic::PunishmentExceedsMaxCost { punishment, max_price });
                     }
@@ -775,15 +1175,30 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
 
                     // Deduct the punishment from the requester.
                     self.accounts.entry(request_signer)?.or_default().deduct_balance(punishment)?;
+                    events.push(VAppEvent::Debited {
+                        account: request_signer,
+                        amount: punishment,
+                        reason: BalanceChangeReason::Punishment,
+                    });
 
                     // Parse the treasury address from the request.
                     let treasury = address(request.treasury.as_slice())?;
 
                     // Send the punishment to the treasury
                     self.accounts.entry(treasury)?.or_default().add_balance(punishment)?;
+                    events.push(VAppEvent::Credited {
+                        account: treasury,
+                        amount: punishment,
+                        reason: BalanceChangeReason::Punishment,
+                    });
 
                     // Set the transaction as processed.
                     self.transactions.insert(request_id, true)?;
+                    events.push(VAppEvent::RequestPunished {
+                        request_id,
+                        requester: request_signer,
+                        punishment,
+                    });
 
                     return Ok(ExecuteOutcome::Applied(None));
                 }
@@ -873,7 +1288,6 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 match (is_primary_version, mode) {
                     // Only the primary version with Compressed uses native SP1 verification.
                     (true, ProofMode::Compressed) => {
-                        let verifier = V::default();
                         verifier
                             .verify(vk, public_values_hash)
                             .map_err(|_| VAppPanic::InvalidProof)?;
@@ -898,8 +1312,9 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 }
 
                 // Calculate the cost of the proof.
-                let pgus = U256::from(execute.pgus.ok_or(VAppPanic::MissingPgusUsed)?);
-                let cost = u256::add(u256::mul(price, pgus)?, base_fee)?;
+                let pgus = execute.pgus.ok_or(VAppPanic::MissingPgusUsed)?;
+                let cost = request_cost(price, pgus, base_fee)?;
+                let pgus = U256::from(pgus);
 
                 // Validate that the execute gas_used was lower than the request gas_limit.
                 let gas_limit = U256::from(request.gas_limit);
@@ -946,6 +1361,11 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
                 // Deduct the cost from the requester.
                 info!("├── Account({}): - {} $PROVE (Requester Fee)", request_signer, cost);
                 self.accounts.entry(request_signer)?.or_default().deduct_balance(cost)?;
+                events.push(VAppEvent::Debited {
+                    account: request_signer,
+                    amount: cost,
+                    reason: BalanceChangeReason::RequesterFee,
+                });
 
                 // Get the protocol fee.
                 let treasury = address(request.treasury.as_slice())?;
@@ -964,18 +1384,40 @@ impl<A: Storage<Address, Account>, R: Storage<RequestId, bool>> VAppState<A, R>
 
                 info!("├── Account({}): + {} $PROVE (Protocol Fee)", treasury, protocol_fee);
                 self.accounts.entry(treasury)?.or_default().add_balance(protocol_fee)?;
+                events.push(VAppEvent::Credited {
+                    account: treasury,
+                    amount: protocol_fee,
+                    reason: BalanceChangeReason::ProtocolFee,
+                });
 
                 info!(
                     "├── Account({}): + {} $PROVE (Staker Reward)",
                     prover_address, prover_staker_fee
                 );
                 self.accounts.entry(prover_address)?.or_default().add_balance(prover_staker_fee)?;
+                events.push(VAppEvent::Credited {
+                    account: prover_address,
+                    amount: prover_staker_fee,
+                    reason: BalanceChangeReason::StakerReward,
+                });
 
                 info!(
                     "├── Account({}): + {} $PROVE (Owner Reward)",
                     prover_owner, prover_owner_fee
                 );
                 self.accounts.entry(prover_owner)?.or_default().add_balance(prover_owner_fee)?;
+                events.push(VAppEvent::Credited {
+                    account: prover_owner,
+                    amount: prover_owner_fee,
+                    reason: BalanceChangeReason::OwnerReward,
+                });
+                events.push(VAppEvent::RequestFulfilled {
+                    request_id,
+                    requester: request_signer,
+                    prover: prover_address,
+                    pgus,
+                    cost,
+                });
 
                 return Ok(ExecuteOutcome::Applied(None));
             }
@@ -4,18 +4,32 @@
 
 use std::collections::btree_map::Entry;
 
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{keccak256, Address, U256};
 use alloy_sol_types::SolValue;
 use thiserror::Error;
 
 /// Storage trait providing basic operations matching those available on `MerkleStore`.
+///
+/// The trait is object-safe, so that code that only reads and writes values can take a
+/// `&mut dyn Storage<K, V>`.
 pub trait Storage<K: StorageKey, V: StorageValue> {
     /// Creates a new empty storage.
-    fn new() -> Self;
+    fn new() -> Self
+    where
+        Self: Sized;
 
     /// Insert a value at the given key.
     fn insert(&mut self, key: K, value: V) -> Result<(), StorageError>;
 
+    /// Insert many values at once.
+    ///
+    /// Implementations may apply the writes more cheaply than one [`Storage::insert`] per value.
+    /// Later pairs overwrite earlier ones with the same key. If an error is returned, none of the
+    /// values are inserted.
+    fn insert_many(&mut self, items: impl IntoIterator<Item = (K, V)>) -> Result<(), StorageError>
+    where
+        Self: Sized;
+
     /// Gets an entry at the given key.
     fn entry(&mut self, key: K) -> Result<Entry<'_, U256, V>, StorageError>;
 
@@ -24,6 +38,20 @@ pub trait Storage<K: StorageKey, V: StorageValue> {
 
     /// Get a mutable reference to a value at the given key.
     fn get_mut(&mut self, key: &K) -> Result<Option<&mut V>, StorageError>;
+
+    /// Whether a value is stored at the given key.
+    ///
+    /// Like [`Storage::get`], this counts as a read of the key, so that the key is witnessed and
+    /// the same check can be replayed against a [`crate::sparse::SparseStorage`].
+    fn contains_key(&mut self, key: &K) -> Result<bool, StorageError> {
+        Ok(self.get(key)?.is_some())
+    }
+
+    /// Iterate over the stored values and their indices, in index order.
+    ///
+    /// Keys are stored by their [`StorageKey::index`], so the original keys can't be recovered.
+    /// The iteration doesn't count as a read of the keys.
+    fn iter(&self) -> Box<dyn Iterator<Item = (&U256, &V)> + '_>;
 }
 
 /// Errors that can occur when interacting with storage.
@@ -76,7 +104,24 @@ impl StorageKey for RequestId {
     }
 }
 
+/// The `(prover, signer)` pair identifying an additional signer of a prover.
+pub type SignerKey = (Address, Address);
+
+impl StorageKey for SignerKey {
+    fn index(&self) -> U256 {
+        let (prover, signer) = self;
+        let hash = keccak256([prover.as_slice(), signer.as_slice()].concat());
+        U256::from_be_slice(&hash[..20])
+    }
+
+    fn bits() -> usize {
+        160
+    }
+}
+
 /// Trait for types that can be used as values in a [`crate::merkle::MerkleTree`].
-pub trait StorageValue: SolValue + Clone + Default {}
+///
+/// Values must be [`Sync`] so that the leaves can be hashed in parallel with the `rayon` feature.
+pub trait StorageValue: SolValue + Clone + Default + Sync {}
 
-impl<V: SolValue + Clone + Default> StorageValue for V {}
+impl<V: SolValue + Clone + Default + Sync> StorageValue for V {}
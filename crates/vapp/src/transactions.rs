@@ -9,7 +9,10 @@ use spn_network_types::{
     SetDelegationRequest, SettleRequest, TransferRequest, WithdrawRequest,
 };
 
-use crate::sol::{CreateProver, Deposit};
+use crate::{
+    errors::VAppPanic,
+    sol::{CreateProver, Deposit},
+};
 
 /// A transaction that can be executed and update the [`crate::state::VAppState`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +50,40 @@ pub enum VAppTransaction {
     Withdraw(WithdrawTransaction),
 }
 
+/// Validates that the onchain transactions among the given transactions are strictly ordered.
+///
+/// Each [`OnchainTransaction`] must come after the previous one by `(block, log_index)` and have
+/// the next onchain transaction ID. Offchain transactions are ignored. This catches batches that
+/// were built out of order before they are executed, with the same panics as
+/// [`crate::state::VAppState::validate_onchain_tx`].
+pub fn validate_onchain_ordering(txs: &[VAppTransaction]) -> Result<(), VAppPanic> {
+    let mut previous: Option<(u64, u64, u64)> = None;
+    for tx in txs {
+        let current = match tx {
+            VAppTransaction::Deposit(tx) => (tx.block, tx.log_index, tx.onchain_tx),
+            VAppTransaction::CreateProver(tx) => (tx.block, tx.log_index, tx.onchain_tx),
+            _ => continue,
+        };
+
+        if let Some((block, log_index, onchain_tx)) = previous {
+            if current.0 < block {
+                return Err(VAppPanic::BlockNumberOutOfOrder { expected: block, actual: current.0 });
+            }
+            if current.0 == block && current.1 <= log_index {
+                return Err(VAppPanic::LogIndexOutOfOrder { current: log_index, next: current.1 });
+            }
+            if current.2 != onchain_tx + 1 {
+                return Err(VAppPanic::OnchainTxOutOfOrder {
+                    expected: onchain_tx + 1,
+                    actual: current.2,
+                });
+            }
+        }
+        previous = Some(current);
+    }
+    Ok(())
+}
+
 /// A transaction that was included in the ledger onchain.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OnchainTransaction<T> {
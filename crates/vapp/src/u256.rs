@@ -1,6 +1,8 @@
 //! U256 Safe Arithmetic Operations.
 //!
-//! This module contains the safe arithmetic operations for the U256 type.
+//! This module contains the safe arithmetic operations for the U256 type. Each operation returns a
+//! [`VAppPanic`] describing the failure instead of wrapping around, so that a bug in the balance
+//! arithmetic halts the state transition function rather than silently corrupting the state.
 
 use alloy_primitives::U256;
 
@@ -13,7 +15,7 @@ pub fn add(a: U256, b: U256) -> Result<U256, VAppPanic> {
 
 /// Safe subtraction of two U256 values.
 pub fn sub(a: U256, b: U256) -> Result<U256, VAppPanic> {
-    a.checked_sub(b).ok_or(VAppPanic::ArithmeticOverflow)
+    a.checked_sub(b).ok_or(VAppPanic::ArithmeticUnderflow)
 }
 
 /// Safe multiplication of two U256 values.
@@ -23,5 +25,52 @@ pub fn mul(a: U256, b: U256) -> Result<U256, VAppPanic> {
 
 /// Safe division of two U256 values.
 pub fn div(a: U256, b: U256) -> Result<U256, VAppPanic> {
-    a.checked_div(b).ok_or(VAppPanic::ArithmeticOverflow)
+    a.checked_div(b).ok_or(VAppPanic::DivisionByZero)
+}
+
+/// Safe addition of an amount to a balance.
+pub fn add_balance(balance: U256, amount: U256) -> Result<U256, VAppPanic> {
+    add(balance, amount).map_err(|_| VAppPanic::BalanceOverflow { balance, amount })
+}
+
+/// Safe subtraction of an amount from a balance.
+pub fn sub_balance(balance: U256, amount: U256) -> Result<U256, VAppPanic> {
+    sub(balance, amount).map_err(|_| VAppPanic::BalanceUnderflow { balance, amount })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic_at_bounds() {
+        // Operations that land exactly on the bounds succeed.
+        assert_eq!(add(U256::MAX - U256::from(1), U256::from(1)), Ok(U256::MAX));
+        assert_eq!(sub(U256::from(1), U256::from(1)), Ok(U256::ZERO));
+        assert_eq!(mul(U256::MAX, U256::from(1)), Ok(U256::MAX));
+        assert_eq!(div(U256::MAX, U256::MAX), Ok(U256::from(1)));
+
+        // Operations that cross them fail instead of wrapping around.
+        assert_eq!(add(U256::MAX, U256::from(1)), Err(VAppPanic::ArithmeticOverflow));
+        assert_eq!(sub(U256::ZERO, U256::from(1)), Err(VAppPanic::ArithmeticUnderflow));
+        assert_eq!(mul(U256::MAX, U256::from(2)), Err(VAppPanic::ArithmeticOverflow));
+        assert_eq!(div(U256::MAX, U256::ZERO), Err(VAppPanic::DivisionByZero));
+    }
+
+    #[test]
+    fn test_balance_arithmetic() {
+        let balance = U256::from(100);
+        assert_eq!(add_balance(balance, U256::from(50)), Ok(U256::from(150)));
+        assert_eq!(sub_balance(balance, balance), Ok(U256::ZERO));
+
+        // Failures report the balance and the amount.
+        assert_eq!(
+            sub_balance(balance, U256::from(101)),
+            Err(VAppPanic::BalanceUnderflow { balance, amount: U256::from(101) })
+        );
+        assert_eq!(
+            add_balance(U256::MAX, balance),
+            Err(VAppPanic::BalanceOverflow { balance: U256::MAX, amount: balance })
+        );
+    }
 }
@@ -2,8 +2,16 @@
 //!
 //! This module contains the traits and implementations for proof verifiers in the vApp.
 
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::Mutex,
+};
+
 use thiserror::Error;
 
+/// The maximum number of successful verifications remembered by a [`CachingVerifier`].
+pub const VERIFIER_CACHE_CAPACITY: usize = 1024;
+
 /// Errors that can occur during proof verification.
 #[derive(Error, Debug)]
 #[allow(missing_docs)]
@@ -49,3 +57,206 @@ impl VAppVerifier for RejectVerifier {
         Err(VAppVerifierError::InvalidProof)
     }
 }
+
+/// A verifier for testing that only accepts some verifying keys.
+///
+/// This models mixed verification outcomes, e.g. a batch where one program's proofs are invalid
+/// while the others are fine. Use it with [`crate::state::VAppState::execute_with_verifier`], as
+/// the default one accepts every proof.
+#[derive(Debug, Clone, Default)]
+pub struct SelectiveVerifier {
+    /// The verifying key digests that are listed.
+    vks: BTreeSet<[u32; 8]>,
+    /// Whether the listed digests are the only ones accepted, rather than the ones rejected.
+    accept_listed: bool,
+}
+
+impl SelectiveVerifier {
+    /// Create a new [`SelectiveVerifier`] that rejects the given verifying key digests and
+    /// accepts all others.
+    #[must_use]
+    pub fn rejecting(vks: impl IntoIterator<Item = [u32; 8]>) -> Self {
+        Self { vks: vks.into_iter().collect(), accept_listed: false }
+    }
+
+    /// Create a new [`SelectiveVerifier`] that accepts the given verifying key digests and
+    /// rejects all others.
+    #[must_use]
+    pub fn accepting(vks: impl IntoIterator<Item = [u32; 8]>) -> Self {
+        Self { vks: vks.into_iter().collect(), accept_listed: true }
+    }
+}
+
+impl VAppVerifier for SelectiveVerifier {
+    fn verify(
+        &self,
+        vk_digest_array: [u32; 8],
+        _pv_digest_array: [u8; 32],
+    ) -> Result<(), VAppVerifierError> {
+        if self.vks.contains(&vk_digest_array) == self.accept_listed {
+            Ok(())
+        } else {
+            Err(VAppVerifierError::InvalidProof)
+        }
+    }
+}
+
+/// A verifier that remembers the successful verifications of another verifier.
+///
+/// Verification is deterministic, so once a `(vk_digest, pv_digest)` pair has been verified, the
+/// same pair is accepted again without calling the inner verifier. This saves work when many
+/// requests share a program and public values, e.g. in aggregation.
+///
+/// Each [`CachingVerifier`] has its own cache, so pass the same one to
+/// [`crate::state::VAppState::execute_with_verifier`] for every transaction rather than using
+/// [`crate::state::VAppState::execute`], which creates a new verifier for each transaction. The
+/// cache holds at most [`VERIFIER_CACHE_CAPACITY`] pairs, evicting the oldest ones first. Failed
+/// verifications are never cached.
+#[derive(Debug, Default)]
+pub struct CachingVerifier<V> {
+    inner: V,
+    cache: Mutex<VerificationCache>,
+}
+
+impl<V> CachingVerifier<V> {
+    /// Create a new [`CachingVerifier`] that remembers the successful verifications of the given
+    /// verifier.
+    #[must_use]
+    pub fn new(inner: V) -> Self {
+        Self { inner, cache: Mutex::default() }
+    }
+}
+
+impl<V: VAppVerifier> VAppVerifier for CachingVerifier<V> {
+    fn verify(
+        &self,
+        vk_digest_array: [u32; 8],
+        pv_digest_array: [u8; 32],
+    ) -> Result<(), VAppVerifierError> {
+        let key = (vk_digest_array, pv_digest_array);
+        if self.cache.lock().unwrap().contains(&key) {
+            return Ok(());
+        }
+
+        self.inner.verify(vk_digest_array, pv_digest_array)?;
+        self.cache.lock().unwrap().insert(key);
+        Ok(())
+    }
+}
+
+/// A verification remembered by a [`CachingVerifier`].
+type VerificationKey = ([u32; 8], [u8; 32]);
+
+/// A bounded set of verifications that evicts the oldest ones first.
+#[derive(Debug)]
+struct VerificationCache {
+    capacity: usize,
+    entries: BTreeSet<VerificationKey>,
+    order: VecDeque<VerificationKey>,
+}
+
+impl Default for VerificationCache {
+    fn default() -> Self {
+        Self::new(VERIFIER_CACHE_CAPACITY)
+    }
+}
+
+impl VerificationCache {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, entries: BTreeSet::new(), order: VecDeque::new() }
+    }
+
+    fn contains(&self, key: &VerificationKey) -> bool {
+        self.entries.contains(key)
+    }
+
+    fn insert(&mut self, key: VerificationKey) {
+        if !self.entries.insert(key) {
+            return;
+        }
+        self.order.push_back(key);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// A verifier that accepts every proof except the ones with an empty vk, and counts its calls.
+    #[derive(Debug, Default)]
+    struct CountingVerifier {
+        calls: AtomicUsize,
+    }
+
+    impl VAppVerifier for CountingVerifier {
+        fn verify(
+            &self,
+            vk_digest_array: [u32; 8],
+            _pv_digest_array: [u8; 32],
+        ) -> Result<(), VAppVerifierError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if vk_digest_array == [0; 8] {
+                return Err(VAppVerifierError::InvalidProof);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_caching_verifier() {
+        // Successful verifications are only done once.
+        let verifier = CachingVerifier::<CountingVerifier>::default();
+        for _ in 0..3 {
+            verifier.verify([1; 8], [2; 32]).unwrap();
+        }
+        assert_eq!(verifier.inner.calls.load(Ordering::SeqCst), 1);
+
+        // Failed verifications are not cached.
+        for _ in 0..2 {
+            assert!(verifier.verify([0; 8], [2; 32]).is_err());
+        }
+        assert_eq!(verifier.inner.calls.load(Ordering::SeqCst), 3);
+
+        // The cache belongs to the verifier, so another one verifies again with its own config.
+        let verifier = CachingVerifier::new(SelectiveVerifier::rejecting([[1; 8]]));
+        assert!(verifier.verify([1; 8], [2; 32]).is_err());
+        let verifier = CachingVerifier::new(SelectiveVerifier::accepting([[1; 8]]));
+        assert!(verifier.verify([1; 8], [2; 32]).is_ok());
+        assert!(verifier.verify([2; 8], [2; 32]).is_err());
+    }
+
+    #[test]
+    fn test_selective_verifier() {
+        let verifier = SelectiveVerifier::rejecting([[1; 8]]);
+        assert!(verifier.verify([1; 8], [0; 32]).is_err());
+        assert!(verifier.verify([2; 8], [0; 32]).is_ok());
+
+        let verifier = SelectiveVerifier::accepting([[1; 8]]);
+        assert!(verifier.verify([1; 8], [0; 32]).is_ok());
+        assert!(verifier.verify([2; 8], [0; 32]).is_err());
+
+        // The default verifier accepts every proof.
+        assert!(SelectiveVerifier::default().verify([1; 8], [0; 32]).is_ok());
+    }
+
+    #[test]
+    fn test_verification_cache_eviction() {
+        let key = |i: u8| ([0; 8], [i; 32]);
+        let mut cache = VerificationCache::new(2);
+        cache.insert(key(1));
+        cache.insert(key(2));
+        cache.insert(key(1));
+        cache.insert(key(3));
+
+        // The oldest key is evicted once the capacity is exceeded.
+        assert!(!cache.contains(&key(1)));
+        assert!(cache.contains(&key(2)) && cache.contains(&key(3)));
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use alloy_primitives::{Keccak256, U256};
+use spn_vapp_core::{
+    errors::VAppPanic,
+    state::{StateImportError, VAppState},
+    verifier::MockVerifier,
+};
+
+use crate::common::*;
+
+#[test]
+fn test_checkpoint_restore() {
+    let mut test = setup();
+    let account = test.requester.address();
+
+    // Execute a deposit and take a checkpoint.
+    let tx1 = deposit_tx(account, U256::from(100), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&tx1).unwrap();
+    let checkpoint = test.state.checkpoint();
+
+    // Execute another deposit after the checkpoint.
+    let tx2 = deposit_tx(account, U256::from(200), 0, 2, 2);
+    test.state.execute::<MockVerifier>(&tx2).unwrap();
+    assert_account_balance(&mut test, account, U256::from(300));
+    assert_state_counters(&test, 3, 3, 0, 2);
+
+    // Verify restoring the checkpoint rolls back the second deposit.
+    test.state.restore(checkpoint);
+    assert_account_balance(&mut test, account, U256::from(100));
+    assert_state_counters(&test, 2, 2, 0, 1);
+
+    // Verify the second deposit can be replayed after the rollback.
+    test.state.execute::<MockVerifier>(&tx2).unwrap();
+    assert_account_balance(&mut test, account, U256::from(300));
+}
+
+#[test]
+fn test_execute_batch() {
+    let mut test = setup();
+    let account1 = test.requester.address();
+    let account2 = test.fulfiller.address();
+
+    // Execute a batch of deposits.
+    let txs = vec![
+        deposit_tx(account1, U256::from(100), 0, 1, 1),
+        deposit_tx(account2, U256::from(200), 0, 2, 2),
+        deposit_tx(account1, U256::from(300), 1, 1, 3),
+    ];
+    let outcomes = test.state.execute_batch::<MockVerifier>(&txs).unwrap();
+
+    // Verify every deposit was applied with a receipt.
+    assert_eq!(outcomes.len(), 3);
+    for (i, outcome) in outcomes.iter().enumerate() {
+        let amount = U256::from(100 * (i + 1));
+        let account = if i == 1 { account2 } else { account1 };
+        assert_deposit_receipt(outcome.as_ref().unwrap(), account, amount, i as u64 + 1);
+    }
+    assert_account_balance(&mut test, account1, U256::from(400));
+    assert_account_balance(&mut test, account2, U256::from(200));
+    assert_state_counters(&test, 4, 4, 1, 1);
+}
+
+#[test]
+fn test_execute_batch_rolls_back_on_panic() {
+    let mut test = setup();
+    let account = test.requester.address();
+
+    // Execute a deposit before the batch.
+    let tx = deposit_tx(account, U256::from(100), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&tx).unwrap();
+
+    // Execute a batch whose last deposit has an out of order onchain tx.
+    let txs = vec![
+        deposit_tx(account, U256::from(200), 0, 2, 2),
+        deposit_tx(account, U256::from(300), 0, 3, 4),
+    ];
+    let result = test.state.execute_batch::<MockVerifier>(&txs);
+
+    // Verify the panic is returned and the whole batch is rolled back.
+    assert_eq!(result.unwrap_err(), (1, VAppPanic::OnchainTxOutOfOrder { expected: 3, actual: 4 }));
+    assert_account_balance(&mut test, account, U256::from(100));
+    assert_state_counters(&test, 2, 2, 0, 1);
+}
+
+#[test]
+fn test_export_import_json() {
+    let mut test = setup();
+    let account = test.requester.address();
+    let tx = deposit_tx(account, U256::from(100), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&tx).unwrap();
+
+    // Verify the imported state matches the exported one.
+    let json = test.state.export_json::<Keccak256>();
+    let mut imported = VAppState::import_json::<Keccak256>(&json).unwrap();
+    assert_eq!(imported.root::<Keccak256>(), test.state.root::<Keccak256>());
+    assert_eq!(imported.tx_id, test.state.tx_id);
+
+    // Verify the imported state can keep executing transactions.
+    let tx = deposit_tx(account, U256::from(200), 0, 2, 2);
+    imported.execute::<MockVerifier>(&tx).unwrap();
+    test.state = imported;
+    assert_account_balance(&mut test, account, U256::from(300));
+
+    // Verify an edited export is rejected.
+    let edited = json.replacen("\"tx_id\": 2", "\"tx_id\": 3", 1);
+    assert_ne!(edited, json);
+    let result = VAppState::import_json::<Keccak256>(&edited).map(|state| test.state = state);
+    assert!(matches!(result, Err(StateImportError::RootMismatch { .. })));
+}
@@ -1,11 +1,12 @@
 mod common;
 
 use alloy_primitives::U256;
-use spn_network_types::{ExecutionStatus, HashableWithSender, ProofMode, TransactionVariant};
+use spn_network_types::{compute_request_id, ExecutionStatus, ProofMode, TransactionVariant};
 use spn_vapp_core::{
     errors::{VAppError, VAppPanic, VAppRevert},
     transactions::VAppTransaction,
-    verifier::{MockVerifier, RejectVerifier},
+    utils::bytes_to_words_be,
+    verifier::{CachingVerifier, MockVerifier, RejectVerifier, SelectiveVerifier},
 };
 
 use crate::common::*;
@@ -809,7 +810,8 @@ fn test_clear_delegated_signer_mismatch() {
 /// Then submit a Clear whose bid is signed by the original prover key (which is no longer the
 /// delegated signer). The Clear handler looks up the prover account (exists, so no
 /// `ProverDoesNotExist`) and then compares `prover_account.get_signer()` against the recovered
-/// `bid_signer` — they differ, so `ProverDelegatedSignerMismatch` must panic.
+/// `bid_signer` — they differ and the key isn't an additional signer, so
+/// `ProverDelegatedSignerMismatch` must panic.
 #[test]
 fn test_clear_delegated_signer_mismatch_with_real_delegation() {
     let mut test = setup();
@@ -861,6 +863,63 @@ fn test_clear_delegated_signer_mismatch_with_real_delegation() {
     ));
 }
 
+/// A bid signed by any of the signers of a prover clears, not only by its delegated signer.
+#[test]
+fn test_clear_additional_signer() {
+    let mut test = setup();
+    let prover_owner = test.signers[0].clone();
+    let delegate = test.signers[2].clone();
+    let requester_address = test.requester.address();
+    let prover_address = test.fulfiller.address();
+
+    // Fund the requester and register the prover with a distinct owner.
+    let amount = U256::from(100_000_000);
+    let fund_requester_tx = deposit_tx(requester_address, amount, 0, 1, 1);
+    test.state.execute::<MockVerifier>(&fund_requester_tx).unwrap();
+    let create_prover_tx =
+        create_prover_tx(prover_address, prover_owner.address(), U256::ZERO, 0, 2, 2);
+    test.state.execute::<MockVerifier>(&create_prover_tx).unwrap();
+
+    // Fund the prover owner to cover two delegation fees.
+    let two_prove = U256::from(2) * U256::from(10).pow(U256::from(18));
+    let fund_owner_tx = deposit_tx(prover_owner.address(), two_prove, 0, 3, 3);
+    test.state.execute::<MockVerifier>(&fund_owner_tx).unwrap();
+
+    // Delegate to another key, then authorize the prover key again as an additional signer.
+    let delegate_tx = delegate_tx(&prover_owner, prover_address, delegate.address(), 1);
+    test.state.execute::<MockVerifier>(&delegate_tx).unwrap();
+    let add_tx = delegate_tx_with_variant(
+        &prover_owner,
+        prover_address,
+        prover_address,
+        2,
+        TransactionVariant::AddDelegateVariant,
+    );
+    test.state.execute::<MockVerifier>(&add_tx).unwrap();
+    assert_prover_signer(&mut test, prover_address, delegate.address());
+
+    // The bid is signed by the additional signer, so the clear goes through.
+    let clear_tx = create_clear_tx(
+        &test.requester,
+        &test.fulfiller,
+        &test.fulfiller,
+        &test.auctioneer,
+        &test.executor,
+        &test.verifier,
+        1,
+        U256::from(50_000),
+        1,
+        1,
+        1,
+        1,
+        ProofMode::Compressed,
+        ExecutionStatus::Executed,
+        false,
+    );
+    test.state.execute::<MockVerifier>(&clear_tx).unwrap();
+    assert_account_balance(&mut test, requester_address, amount - U256::from(50_000_000));
+}
+
 #[test]
 fn test_clear_prover_not_in_whitelist() {
     let mut test = setup();
@@ -2169,6 +2228,56 @@ fn test_clear_punishment_exceeds_max_cost() {
     assert!(matches!(result, Err(VAppError::Panic(VAppPanic::PunishmentExceedsMaxCost { .. }))));
 }
 
+#[test]
+fn test_max_punishment() {
+    let mut test = setup();
+
+    // Setup: Deposit funds for requester and create prover.
+    let requester_address = test.requester.address();
+    let prover_address = test.fulfiller.address();
+    let amount = U256::from(100_000_000);
+
+    let deposit_tx = deposit_tx(requester_address, amount, 0, 1, 1);
+    test.state.execute::<MockVerifier>(&deposit_tx).unwrap();
+
+    let create_prover_tx = create_prover_tx(prover_address, prover_address, U256::ZERO, 1, 2, 2);
+    test.state.execute::<MockVerifier>(&create_prover_tx).unwrap();
+
+    // Max cost = max_price_per_pgu * gas_limit + base_fee = 100,000 * 10,000 + 7 = 1,000,000,007
+    let mut clear_tx = create_clear_tx_with_base_fee(
+        &test.requester,
+        &test.fulfiller,
+        &test.fulfiller,
+        &test.auctioneer,
+        &test.executor,
+        &test.verifier,
+        1,
+        U256::from(50_000),
+        1,
+        1,
+        1,
+        1,
+        ProofMode::Compressed,
+        ExecutionStatus::Unexecutable,
+        false,
+        "7",
+    );
+    let VAppTransaction::Clear(ref mut clear) = clear_tx else { unreachable!() };
+    let max_punishment = test.state.max_punishment(clear.request.body.as_ref().unwrap()).unwrap();
+    assert_eq!(max_punishment, U256::from(1_000_000_007));
+
+    // A punishment one above the cap is rejected with the same cap.
+    let execute_body = clear.execute.body.as_mut().unwrap();
+    execute_body.punishment = Some((max_punishment + U256::from(1)).to_string());
+    clear.execute.signature = proto_sign(&test.executor, execute_body).as_bytes().to_vec();
+    let result = test.state.execute::<MockVerifier>(&clear_tx);
+    assert!(matches!(
+        result,
+        Err(VAppError::Panic(VAppPanic::PunishmentExceedsMaxCost { max_price, .. }))
+            if max_price == max_punishment
+    ));
+}
+
 #[test]
 fn test_clear_invalid_execution_status() {
     let mut test = setup();
@@ -2432,14 +2541,10 @@ fn test_clear_public_values_hash_mismatch() {
             request_body.public_values_hash = Some(vec![9u8; 32]); // Different hash
             clear.request.signature = proto_sign(&test.requester, request_body).as_bytes().to_vec();
         }
-        let request_id = clear
-            .request
-            .body
-            .clone()
-            .unwrap()
-            .hash_with_signer(test.requester.address().as_slice())
-            .unwrap()
-            .to_vec();
+        let request_id =
+            compute_request_id(clear.request.body.as_ref().unwrap(), &test.requester.address())
+                .unwrap()
+                .to_vec();
         if let Some(ref mut bid_body) = clear.bid.body {
             bid_body.request_id = request_id.clone();
             clear.bid.signature = proto_sign(&test.fulfiller, bid_body).as_bytes().to_vec();
@@ -2653,6 +2758,58 @@ fn test_clear_invalid_proof_compressed() {
     assert!(matches!(result, Err(VAppError::Panic(VAppPanic::InvalidProof))));
 }
 
+#[test]
+fn test_clear_selective_verifier() {
+    let mut test = setup();
+
+    // Setup: Deposit funds for requester and create prover.
+    let requester_address = test.requester.address();
+    let prover_address = test.fulfiller.address();
+    let amount = U256::from(100_000_000);
+
+    let deposit_tx = deposit_tx(requester_address, amount, 0, 1, 1);
+    test.state.execute::<MockVerifier>(&deposit_tx).unwrap();
+
+    let create_prover_tx = create_prover_tx(prover_address, prover_address, U256::ZERO, 1, 2, 2);
+    test.state.execute::<MockVerifier>(&create_prover_tx).unwrap();
+
+    let clear_tx = create_clear_tx(
+        &test.requester,
+        &test.fulfiller,
+        &test.fulfiller,
+        &test.auctioneer,
+        &test.executor,
+        &test.verifier,
+        1,
+        U256::from(50_000),
+        1,
+        1,
+        1,
+        1,
+        ProofMode::Compressed,
+        ExecutionStatus::Executed,
+        false,
+    );
+    let VAppTransaction::Clear(clear) = &clear_tx else { unreachable!() };
+    let vk_hash = clear.request.body.as_ref().unwrap().vk_hash.clone();
+    let vk = bytes_to_words_be(&vk_hash.try_into().unwrap()).unwrap();
+
+    // Execute should fail with InvalidProof when the request's vk is rejected.
+    let verifier = SelectiveVerifier::rejecting([vk]);
+    let result = test.state.execute_with_verifier(&clear_tx, &verifier);
+    assert!(matches!(result, Err(VAppError::Panic(VAppPanic::InvalidProof))));
+
+    // A caching verifier keeps the configuration of the verifier it wraps.
+    let verifier = CachingVerifier::new(SelectiveVerifier::rejecting([vk]));
+    let result = test.state.execute_with_verifier(&clear_tx, &verifier);
+    assert!(matches!(result, Err(VAppError::Panic(VAppPanic::InvalidProof))));
+
+    // Execute should succeed when only other vks are rejected.
+    let verifier = SelectiveVerifier::rejecting([[0; 8]]);
+    test.state.execute_with_verifier(&clear_tx, &verifier).unwrap();
+    assert_account_balance(&mut test, prover_address, U256::from(50_000_000));
+}
+
 #[test]
 fn test_clear_invalid_request_variant() {
     let mut test = setup();
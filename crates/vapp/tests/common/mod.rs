@@ -6,10 +6,11 @@ use alloy::signers::{local::PrivateKeySigner, SignerSync};
 use alloy_primitives::{keccak256, Address, Signature, U256};
 use prost::Message;
 use spn_network_types::{
-    BidRequest, BidRequestBody, ExecuteProofRequest, ExecuteProofRequestBody, ExecutionStatus,
-    FulfillProofRequest, FulfillProofRequestBody, FulfillmentStrategy, MessageFormat, ProofMode,
-    RequestProofRequest, RequestProofRequestBody, SetDelegationRequest, SetDelegationRequestBody,
-    SettleRequest, SettleRequestBody, TransactionVariant, WithdrawRequest, WithdrawRequestBody,
+    compute_request_id, BidRequest, BidRequestBody, ExecuteProofRequest, ExecuteProofRequestBody,
+    ExecutionStatus, FulfillProofRequest, FulfillProofRequestBody, FulfillmentStrategy,
+    MessageFormat, ProofMode, RequestProofRequest, RequestProofRequestBody, SetDelegationRequest,
+    SetDelegationRequestBody, SettleRequest, SettleRequestBody, TransactionVariant,
+    WithdrawRequest, WithdrawRequestBody,
 };
 use spn_utils::SPN_MAINNET_V1_DOMAIN;
 use spn_vapp_core::{
@@ -17,7 +18,7 @@ use spn_vapp_core::{
     receipts::VAppReceipt,
     sol::{Account, CreateProver, Deposit, TransactionStatus},
     state::VAppState,
-    storage::{RequestId, Storage},
+    storage::{RequestId, SignerKey, Storage},
     transactions::{
         ClearTransaction, DelegateTransaction, OnchainTransaction, TransferTransaction,
         VAppTransaction, WithdrawTransaction,
@@ -44,7 +45,11 @@ pub fn proto_sign<T: Message>(signer: &PrivateKeySigner, message: &T) -> Signatu
 /// Test environment containing state, domain, and signers.
 pub struct VAppTestContext {
     /// The state of the vApp.
-    pub state: VAppState<MerkleStorage<Address, Account>, MerkleStorage<RequestId, bool>>,
+    pub state: VAppState<
+        MerkleStorage<Address, Account>,
+        MerkleStorage<RequestId, bool>,
+        MerkleStorage<SignerKey, bool>,
+    >,
     /// The auctioneer signer.
     pub auctioneer: PrivateKeySigner,
     /// The executor signer.
@@ -163,13 +168,30 @@ pub fn delegate_tx(
     prover_address: Address,
     delegate_address: Address,
     nonce: u64,
+) -> VAppTransaction {
+    delegate_tx_with_variant(
+        prover_owner,
+        prover_address,
+        delegate_address,
+        nonce,
+        TransactionVariant::DelegateVariant,
+    )
+}
+
+/// Creates a delegate tx with the given variant, e.g. to add or remove a signer.
+pub fn delegate_tx_with_variant(
+    prover_owner: &alloy::signers::local::PrivateKeySigner,
+    prover_address: Address,
+    delegate_address: Address,
+    nonce: u64,
+    variant: TransactionVariant,
 ) -> VAppTransaction {
     let body = SetDelegationRequestBody {
         nonce,
         delegate: delegate_address.to_vec(),
         prover: prover_address.to_vec(),
         domain: spn_utils::SPN_MAINNET_V1_DOMAIN.to_vec(),
-        variant: TransactionVariant::DelegateVariant as i32,
+        variant: variant as i32,
         auctioneer: crate::common::signer("auctioneer").address().to_vec(),
         fee: "1000000000000000000".to_string(), // 1 PROVE default fee
     };
@@ -488,6 +510,17 @@ pub fn assert_prover_signer(test: &mut VAppTestContext, prover: Address, expecte
     assert!(account.is_signer(expected_signer), "Expected address should be a valid signer");
 }
 
+/// Asserts whether an address is an additional signer of a prover.
+pub fn assert_additional_signer(
+    test: &mut VAppTestContext,
+    prover: Address,
+    signer: Address,
+    expected: bool,
+) {
+    let actual = test.state.signers.get(&(prover, signer)).unwrap().copied().unwrap_or_default();
+    assert_eq!(actual, expected, "Additional signer mismatch");
+}
+
 /// Asserts that the state counters match the expected values.
 pub fn assert_state_counters(
     test: &VAppTestContext,
@@ -595,8 +628,7 @@ pub fn create_clear_tx_with_options(
     };
 
     // Compute the request ID from the request body and signer.
-    let request_id = request_body
-        .hash_with_signer(requester_signer.address().as_slice())
+    let request_id = compute_request_id(&request_body, &requester_signer.address())
         .expect("Failed to hash request body");
 
     // Create and sign request.
@@ -907,8 +939,7 @@ pub fn create_clear_tx_with_public_values_hash(
     };
 
     // Compute the request ID from the request body and signer.
-    let request_id = request_body
-        .hash_with_signer(requester_signer.address().as_slice())
+    let request_id = compute_request_id(&request_body, &requester_signer.address())
         .expect("Failed to hash request body");
 
     // Create and sign request.
@@ -1114,8 +1145,7 @@ pub fn create_clear_tx_with_version(
     };
 
     // Compute the request ID from the request body and signer.
-    let request_id = request_body
-        .hash_with_signer(requester_signer.address().as_slice())
+    let request_id = compute_request_id(&request_body, &requester_signer.address())
         .expect("Failed to hash request body");
 
     // Create and sign request.
@@ -1,11 +1,14 @@
 mod common;
 
-use alloy_primitives::U256;
+use alloy_primitives::{Keccak256, U256};
 use spn_network_types::{
     MessageFormat, SetDelegationRequest, SetDelegationRequestBody, TransactionVariant,
 };
 use spn_vapp_core::{
     errors::{VAppError, VAppPanic, VAppRevert},
+    merkle::MerkleTreeHasher,
+    sol::VAppStateContainer,
+    storage::Storage,
     transactions::{DelegateTransaction, VAppTransaction},
     verifier::MockVerifier,
 };
@@ -552,3 +555,111 @@ fn test_delegate_zero_balance_owner() {
     // Verify signer remains unchanged.
     assert_prover_signer(&mut test, prover_address, prover_owner.address());
 }
+
+#[test]
+fn test_delegate_add_and_remove_signers() {
+    let mut test = setup();
+    let prover_owner = test.signers[0].clone();
+    let prover_address = test.signers[1].address();
+    let delegate1 = test.signers[2].address();
+    let delegate2 = test.signers[3].address();
+    let delegate3 = test.signers[4].address();
+
+    // Create prover and fund the owner for four delegation fees.
+    let create_prover_tx =
+        create_prover_tx(prover_address, prover_owner.address(), U256::from(500), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&create_prover_tx).unwrap();
+    let fee_amount = U256::from(4) * U256::from(10).pow(U256::from(18)); // 4 PROVE
+    let deposit_tx = deposit_tx(prover_owner.address(), fee_amount, 0, 2, 2);
+    test.state.execute::<MockVerifier>(&deposit_tx).unwrap();
+    let account_before = test.state.accounts.get(&prover_address).unwrap().unwrap().clone();
+
+    // Add two signers alongside the owner.
+    for (nonce, delegate) in [(1, delegate1), (2, delegate2)] {
+        let add_tx = delegate_tx_with_variant(
+            &prover_owner,
+            prover_address,
+            delegate,
+            nonce,
+            TransactionVariant::AddDelegateVariant,
+        );
+        test.state.execute::<MockVerifier>(&add_tx).unwrap();
+    }
+    assert_prover_signer(&mut test, prover_address, prover_owner.address());
+    assert_additional_signer(&mut test, prover_address, delegate1, true);
+    assert_additional_signer(&mut test, prover_address, delegate2, true);
+
+    // The additional signers are stored apart from the prover's account.
+    let account_after = test.state.accounts.get(&prover_address).unwrap().unwrap().clone();
+    assert_eq!(account_after.delegatedSigner, account_before.delegatedSigner);
+    assert_eq!(account_after.stakerFeeBips, account_before.stakerFeeBips);
+
+    // Removing a signer only revokes that signer.
+    let remove_tx = delegate_tx_with_variant(
+        &prover_owner,
+        prover_address,
+        delegate1,
+        3,
+        TransactionVariant::RemoveDelegateVariant,
+    );
+    test.state.execute::<MockVerifier>(&remove_tx).unwrap();
+    assert_prover_signer(&mut test, prover_address, prover_owner.address());
+    assert_additional_signer(&mut test, prover_address, delegate1, false);
+    assert_additional_signer(&mut test, prover_address, delegate2, true);
+
+    // A plain delegation replaces the delegated signer and keeps the additional signers.
+    let delegate_tx = delegate_tx(&prover_owner, prover_address, delegate3, 4);
+    test.state.execute::<MockVerifier>(&delegate_tx).unwrap();
+    assert_prover_signer(&mut test, prover_address, delegate3);
+    assert_additional_signer(&mut test, prover_address, delegate2, true);
+}
+
+#[test]
+fn test_delegate_root_unchanged_without_additional_signers() {
+    let mut test = setup();
+    let prover_owner = test.signers[0].clone();
+    let prover_address = test.signers[1].address();
+    let delegate = test.signers[2].address();
+
+    // Create prover, fund the owner and delegate to another signer.
+    let create_prover_tx =
+        create_prover_tx(prover_address, prover_owner.address(), U256::from(500), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&create_prover_tx).unwrap();
+    let fee_amount = U256::from(2) * U256::from(10).pow(U256::from(18)); // 2 PROVE
+    let deposit_tx = deposit_tx(prover_owner.address(), fee_amount, 0, 2, 2);
+    test.state.execute::<MockVerifier>(&deposit_tx).unwrap();
+    let delegate_tx = delegate_tx(&prover_owner, prover_address, delegate, 1);
+    test.state.execute::<MockVerifier>(&delegate_tx).unwrap();
+
+    // Without additional signers, the root only commits to the state container.
+    let container = VAppStateContainer {
+        domain: test.state.domain,
+        txId: test.state.tx_id,
+        onchainTxId: test.state.onchain_tx_id,
+        onchainBlock: test.state.onchain_block,
+        onchainLogIndex: test.state.onchain_log_index,
+        accountsRoot: test.state.accounts.root(),
+        transactionsRoot: test.state.transactions.root(),
+    };
+    assert_eq!(test.state.root::<Keccak256>(), Keccak256::hash(&container));
+
+    // Adding a signer commits to the signers too.
+    let add_tx = delegate_tx_with_variant(
+        &prover_owner,
+        prover_address,
+        prover_owner.address(),
+        2,
+        TransactionVariant::AddDelegateVariant,
+    );
+    test.state.execute::<MockVerifier>(&add_tx).unwrap();
+    let container = VAppStateContainer {
+        txId: test.state.tx_id,
+        accountsRoot: test.state.accounts.root(),
+        transactionsRoot: test.state.transactions.root(),
+        ..container
+    };
+    assert_eq!(
+        test.state.root::<Keccak256>(),
+        Keccak256::hash_pair(&Keccak256::hash(&container), &test.state.signers.root())
+    );
+}
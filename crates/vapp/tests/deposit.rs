@@ -3,6 +3,7 @@ mod common;
 use alloy_primitives::U256;
 use spn_vapp_core::{
     errors::{VAppError, VAppPanic},
+    transactions::validate_onchain_ordering,
     verifier::MockVerifier,
 };
 
@@ -216,3 +217,41 @@ fn test_deposit_log_index_valid_progression() {
     assert_deposit_receipt(&receipt3, account, U256::from(100), 3);
     assert_state_counters(&test, 4, 4, 1, 1);
 }
+
+#[test]
+fn test_validate_onchain_ordering() {
+    let test = setup();
+    let account = test.requester.address();
+    let prover = test.fulfiller.address();
+
+    // Onchain transactions ordered by block and log index, with offchain ones in between.
+    let mut txs = vec![
+        deposit_tx(account, U256::from(100), 0, 5, 1),
+        withdraw_tx(&test.requester, account, U256::from(10), 1),
+        create_prover_tx(prover, account, U256::ZERO, 0, 6, 2),
+        deposit_tx(account, U256::from(100), 1, 1, 3),
+    ];
+    assert!(validate_onchain_ordering(&txs).is_ok());
+    assert!(validate_onchain_ordering(&[]).is_ok());
+
+    // A block regression is rejected.
+    txs.push(deposit_tx(account, U256::from(100), 0, 7, 4));
+    assert!(matches!(
+        validate_onchain_ordering(&txs),
+        Err(VAppPanic::BlockNumberOutOfOrder { expected: 1, actual: 0 })
+    ));
+
+    // A repeated log index in the same block is rejected.
+    txs[4] = deposit_tx(account, U256::from(100), 1, 1, 4);
+    assert!(matches!(
+        validate_onchain_ordering(&txs),
+        Err(VAppPanic::LogIndexOutOfOrder { current: 1, next: 1 })
+    ));
+
+    // A skipped onchain transaction ID is rejected.
+    txs[4] = deposit_tx(account, U256::from(100), 1, 2, 5);
+    assert!(matches!(
+        validate_onchain_ordering(&txs),
+        Err(VAppPanic::OnchainTxOutOfOrder { expected: 4, actual: 5 })
+    ));
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use alloy_primitives::U256;
+use spn_network_types::{ExecutionStatus, ProofMode};
+use spn_vapp_core::{
+    errors::{VAppError, VAppRevert},
+    events::{BalanceChangeReason, FeeBreakdown, VAppEvent},
+    verifier::MockVerifier,
+};
+
+use crate::common::*;
+
+#[test]
+fn test_events_deposit_and_transfer() {
+    let mut test = setup();
+    let from = test.signers[0].clone();
+    let to = test.signers[1].address();
+    let auctioneer = test.auctioneer.address();
+
+    // Deposit funds.
+    let tx = deposit_tx(from.address(), U256::from(100), 0, 1, 1);
+    let (receipt, events) = test.state.execute_with_events::<MockVerifier>(&tx).unwrap();
+    assert!(receipt.is_some());
+    assert_eq!(
+        events,
+        vec![VAppEvent::Credited {
+            account: from.address(),
+            amount: U256::from(100),
+            reason: BalanceChangeReason::Deposit,
+        }]
+    );
+
+    // Transfer funds, paying the auctioneer fee.
+    let tx = transfer_tx(&from, to, U256::from(60), 1, auctioneer, U256::from(5));
+    let (_, events) = test.state.execute_with_events::<MockVerifier>(&tx).unwrap();
+    assert_eq!(
+        events,
+        vec![
+            VAppEvent::Debited {
+                account: from.address(),
+                amount: U256::from(60),
+                reason: BalanceChangeReason::Transfer,
+            },
+            VAppEvent::Credited {
+                account: to,
+                amount: U256::from(60),
+                reason: BalanceChangeReason::Transfer,
+            },
+            VAppEvent::Debited {
+                account: from.address(),
+                amount: U256::from(5),
+                reason: BalanceChangeReason::AuctioneerFee,
+            },
+            VAppEvent::Credited {
+                account: auctioneer,
+                amount: U256::from(5),
+                reason: BalanceChangeReason::AuctioneerFee,
+            },
+        ]
+    );
+
+    // Reverted transactions don't emit events.
+    let tx = transfer_tx(&from, to, U256::from(60), 2, auctioneer, U256::from(5));
+    let result = test.state.execute_with_events::<MockVerifier>(&tx);
+    assert!(matches!(
+        result,
+        Err(VAppError::Revert(VAppRevert::InsufficientTransferBalance { .. }))
+    ));
+}
+
+#[test]
+fn test_events_clear() {
+    let mut test = setup();
+    let requester = test.requester.address();
+    let prover = test.fulfiller.address();
+
+    // Deposit funds for the requester and create the prover.
+    let tx = deposit_tx(requester, U256::from(100_000_000), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&tx).unwrap();
+    let tx = create_prover_tx(prover, prover, U256::ZERO, 1, 2, 2);
+    let (_, events) = test.state.execute_with_events::<MockVerifier>(&tx).unwrap();
+    assert_eq!(
+        events,
+        vec![VAppEvent::ProverCreated { prover, owner: prover, staker_fee_bips: U256::ZERO }]
+    );
+
+    // Clear a request.
+    let clear_tx = create_clear_tx(
+        &test.requester,
+        &test.fulfiller,
+        &test.fulfiller,
+        &test.auctioneer,
+        &test.executor,
+        &test.verifier,
+        1,
+        U256::from(50_000),
+        1,
+        1,
+        1,
+        1,
+        ProofMode::Compressed,
+        ExecutionStatus::Executed,
+        false,
+    );
+    let (_, events) = test.state.execute_with_events::<MockVerifier>(&clear_tx).unwrap();
+
+    // The requester pays the cost, which is split between the treasury and the prover.
+    let cost = U256::from(50_000_000);
+    assert_eq!(
+        events.first(),
+        Some(&VAppEvent::Debited {
+            account: requester,
+            amount: cost,
+            reason: BalanceChangeReason::RequesterFee,
+        })
+    );
+    let credited: U256 = events
+        .iter()
+        .filter_map(|event| match event {
+            VAppEvent::Credited { amount, .. } => Some(*amount),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(credited, cost);
+    assert!(matches!(
+        events.last(),
+        Some(VAppEvent::RequestFulfilled { requester: r, prover: p, cost: c, .. })
+            if *r == requester && *p == prover && *c == cost
+    ));
+}
+
+#[test]
+fn test_fee_breakdown() {
+    let mut test = setup();
+    let requester = test.requester.address();
+    let prover = test.fulfiller.address();
+    let treasury = signer("treasury").address();
+    let mut fees = FeeBreakdown::default();
+
+    // Deposit funds for the requester and create the prover with a 10% staker fee.
+    let tx = deposit_tx(requester, U256::from(100_000_000), 0, 1, 1);
+    let (_, events) = test.state.execute_with_events::<MockVerifier>(&tx).unwrap();
+    fees.record(&events);
+    let tx = create_prover_tx(prover, prover, U256::from(1000), 1, 2, 2);
+    test.state.execute::<MockVerifier>(&tx).unwrap();
+    assert_eq!(fees, FeeBreakdown::default());
+
+    // Clear a request.
+    let clear_tx = create_clear_tx(
+        &test.requester,
+        &test.fulfiller,
+        &test.fulfiller,
+        &test.auctioneer,
+        &test.executor,
+        &test.verifier,
+        1,
+        U256::from(50_000),
+        1,
+        1,
+        1,
+        1,
+        ProofMode::Compressed,
+        ExecutionStatus::Executed,
+        false,
+    );
+    let (_, events) = test.state.execute_with_events::<MockVerifier>(&clear_tx).unwrap();
+    fees.record(&events);
+
+    // The cost is fully split between the treasury, the stakers, and the owner.
+    let cost = U256::from(50_000_000);
+    assert_eq!(fees.protocol_fees + fees.staker_rewards + fees.owner_rewards, cost);
+    assert_eq!(fees.punishments, U256::ZERO);
+    assert_eq!(fees.protocol_revenue(), fees.protocol_fees);
+    assert_eq!(test.state.treasury_balance(&treasury), fees.protocol_revenue());
+
+    // Deposits to the treasury count towards its balance but aren't revenue.
+    let tx = deposit_tx(treasury, U256::from(1_000), 2, 3, 3);
+    let (_, events) = test.state.execute_with_events::<MockVerifier>(&tx).unwrap();
+    fees.record(&events);
+    assert_eq!(test.state.treasury_balance(&treasury), fees.protocol_revenue() + U256::from(1_000));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use alloy_primitives::U256;
+use spn_vapp_core::{
+    errors::{VAppPanic, VAppRevert},
+    state::BalanceChange,
+    verifier::MockVerifier,
+};
+
+use crate::common::*;
+
+#[test]
+fn test_simulate_deposit() {
+    let mut test = setup();
+    let account = test.requester.address();
+
+    // Simulate a deposit.
+    let tx = deposit_tx(account, U256::from(100), 0, 1, 1);
+    let result = test.state.simulate::<MockVerifier>(&tx).unwrap();
+
+    // Verify the projected receipt and balance change.
+    assert_deposit_receipt(result.outcome.as_ref().unwrap(), account, U256::from(100), 1);
+    assert_eq!(
+        result.balance_changes,
+        vec![BalanceChange { account, before: U256::ZERO, after: U256::from(100) }]
+    );
+
+    // Verify the state was not mutated.
+    assert_account_balance(&mut test, account, U256::ZERO);
+    assert_state_counters(&test, 1, 1, 0, 0);
+
+    // Verify executing the transaction matches the simulation.
+    let receipt = test.state.execute::<MockVerifier>(&tx).unwrap();
+    assert_eq!(result.outcome, Ok(receipt));
+    assert_account_balance(&mut test, account, U256::from(100));
+}
+
+#[test]
+fn test_simulate_transfer_insufficient_balance() {
+    let mut test = setup();
+    let from_signer = test.signers[0].clone();
+    let to_address = test.signers[1].address();
+    let auctioneer = test.auctioneer.address();
+
+    // Set up initial balance.
+    let deposit_tx = deposit_tx(from_signer.address(), U256::from(100), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&deposit_tx).unwrap();
+
+    // Simulate a transfer that can't cover its fee.
+    let transfer_tx =
+        transfer_tx(&from_signer, to_address, U256::from(100), 1, auctioneer, U256::from(1));
+    let result = test.state.simulate::<MockVerifier>(&transfer_tx).unwrap();
+
+    // Verify the revert is projected without any balance changes.
+    assert!(matches!(
+        result.outcome,
+        Err(VAppRevert::InsufficientTransferBalance { required, balance, .. })
+            if required == U256::from(101) && balance == U256::from(100)
+    ));
+    assert!(result.balance_changes.is_empty());
+    assert_state_counters(&test, 2, 2, 0, 1);
+}
+
+#[test]
+fn test_simulate_panic() {
+    let test = setup();
+    let account = test.requester.address();
+
+    // Simulate a deposit with an out of order onchain tx.
+    let tx = deposit_tx(account, U256::from(100), 0, 1, 2);
+    let result = test.state.simulate::<MockVerifier>(&tx);
+
+    // Verify the panic is returned.
+    assert_eq!(result.unwrap_err(), VAppPanic::OnchainTxOutOfOrder { expected: 1, actual: 2 });
+    assert_state_counters(&test, 1, 1, 0, 0);
+}
@@ -1,9 +1,12 @@
 mod common;
 
 use alloy_primitives::U256;
-use spn_network_types::{MessageFormat, TransactionVariant, TransferRequest, TransferRequestBody};
+use spn_network_types::{
+    HashableWithSender, MessageFormat, TransactionVariant, TransferRequest, TransferRequestBody,
+};
 use spn_vapp_core::{
     errors::{VAppError, VAppPanic, VAppRevert},
+    storage::Storage,
     transactions::{TransferTransaction, VAppTransaction},
     verifier::MockVerifier,
 };
@@ -457,6 +460,36 @@ fn test_transfer_replay_protection() {
     assert_state_counters(&test, 3, 2, 0, 1);
 }
 
+#[test]
+fn test_transfer_with_unprocessed_transaction_leaf() {
+    let mut test = setup();
+    let from_signer = test.signers[0].clone();
+    let to_address = test.signers[1].address();
+    let auctioneer = test.auctioneer.address();
+
+    // Set up initial balance for sender.
+    let deposit_tx = deposit_tx(from_signer.address(), prove(10), 0, 1, 1);
+    test.state.execute::<MockVerifier>(&deposit_tx).unwrap();
+
+    // Store the transfer as explicitly not processed, as older states may contain.
+    let transfer_tx = transfer_tx(&from_signer, to_address, prove(2), 1, auctioneer, prove(1));
+    let VAppTransaction::Transfer(transfer) = &transfer_tx else { unreachable!() };
+    let transfer_id = transfer
+        .transfer
+        .body
+        .as_ref()
+        .unwrap()
+        .hash_with_signer(from_signer.address().as_slice())
+        .unwrap();
+    test.state.transactions.insert(transfer_id, false).unwrap();
+
+    // A leaf stored as `false` doesn't count as processed.
+    test.state.execute::<MockVerifier>(&transfer_tx).unwrap();
+    assert_account_balance(&mut test, from_signer.address(), prove(7));
+    assert_account_balance(&mut test, to_address, prove(2));
+    assert_eq!(test.state.transactions.get(&transfer_id).unwrap(), Some(&true));
+}
+
 #[test]
 fn test_transfer_invalid_fee_parsing() {
     let mut test = setup();
@@ -341,3 +341,30 @@ fn test_withdraw_non_prover_only_self_can_withdraw() {
 
     assert!(matches!(result, Err(VAppError::Panic(VAppPanic::OnlyAccountCanWithdraw))));
 }
+
+#[test]
+fn test_max_withdrawable() {
+    let mut test = setup();
+    let account = test.requester.address();
+    let fee = U256::from(10).pow(U256::from(18)); // 1 PROVE, the fee of `withdraw_tx`
+
+    // Accounts without a balance can't withdraw anything.
+    assert_eq!(test.state.max_withdrawable(&account, fee), U256::ZERO);
+
+    // Set up initial balance with deposit (101 PROVE).
+    let initial_balance = U256::from(101) * U256::from(10).pow(U256::from(18));
+    let deposit_tx = deposit_tx(account, initial_balance, 0, 1, 1);
+    test.state.execute::<MockVerifier>(&deposit_tx).unwrap();
+
+    // The fee is set aside from the balance, and saturates at zero if it exceeds it.
+    let max_withdrawable = test.state.max_withdrawable(&account, fee);
+    assert_eq!(max_withdrawable, initial_balance - fee);
+    assert_eq!(test.state.max_withdrawable(&account, initial_balance + fee), U256::ZERO);
+
+    // Withdrawing the maximum amount succeeds and empties the account.
+    let withdraw_tx = withdraw_tx(&test.requester, account, max_withdrawable, 0);
+    let receipt = test.state.execute::<MockVerifier>(&withdraw_tx).unwrap();
+    assert_withdraw_receipt(&receipt, account, max_withdrawable);
+    assert_account_balance(&mut test, account, U256::ZERO);
+    assert_eq!(test.state.max_withdrawable(&account, fee), U256::ZERO);
+}
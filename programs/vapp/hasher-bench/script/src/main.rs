@@ -0,0 +1,42 @@
+//! Compares the cycles spent inside the zkVM computing the root of the same state tree with each
+//! [`spn_vapp_core::merkle::MerkleTreeHasher`].
+//!
+//! Run with `cargo run --release -p vapp-hasher-bench-script`.
+
+use anyhow::{Context, Result};
+use sp1_sdk::{include_elf, Elf, Prover, ProverClient, SP1Stdin};
+
+const HASHER_BENCH_ELF: Elf = include_elf!("vapp-hasher-bench-program");
+
+/// The labels under which the program reports the cycles of each hasher.
+const HASHERS: [&str; 3] = ["keccak", "blake3", "poseidon"];
+
+/// The numbers of accounts to store in the trees.
+const SIZES: [u64; 3] = [16, 256, 4096];
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Initialize the prover client from environment.
+    let client = ProverClient::from_env().await;
+
+    println!("{:>8} {:>10} {:>14} {:>14}", "accounts", "hasher", "cycles", "vs keccak");
+    for n in SIZES {
+        // Execute the program, which computes the root once per hasher.
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&n);
+        let (_, report) = client
+            .execute(HASHER_BENCH_ELF, stdin)
+            .await
+            .with_context(|| format!("failed to execute the program for {n} accounts"))?;
+
+        // Print the cycles of each hasher relative to Keccak.
+        let cycles = |hasher: &str| report.cycle_tracker.get(hasher).copied().unwrap_or_default();
+        let keccak = cycles("keccak");
+        for hasher in HASHERS {
+            let ratio = cycles(hasher) as f64 / keccak.max(1) as f64;
+            println!("{n:>8} {hasher:>10} {:>14} {ratio:>13.2}x", cycles(hasher));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use alloy_primitives::{Address, Keccak256, B256, U256};
+use spn_vapp_core::{
+    hasher::{Blake3, Poseidon},
+    merkle::{MerkleStorage, MerkleTreeHasher},
+    sol::Account,
+    storage::Storage,
+};
+
+pub fn main() {
+    // Read the number of accounts to store in each tree.
+    let n = sp1_zkvm::io::read::<u64>();
+
+    // Compute the root of the same tree with each hasher.
+    let keccak = root::<Keccak256>("keccak", n);
+    let blake3 = root::<Blake3>("blake3", n);
+    let poseidon = root::<Poseidon>("poseidon", n);
+
+    // Commit to the roots so that the hashing can't be optimized away.
+    sp1_zkvm::io::commit(&(keccak, blake3, poseidon));
+}
+
+/// Computes the root of a tree of `n` accounts with the hasher `H`.
+///
+/// The cycles spent hashing are reported under the given label.
+fn root<H: MerkleTreeHasher>(label: &str, n: u64) -> B256 {
+    let mut tree = MerkleStorage::<Address, Account, H>::new();
+    for i in 1..=n {
+        let address = Address::from_word(B256::from(U256::from(i)));
+        let account = Account {
+            balance: U256::from(i),
+            owner: address,
+            delegatedSigner: address,
+            stakerFeeBips: U256::ZERO,
+        };
+        tree.insert(address, account).expect("failed to insert account");
+    }
+
+    println!("cycle-tracker-report-start: {label}");
+    let root = tree.root();
+    println!("cycle-tracker-report-end: {label}");
+    root
+}
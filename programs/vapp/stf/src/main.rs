@@ -5,13 +5,27 @@ use alloy_primitives::Keccak256;
 use alloy_sol_types::SolType;
 use sp1_zkvm::lib::verify::verify_sp1_proof;
 use spn_vapp_core::{
-    errors::VAppError,
     input::VAppStfInput,
     merkle::MerkleStorage,
     sol::StepPublicValues,
+    storage::SignerKey,
+    transactions::{validate_onchain_ordering, VAppTransaction},
     verifier::{VAppVerifier, VAppVerifierError},
 };
 
+#[cfg(all(feature = "blake3", feature = "poseidon"))]
+compile_error!("the `blake3` and `poseidon` features select different tree hashers, enable one");
+
+/// The hasher of the state trees, selected with the `blake3` and `poseidon` features.
+///
+/// Defaults to Keccak, which is what the L1 contract expects.
+#[cfg(not(any(feature = "blake3", feature = "poseidon")))]
+type TreeHasher = Keccak256;
+#[cfg(feature = "blake3")]
+type TreeHasher = spn_vapp_core::hasher::Blake3;
+#[cfg(all(feature = "poseidon", not(feature = "blake3")))]
+type TreeHasher = spn_vapp_core::hasher::Poseidon;
+
 #[derive(Debug, Clone, Default)]
 struct SP1RecursiveVerifier;
 
@@ -28,12 +42,12 @@ impl VAppVerifier for SP1RecursiveVerifier {
 
 pub fn main() {
     // Read the program input.
-    let input = sp1_zkvm::io::read::<VAppStfInput>();
+    let input = sp1_zkvm::io::read::<VAppStfInput<TreeHasher>>();
 
     // Check that the state root is consistent with the state.
     let mut state = input.state;
     assert_eq!(
-        state.root::<Keccak256>(input.accounts_root, input.requests_root),
+        state.root::<Keccak256>(input.accounts_root, input.requests_root, input.signers_root),
         input.root,
         "state root mismatch"
     );
@@ -41,32 +55,40 @@ pub fn main() {
     // Verify the roots against the proofs.
     state
         .accounts
-        .recover::<Keccak256>(input.accounts_root, &input.account_proofs)
+        .recover::<TreeHasher>(input.accounts_root, &input.account_proofs)
         .expect("accounts root mismatch");
 
     state
         .transactions
-        .recover::<Keccak256>(input.requests_root, &input.request_proofs)
+        .recover::<TreeHasher>(input.requests_root, &input.request_proofs)
         .expect("requests root mismatch");
 
+    let signers_root =
+        input.signers_root.unwrap_or_else(MerkleStorage::<SignerKey, bool, TreeHasher>::empty_root);
+    state
+        .signers
+        .recover::<TreeHasher>(signers_root, &input.signer_proofs)
+        .expect("signers root mismatch");
+
     // Apply the state transition function.
+    let (positions, txs): (Vec<i64>, Vec<VAppTransaction>) = input.txs.into_iter().unzip();
+    validate_onchain_ordering(&txs)
+        .unwrap_or_else(|panic| panic!("onchain txs out of order: {panic:?}"));
+    let outcomes = state
+        .execute_batch::<SP1RecursiveVerifier>(&txs)
+        .unwrap_or_else(|(index, panic)| panic!("tx {} panicked: {panic:?}", positions[index]));
+
+    // Collect the receipts of the processed transactions.
     let mut receipts = Vec::new();
-    for (pos, tx) in input.txs {
-        let action = state.execute::<SP1RecursiveVerifier>(&tx);
-        match action {
-            Ok(Some(receipt)) => {
+    for (pos, outcome) in positions.into_iter().zip(outcomes) {
+        match outcome {
+            Ok(receipt) => {
                 println!("tx {pos} processed");
-                receipts.push(receipt);
+                receipts.extend(receipt);
             }
-            Ok(None) => {
-                println!("tx {pos} processed");
-            }
-            Err(VAppError::Revert(revert)) => {
+            Err(revert) => {
                 println!("tx {pos} reverted: {revert:?}");
             }
-            Err(VAppError::Panic(panic)) => {
-                panic!("tx {pos} panicked: {panic:?}");
-            }
         }
     }
 
@@ -85,8 +107,17 @@ pub fn main() {
     )
     .expect("failed to compute new requests root");
 
+    let new_signers_root = MerkleStorage::calculate_new_root_sparse(
+        signers_root,
+        &input.signer_proofs,
+        &state.signers,
+    )
+    .expect("failed to compute new signers root");
+    let new_signers_root = (input.signers_root.is_some() || new_signers_root != signers_root)
+        .then_some(new_signers_root);
+
     // Compute the new state root.
-    let new_root = state.root::<Keccak256>(new_accounts_root, new_requests_root);
+    let new_root = state.root::<Keccak256>(new_accounts_root, new_requests_root, new_signers_root);
 
     // Encode the public values of the program.
     let public_values = StepPublicValues {